@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+/// What the header of a WAV file says about its samples.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WavInfo {
+    pub sample_rate: usize,
+    pub channels: usize,
+    pub bits_per_sample: usize,
+    /// Where the samples are in the file.
+    pub data: Range<usize>,
+}
+
+/// Reads the format and the position of the samples of a WAV file, by walking its RIFF chunks.
+/// For exemple, with a 44100Hz stereo 16 bits WAV with a 44 bytes header, the function returns
+/// WavInfo { sample_rate: 44100, channels: 2, bits_per_sample: 16, data: 44..file length }.
+pub fn parse_wav(binary: &[u8]) -> Result<WavInfo, String> {
+    if binary.len() < 12 || &binary[0..4] != b"RIFF" || &binary[8..12] != b"WAVE" {
+        return Err("The input isn't a WAV file".to_string());
+    }
+    let mut format = None;
+    let mut offset = 12;
+    while offset + 8 <= binary.len() {
+        let kind = &binary[offset..offset + 4];
+        let size = u32::from_le_bytes(binary[offset + 4..offset + 8].try_into().unwrap()) as usize;
+        let start = offset + 8;
+        match kind {
+            b"fmt " => {
+                let chunk = binary
+                    .get(start..start + 16)
+                    .ok_or("The WAV format chunk is truncated")?;
+                let le16 = |i: usize| u16::from_le_bytes([chunk[i], chunk[i + 1]]) as usize;
+                format = Some((
+                    u32::from_le_bytes(chunk[4..8].try_into().unwrap()) as usize,
+                    le16(2),
+                    le16(14),
+                ));
+            }
+            b"data" => {
+                let (sample_rate, channels, bits_per_sample) =
+                    format.ok_or("The WAV data chunk comes before its format chunk")?;
+                // Some writers leave the size of a streamed data chunk at its maximum.
+                let end = (start + size).min(binary.len());
+                return Ok(WavInfo {
+                    sample_rate,
+                    channels,
+                    bits_per_sample,
+                    data: start..end,
+                });
+            }
+            _ => (),
+        }
+        // Chunks are padded to an even size.
+        offset = start + size + size % 2;
+    }
+    Err("The WAV file has no data chunk".to_string())
+}
@@ -0,0 +1,425 @@
+use crate::library::constant_value;
+
+/// Removes the `/* */` and `//` comments of a source file, so their content isn't decoded.
+fn strip_comments(source: &str) -> String {
+    let mut out = String::new();
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("/*") {
+            rest = match comment.find("*/") {
+                Some(end) => &comment[end + 2..],
+                None => "",
+            };
+            out.push(' ');
+        } else if let Some(comment) = rest.strip_prefix("//") {
+            rest = match comment.find('\n') {
+                Some(end) => &comment[end..],
+                None => "",
+            };
+        } else {
+            let c = rest.chars().next().unwrap();
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// Extracts the bytes of a generated array back from its source, by reading every hex literal
+/// outside of comments, in order: `0x00` literals, the `$00` and `000h` literals of the
+/// assemblers, the `16#00#` literals of Ada and the `16#00` ones of Erlang. Decimal numbers such
+/// as the array size are ignored, and so are literals that are part of an identifier, like in
+/// `DATA_0x10`.
+/// For exemple, with source = "const TEST_TXT: [u8; 2] = [0x00, 0xff];", the function
+/// returns vec![0x00, 0xff].
+pub fn decode_array(source: &str) -> Result<Vec<u8>, String> {
+    let source = strip_comments(source);
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    let mut out = Vec::new();
+    let mut rest = source.as_str();
+    while let Some(c) = rest.chars().next() {
+        let based = rest.strip_prefix("16#").map(|digits| {
+            let end = digits
+                .find(|c: char| !c.is_ascii_hexdigit())
+                .unwrap_or(digits.len());
+            // Ada closes the literal with another #, Erlang doesn't.
+            3 + end + usize::from(digits[end..].starts_with('#'))
+        });
+        let token_length = match based {
+            Some(length) => length,
+            None if is_identifier(c) || c == '$' => rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !is_identifier(*c))
+                .map_or(rest.len(), |(i, _)| i),
+            None => c.len_utf8(),
+        };
+        let token = &rest[..token_length];
+        let literal = if let Some(digits) = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+        {
+            Some(digits)
+        } else if based.is_some() {
+            Some(token[3..].trim_end_matches('#'))
+        } else if c == '$' {
+            Some(&token[1..])
+        } else if c.is_ascii_digit() && (token.ends_with('h') || token.ends_with('H')) {
+            Some(&token[..token.len() - 1])
+        } else {
+            None
+        };
+        match literal {
+            Some(digits) if !digits.is_empty() => match u8::from_str_radix(digits, 16) {
+                Ok(byte) => out.push(byte),
+                Err(_) => return Err(format!("{} is not a byte", token)),
+            },
+            _ => (),
+        }
+        rest = &rest[token_length..];
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of the array the constant `name` is initialized with in a C, C++, Rust, C#
+/// or Java source, between braces or brackets, by reading its hex literals like decode_array.
+/// For exemple, with source = "#define TEST_TXT_LEN 2\nconst uint8_t TEST_TXT[] = {0x00, 0xff};"
+/// and name = "TEST_TXT", the function returns vec![0x00, 0xff].
+pub fn decode_initializer(source: &str, name: &str) -> Result<Vec<u8>, String> {
+    let value = constant_value(source, name).ok_or(format!("No constant named {}", name))?;
+    if !value.contains(['{', '[']) {
+        return Err(format!("{} isn't initialized with an array", name));
+    }
+    decode_array(value)
+}
+
+/// Extracts the bytes of a generated string back from its source, by reading every `\xff`
+/// escape outside of comments, in order, as the string outputs escape every byte.
+/// For exemple, with source = "const TEST_TXT = \"\\x00\\xff\";", the function returns
+/// vec![0x00, 0xff].
+pub fn decode_escapes(source: &str) -> Result<Vec<u8>, String> {
+    let source = strip_comments(source);
+    let mut out = Vec::new();
+    for (i, _) in source.match_indices("\\x") {
+        let digits = source.get(i + 2..i + 4).unwrap_or_default();
+        match u8::from_str_radix(digits, 16) {
+            Ok(byte) => out.push(byte),
+            Err(_) => return Err(format!("\\x{} is not a byte", digits)),
+        }
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated decimal array back from its source, by reading every
+/// decimal number between square brackets, in order, as the MATLAB and Fortran outputs write the
+/// bytes in decimal next to integer constants. The negative numbers are signed bytes.
+/// For exemple, with source = "TEST_TXT = uint8([0 255]);", the function returns
+/// vec![0x00, 0xff].
+pub fn decode_decimal(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('[') {
+        let inside = &rest[start + 1..];
+        let end = match inside.find(']') {
+            Some(end) => end,
+            None => return Err("a [ is never closed".to_string()),
+        };
+        let numbers = inside[..end]
+            .split(|c: char| !c.is_ascii_digit() && c != '-')
+            .filter(|number| !number.is_empty());
+        for number in numbers {
+            match number.parse::<i16>() {
+                Ok(byte @ -128..=255) => out.push(byte as u8),
+                _ => return Err(format!("{} is not a byte", number)),
+            }
+        }
+        rest = &inside[end + 1..];
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated memory file back from its source, by reading every word
+/// outside of comments as a hex byte. The `@` addresses of `$readmemh` are skipped.
+/// For exemple, with source = "// TEST_TXT\n00 ff\n", the function returns vec![0x00, 0xff].
+pub fn decode_memh(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for word in strip_comments(source).split_whitespace() {
+        if word.starts_with('@') {
+            continue;
+        }
+        match u8::from_str_radix(word, 16) {
+            Ok(byte) => out.push(byte),
+            Err(_) => return Err(format!("{} is not a byte", word)),
+        }
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated Rust byte string back from its source, by reading the
+/// characters and the escapes of every `b"..."` literal, in order. The other string literals are
+/// skipped, and a backslash ending a line continues the literal without the next indentation.
+/// For exemple, with source = "const TEST_TXT: &[u8; 3] = b\"a\\\"\\xff\";", the function
+/// returns vec![b'a', b'"', 0xff].
+pub fn decode_byte_strings(source: &str) -> Result<Vec<u8>, String> {
+    let source = source.as_bytes();
+    let is_identifier = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < source.len() {
+        let byte_string =
+            source[i..].starts_with(b"b\"") && (i == 0 || !is_identifier(source[i - 1]));
+        if !byte_string && source[i] != b'"' {
+            i += 1;
+            continue;
+        }
+        i += if byte_string { 2 } else { 1 };
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&byte) = source.get(i) else {
+                return Err("a string is never closed".to_string());
+            };
+            i += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&escape) = source.get(i) else {
+                        return Err("a string is never closed".to_string());
+                    };
+                    i += 1;
+                    match escape {
+                        b'n' => bytes.push(b'\n'),
+                        b'r' => bytes.push(b'\r'),
+                        b't' => bytes.push(b'\t'),
+                        b'0' => bytes.push(0),
+                        b'\\' | b'"' | b'\'' => bytes.push(escape),
+                        b'x' => {
+                            let digits = source.get(i..i + 2).unwrap_or_default();
+                            let digits = String::from_utf8_lossy(digits);
+                            match u8::from_str_radix(&digits, 16) {
+                                Ok(byte) => bytes.push(byte),
+                                Err(_) => return Err(format!("\\x{} is not a byte", digits)),
+                            }
+                            i += 2;
+                        }
+                        b'\r' | b'\n' => {
+                            while source.get(i).is_some_and(u8::is_ascii_whitespace) {
+                                i += 1;
+                            }
+                        }
+                        // The escapes of the other strings, such as \u{...}, don't matter.
+                        _ if !byte_string => (),
+                        _ => {
+                            return Err(format!(
+                                "\\{} is not an escape of byte strings",
+                                escape as char
+                            ))
+                        }
+                    }
+                }
+                byte if byte_string && !byte.is_ascii() => {
+                    return Err("byte strings can only hold ASCII characters".to_string())
+                }
+                byte => bytes.push(byte),
+            }
+        }
+        if byte_string {
+            out.extend(bytes);
+        }
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated VHDL array back from its source, by reading every `x"00"`
+/// bit string literal, in order.
+/// For exemple, with source = "constant TEST_TXT : TEST_TXT_type := (x\"00\", x\"ff\");", the
+/// function returns vec![0x00, 0xff].
+pub fn decode_bit_strings(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for (i, _) in source.match_indices("x\"") {
+        let literal = &source[i + 2..];
+        let digits = &literal[..literal.find('"').unwrap_or(literal.len())];
+        match u8::from_str_radix(digits, 16) {
+            Ok(byte) => out.push(byte),
+            Err(_) => return Err(format!("x\"{}\" is not a byte", digits)),
+        }
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated printf back from its source, by reading every `\377` octal
+/// escape, in order, as the shell outputs escape every byte.
+/// For exemple, with source = "TEST_TXT() { printf '\\000\\377'; }", the function returns
+/// vec![0x00, 0xff].
+pub fn decode_octal_escapes(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for (i, _) in source.match_indices('\\') {
+        let digits = source.get(i + 1..i + 4).unwrap_or_default();
+        match u8::from_str_radix(digits, 8) {
+            Ok(byte) => out.push(byte),
+            Err(_) => return Err(format!("\\{} is not a byte", digits)),
+        }
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated SQL statement back from its source, by reading the hex
+/// digits of its `X'00ff'` or `'\x00ff'` literals, in order. The `--` comments are skipped.
+/// For exemple, with source = "INSERT INTO blobs(name, data) VALUES ('TEST_TXT', X'00ff');",
+/// the function returns vec![0x00, 0xff].
+pub fn decode_hex_strings(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for line in source.lines().filter(|line| !line.starts_with("--")) {
+        // The literal comes after the name, which can itself end with an X.
+        let start = match (line.rfind("'\\x"), line.rfind("X'")) {
+            (Some(start), _) => start + 3,
+            (None, Some(start)) => start + 2,
+            (None, None) => continue,
+        };
+        let digits = &line[start..];
+        out.extend(decode_hex_digits(
+            &digits[..digits.find('\'').unwrap_or(digits.len())],
+        )?);
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated Tcl script back from its source, by reading the hex digits
+/// given to its `binary decode hex` commands, in order.
+/// For exemple, with source = "set TEST_TXT [binary decode hex \"00ff\"]", the function returns
+/// vec![0x00, 0xff].
+pub fn decode_tcl_hex(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for (i, command) in source.match_indices("binary decode hex \"") {
+        let digits = &source[i + command.len()..];
+        out.extend(decode_hex_digits(
+            &digits[..digits.find('"').unwrap_or(digits.len())],
+        )?);
+    }
+    Ok(out)
+}
+
+/// Extracts the bytes of a generated Solidity constant back from its source, by reading the hex
+/// digits of its `hex"00ff"` literals outside of comments, in order.
+/// For exemple, with source = "bytes constant TEST_TXT =\n    hex\"00ff\";", the function
+/// returns vec![0x00, 0xff].
+pub fn decode_solidity_hex(source: &str) -> Result<Vec<u8>, String> {
+    let source = strip_comments(source);
+    let mut out = Vec::new();
+    for (i, literal) in source.match_indices("hex\"") {
+        let digits = &source[i + literal.len()..];
+        out.extend(decode_hex_digits(
+            &digits[..digits.find('"').unwrap_or(digits.len())],
+        )?);
+    }
+    Ok(out)
+}
+
+/// Reads hex digits as pairs, ignoring the whitespace between them.
+pub fn decode_hex_digits(digits: &str) -> Result<Vec<u8>, String> {
+    let digits = digits
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("{} is not a hex digit", c));
+    }
+    if digits.len() % 2 != 0 {
+        return Err(format!(
+            "{} hex digits can't be read as pairs",
+            digits.len()
+        ));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| format!("{} is not a byte", &digits[i..i + 2]))
+        })
+        .collect()
+}
+
+/// Extracts the bytes of a generated data segment back from its source, by reading the `\ff`
+/// escapes of its lines that are strings, in order, as the WAT output escapes every byte.
+/// For exemple, with source = "(data $TEST_TXT (i32.const 0)\n    \"\\00\\ff\"\n)", the
+/// function returns vec![0x00, 0xff].
+pub fn decode_wat_strings(source: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for line in source.lines().filter(|line| line.trim().starts_with('"')) {
+        for escape in line.trim().trim_matches('"').split('\\').skip(1) {
+            match u8::from_str_radix(escape, 16) {
+                Ok(byte) => out.push(byte),
+                Err(_) => return Err(format!("\\{} is not a byte", escape)),
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Reads a hex dump back into the bytes it shows: the dumps of the hex conversion type and of
+/// `hexdump -C`, with a `|` before their text column, and the dumps of xxd, with a `:` after
+/// their offsets and two spaces before their text column. The offsets must follow each other
+/// from the first one, except after a `*` line, which repeats the line before it up to the next
+/// offset, as long as the dump doesn't span more than `max_size` bytes. A line with an offset
+/// alone gives the end of the dump.
+/// For exemple, with source = "00000000: 0001 02ff  ....\n" and max_size = 16, the function
+/// returns vec![0x00, 0x01, 0x02, 0xff].
+pub fn decode_hexdump(source: &str, max_size: usize) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut first_offset = None;
+    let mut previous: Vec<u8> = Vec::new();
+    let mut squeezed = false;
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "*" {
+            squeezed = true;
+            continue;
+        }
+        let error = |message: String| format!("line {}: {}", number + 1, message);
+        let digits = line.len()
+            - line
+                .trim_start_matches(|c: char| c.is_ascii_hexdigit())
+                .len();
+        let offset = usize::from_str_radix(&line[..digits], 16)
+            .map_err(|_| error(format!("{} doesn't start with an offset", line)))?;
+        let rest = &line[digits..];
+        let hex = match rest.strip_prefix(':') {
+            Some(rest) => rest.trim_start().split("  ").next().unwrap_or(""),
+            None => rest.split('|').next().unwrap_or(""),
+        };
+        let bytes = hex
+            .split_whitespace()
+            .map(|group| decode_hex_digits(group).map_err(&error))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+
+        let start = *first_offset.get_or_insert(offset);
+        if squeezed && !previous.is_empty() {
+            let size = offset.saturating_sub(start);
+            if size > max_size {
+                return Err(error(format!(
+                    "the dump spans {} bytes, more than the --max-size of {} bytes",
+                    size, max_size
+                )));
+            }
+            while out.len() < size {
+                out.extend_from_slice(&previous);
+            }
+            squeezed = false;
+        }
+        let expected = start
+            .checked_add(out.len())
+            .ok_or_else(|| error(format!("the dump overflows past the offset 0x{:x}", start)))?;
+        if offset != expected {
+            return Err(error(format!(
+                "expected the offset 0x{:x}, found 0x{:x}",
+                expected, offset
+            )));
+        }
+        out.extend_from_slice(&bytes);
+        previous = bytes;
+    }
+    Ok(out)
+}
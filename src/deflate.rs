@@ -0,0 +1,376 @@
+/// The base lengths of the length codes 257 to 285, and their number of extra bits.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// The base distances of the distance codes 0 to 29, and their number of extra bits.
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// The order the code length code lengths of a dynamic block are stored in.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Reads a deflate stream bit by bit, least significant bit first.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn bit(&mut self) -> Result<u32, String> {
+        let byte = self
+            .data
+            .get(self.position / 8)
+            .ok_or("The compressed data is truncated")?;
+        let bit = (byte >> (self.position % 8)) & 1;
+        self.position += 1;
+        Ok(bit as u32)
+    }
+
+    fn bits(&mut self, count: u8) -> Result<u32, String> {
+        let mut value = 0;
+        for i in 0..count {
+            value |= self.bit()? << i;
+        }
+        Ok(value)
+    }
+
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+}
+
+/// A canonical Huffman code, decoded one bit at a time.
+struct Huffman {
+    /// The number of codes of each length.
+    counts: [u16; 16],
+    /// The symbols ordered by code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Builds the code from the code length of each symbol, 0 meaning the symbol is unused.
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for length in lengths {
+            counts[*length as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols = Vec::new();
+        for length in 1..16 {
+            for (symbol, _) in lengths.iter().enumerate().filter(|(_, l)| **l == length) {
+                symbols.push(symbol as u16);
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for count in &self.counts[1..] {
+            code |= reader.bit()? as i32;
+            let count = *count as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("Invalid Huffman code in the compressed data".to_string())
+    }
+}
+
+/// Decompresses raw deflate data (RFC 1951).
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = BitReader { data, position: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = reader.bit()? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align();
+                let start = reader.position / 8;
+                let header = data
+                    .get(start..start + 4)
+                    .ok_or("The compressed data is truncated")?;
+                let length = u16::from_le_bytes([header[0], header[1]]) as usize;
+                let stored = data
+                    .get(start + 4..start + 4 + length)
+                    .ok_or("The compressed data is truncated")?;
+                out.extend_from_slice(stored);
+                reader.position = (start + 4 + length) * 8;
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                let literals = Huffman::new(&lengths);
+                let distances = Huffman::new(&[5; 30]);
+                inflate_block(&mut reader, &literals, &distances, &mut out)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &literals, &distances, &mut out)?;
+            }
+            _ => return Err("Invalid block type in the compressed data".to_string()),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+/// Reads the literal/length and distance codes at the start of a dynamic block.
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for i in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[*i] = reader.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::new();
+    while lengths.len() < literal_count + distance_count {
+        let (length, repeat) = match code_lengths.decode(reader)? {
+            16 => {
+                let previous = *lengths
+                    .last()
+                    .ok_or("Invalid code lengths in the compressed data")?;
+                (previous, 3 + reader.bits(2)?)
+            }
+            17 => (0, 3 + reader.bits(3)?),
+            18 => (0, 11 + reader.bits(7)?),
+            length => (length as u8, 1),
+        };
+        lengths.extend(std::iter::repeat_n(length, repeat as usize));
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err("Invalid code lengths in the compressed data".to_string());
+    }
+    Ok((
+        Huffman::new(&lengths[..literal_count]),
+        Huffman::new(&lengths[literal_count..]),
+    ))
+}
+
+/// Decodes the symbols of a compressed block until its end of block symbol.
+fn inflate_block(
+    reader: &mut BitReader,
+    literals: &Huffman,
+    distances: &Huffman,
+    out: &mut Vec<u8>,
+) -> Result<(), String> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+        if symbol < 256 {
+            out.push(symbol as u8);
+            continue;
+        }
+        if symbol == 256 {
+            return Ok(());
+        }
+        let code = symbol - 257;
+        if code >= LENGTH_BASE.len() {
+            return Err("Invalid length in the compressed data".to_string());
+        }
+        let length = LENGTH_BASE[code] as usize + reader.bits(LENGTH_EXTRA[code])? as usize;
+        let code = distances.decode(reader)? as usize;
+        if code >= DISTANCE_BASE.len() {
+            return Err("Invalid distance in the compressed data".to_string());
+        }
+        let distance = DISTANCE_BASE[code] as usize + reader.bits(DISTANCE_EXTRA[code])? as usize;
+        if distance > out.len() {
+            return Err("Invalid distance in the compressed data".to_string());
+        }
+        // Byte by byte, as the copy may overlap what it produces.
+        for _ in 0..length {
+            out.push(out[out.len() - distance]);
+        }
+    }
+}
+
+/// Decompresses zlib data (RFC 1950), skipping its header and ignoring its checksum.
+pub fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < 2
+        || data[0] & 0x0f != 8
+        || !(data[0] as u16 * 256 + data[1] as u16).is_multiple_of(31)
+    {
+        return Err("Invalid zlib header".to_string());
+    }
+    if data[1] & 0x20 != 0 {
+        return Err("zlib preset dictionaries are not supported".to_string());
+    }
+    inflate(&data[2..])
+}
+
+/// Writes a deflate stream bit by bit, least significant bit first.
+struct BitWriter {
+    out: Vec<u8>,
+    bits: u32,
+    count: u8,
+}
+
+impl BitWriter {
+    fn bits(&mut self, value: u32, count: u8) {
+        for i in 0..count {
+            self.bits |= ((value >> i) & 1) << self.count;
+            self.count += 1;
+            if self.count == 8 {
+                self.out.push(self.bits as u8);
+                self.bits = 0;
+                self.count = 0;
+            }
+        }
+    }
+
+    /// Writes a Huffman code, which unlike the other values is stored most significant bit first.
+    fn code(&mut self, code: u32, length: u8) {
+        for i in (0..length).rev() {
+            self.bits((code >> i) & 1, 1);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.bits as u8);
+        }
+        self.out
+    }
+}
+
+/// Writes a literal or length symbol with the fixed Huffman code.
+fn write_fixed_symbol(writer: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => writer.code(0x30 + symbol, 8),
+        144..=255 => writer.code(0x190 + symbol - 144, 9),
+        256..=279 => writer.code(symbol - 256, 7),
+        _ => writer.code(0xc0 + symbol - 280, 8),
+    }
+}
+
+/// Compresses data to raw deflate (RFC 1951), in a single block with the fixed Huffman codes
+/// and matches found with hash chains, which is a lot simpler than zlib and not much bigger.
+/// Data that doesn't compress is stored as is instead.
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let compressed = deflate_fixed(data);
+    if compressed.len() <= data.len() + 5 * data.len().div_ceil(65535).max(1) {
+        return compressed;
+    }
+    let mut out = Vec::new();
+    let blocks = data.chunks(65535).collect::<Vec<&[u8]>>();
+    for (i, block) in blocks.iter().enumerate() {
+        let length = block.len() as u16;
+        out.push((i + 1 == blocks.len()) as u8);
+        out.extend_from_slice(&length.to_le_bytes());
+        out.extend_from_slice(&(!length).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out
+}
+
+/// Compresses data to a single deflate block with the fixed Huffman codes.
+fn deflate_fixed(data: &[u8]) -> Vec<u8> {
+    const WINDOW: usize = 32768;
+    const MAX_CHAIN: usize = 128;
+    let mut writer = BitWriter {
+        out: Vec::new(),
+        bits: 0,
+        count: 0,
+    };
+    writer.bits(1, 1);
+    writer.bits(1, 2);
+
+    // The last position of each 3 bytes hash, and the previous position with the same hash.
+    let mut head = vec![usize::MAX; 1 << 15];
+    let mut previous = vec![usize::MAX; data.len()];
+    let hash = |i: usize| {
+        ((data[i] as usize) << 10 ^ (data[i + 1] as usize) << 5 ^ data[i + 2] as usize) & 0x7fff
+    };
+    let insert = |i: usize, head: &mut [usize], previous: &mut [usize]| {
+        if i + 2 < data.len() {
+            previous[i] = head[hash(i)];
+            head[hash(i)] = i;
+        }
+    };
+
+    let mut i = 0;
+    while i < data.len() {
+        let (mut best_length, mut best_distance) = (0, 0);
+        if i + 2 < data.len() {
+            let mut candidate = head[hash(i)];
+            let mut chain = 0;
+            while candidate != usize::MAX && i - candidate <= WINDOW && chain < MAX_CHAIN {
+                let length = data[candidate..]
+                    .iter()
+                    .zip(&data[i..])
+                    .take(258)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if length > best_length {
+                    (best_length, best_distance) = (length, i - candidate);
+                    if length == 258 {
+                        break;
+                    }
+                }
+                candidate = previous[candidate];
+                chain += 1;
+            }
+        }
+
+        if best_length < 3 {
+            write_fixed_symbol(&mut writer, data[i] as u32);
+            insert(i, &mut head, &mut previous);
+            i += 1;
+            continue;
+        }
+        let code = LENGTH_BASE
+            .iter()
+            .rposition(|base| *base as usize <= best_length)
+            .unwrap();
+        write_fixed_symbol(&mut writer, 257 + code as u32);
+        writer.bits(
+            (best_length - LENGTH_BASE[code] as usize) as u32,
+            LENGTH_EXTRA[code],
+        );
+        let code = DISTANCE_BASE
+            .iter()
+            .rposition(|base| *base as usize <= best_distance)
+            .unwrap();
+        writer.code(code as u32, 5);
+        writer.bits(
+            (best_distance - DISTANCE_BASE[code] as usize) as u32,
+            DISTANCE_EXTRA[code],
+        );
+        for j in i..i + best_length {
+            insert(j, &mut head, &mut previous);
+        }
+        i += best_length;
+    }
+    write_fixed_symbol(&mut writer, 256);
+    writer.finish()
+}
+
+/// Compresses data to a gzip file (RFC 1952), without a name and with a zero timestamp so the
+/// same input always gives the same bytes.
+pub fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+    out.extend_from_slice(&deflate(data));
+    out.extend_from_slice(&crate::hash::crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
@@ -0,0 +1,231 @@
+use crate::library::long_path;
+use std::path::{Path, PathBuf};
+
+/// Which files of a directory are converted.
+#[derive(Clone, Debug, Default)]
+pub struct DirectoryFilter {
+    /// Only the files matching one of these globs are kept, all of them if there is none.
+    pub include: Vec<String>,
+    /// The files and directories matching one of these globs are skipped.
+    pub exclude: Vec<String>,
+    /// Whether the files and directories starting with a dot are kept.
+    pub hidden: bool,
+    /// Whether the .gitignore files of the directory are followed, and its .git skipped.
+    pub gitignore: bool,
+    /// How deep the directory is walked, 1 being only its own files.
+    pub max_depth: Option<usize>,
+}
+
+/// A pattern of a .gitignore file.
+struct IgnoreRule {
+    /// The directory of the .gitignore file, relative to the walked directory.
+    base: String,
+    pattern: String,
+    negated: bool,
+    directories_only: bool,
+}
+
+/// Lists the files of a directory and of its subdirectories kept by the filter, sorted by path.
+/// Each file is returned with its path relative to the directory, both as a path and as a text
+/// with `/` separators.
+/// For exemple, with a directory containing `logo.png`, `.cache` and `icons/home.png`, and the
+/// default filter, the function returns the files "icons/home.png" and "logo.png".
+pub fn list_directory(
+    directory: &Path,
+    filter: &DirectoryFilter,
+) -> Result<Vec<(PathBuf, String)>, std::io::Error> {
+    let mut files = Vec::new();
+    walk(directory, "", 1, filter, &mut Vec::new(), &mut files)?;
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+/// Whether an input is a glob pattern such as `assets/*.png` rather than a file, which can have
+/// a `*` or a `?` in its name on unix.
+pub fn is_glob(input: &Path) -> bool {
+    input.to_string_lossy().contains(['*', '?']) && !long_path(input).exists()
+}
+
+/// Expands a glob pattern into the files it matches that are kept by the filter, sorted by path,
+/// without relying on the shell so it works the same on Windows, where `\` separators are
+/// accepted too. The directory before the first wildcard is walked, as deep as the pattern goes.
+/// Each file is returned with its path, and its path relative to the walked directory as a text
+/// with `/` separators.
+/// For exemple, with pattern = "assets/*.png" and a directory containing `assets/logo.png`,
+/// `assets/logo.jpg` and `assets/icons/home.png`, the function returns the file
+/// "assets/logo.png" with the path "logo.png".
+pub fn expand_glob(
+    pattern: &str,
+    filter: &DirectoryFilter,
+) -> Result<Vec<(PathBuf, String)>, std::io::Error> {
+    let pattern = match cfg!(windows) {
+        true => pattern.replace('\\', "/"),
+        false => pattern.to_string(),
+    };
+    let components = pattern.split('/').collect::<Vec<&str>>();
+    let literal = components
+        .iter()
+        .take_while(|component| !component.contains(['*', '?']))
+        .count();
+    let directory = match components[..literal].join("/") {
+        base if base.is_empty() && pattern.starts_with('/') => PathBuf::from("/"),
+        base if base.is_empty() => PathBuf::from("."),
+        base => PathBuf::from(base),
+    };
+    let glob = components[literal..].join("/");
+    let filter = DirectoryFilter {
+        // A leading `/` matches the glob against the whole relative path.
+        include: vec![format!("/{}", glob)],
+        max_depth: match glob.contains("**") {
+            true => filter.max_depth,
+            false => Some(components.len() - literal),
+        },
+        ..filter.clone()
+    };
+    let files = list_directory(&directory, &filter)?;
+    Ok(files
+        .into_iter()
+        .map(|(file, path)| match literal {
+            0 => (file, path),
+            _ => (directory.join(file), path),
+        })
+        .collect())
+}
+
+fn walk(
+    root: &Path,
+    relative: &str,
+    depth: usize,
+    filter: &DirectoryFilter,
+    rules: &mut Vec<IgnoreRule>,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<(), std::io::Error> {
+    let directory = long_path(&root.join(relative));
+    let inherited_rules = rules.len();
+    if filter.gitignore {
+        if let Ok(gitignore) = std::fs::read_to_string(directory.join(".gitignore")) {
+            rules.extend(parse_gitignore(&gitignore, relative));
+        }
+    }
+
+    for entry in std::fs::read_dir(&directory)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = if relative.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{}", relative, name)
+        };
+        let file_type = entry.file_type()?;
+        // Symbolic links to files are followed, but not the ones to directories, which can loop.
+        let is_directory = file_type.is_dir();
+        let is_file = file_type.is_file() || (file_type.is_symlink() && entry.path().is_file());
+
+        if (name.starts_with('.') && !filter.hidden)
+            || (filter.gitignore && name == ".git")
+            || is_ignored(rules, &path, &name, is_directory)
+            || filter.exclude.iter().any(|p| path_matches(p, &path, &name))
+        {
+            continue;
+        }
+        if is_directory {
+            if filter.max_depth.is_none_or(|max_depth| depth < max_depth) {
+                walk(root, &path, depth + 1, filter, rules, files)?;
+            }
+        } else if is_file
+            && (filter.include.is_empty()
+                || filter.include.iter().any(|p| path_matches(p, &path, &name)))
+        {
+            let mut file = PathBuf::from(relative);
+            file.push(entry.file_name());
+            files.push((file, path));
+        }
+    }
+    rules.truncate(inherited_rules);
+    Ok(())
+}
+
+/// Reads the patterns of a .gitignore file found in the `base` directory.
+fn parse_gitignore(gitignore: &str, base: &str) -> Vec<IgnoreRule> {
+    let mut rules = Vec::new();
+    for line in gitignore.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(line) => (true, line),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (directories_only, pattern) = match line.strip_suffix('/') {
+            Some(pattern) => (true, pattern),
+            None => (false, line),
+        };
+        rules.push(IgnoreRule {
+            base: base.to_string(),
+            pattern: pattern.to_string(),
+            negated,
+            directories_only,
+        });
+    }
+    rules
+}
+
+/// Whether a file or directory is ignored by the .gitignore rules, the last matching rule
+/// winning as in git.
+fn is_ignored(rules: &[IgnoreRule], path: &str, name: &str, is_directory: bool) -> bool {
+    let mut ignored = false;
+    for rule in rules {
+        if rule.directories_only && !is_directory {
+            continue;
+        }
+        let path = match rule.base.as_str() {
+            "" => path,
+            base => match path.strip_prefix(base).and_then(|p| p.strip_prefix('/')) {
+                Some(path) => path,
+                None => continue,
+            },
+        };
+        if path_matches(&rule.pattern, path, name) {
+            ignored = !rule.negated;
+        }
+    }
+    ignored
+}
+
+/// Matches a file against a glob: a glob with a `/` is matched against its relative path, other
+/// globs against its name only, so `*.png` matches every PNG file of the tree.
+fn path_matches(glob: &str, path: &str, name: &str) -> bool {
+    if glob.contains('/') {
+        glob_match(glob.trim_start_matches('/'), path)
+    } else {
+        glob_match(glob, name)
+    }
+}
+
+/// Matches a text against a glob, where `*` matches anything but a `/`, `**` matches anything
+/// and `?` matches one character other than a `/`.
+/// For exemple, with glob = "assets/**/*.png", the function returns true for
+/// "assets/logo.png" and "assets/icons/home.png", and false for "assets/logo.jpg".
+pub fn glob_match(glob: &str, text: &str) -> bool {
+    fn matches(glob: &[char], text: &[char]) -> bool {
+        match glob {
+            [] => text.is_empty(),
+            ['*', '*', rest @ ..] => {
+                // `**/` also matches no directory at all.
+                (rest.first() == Some(&'/') && matches(&rest[1..], text))
+                    || (0..=text.len()).any(|i| matches(rest, &text[i..]))
+            }
+            ['*', rest @ ..] => (0..=text.len())
+                .take_while(|&i| i == 0 || text[i - 1] != '/')
+                .any(|i| matches(rest, &text[i..])),
+            ['?', rest @ ..] => {
+                text.first().is_some_and(|c| *c != '/') && matches(rest, &text[1..])
+            }
+            [c, rest @ ..] => text.first() == Some(c) && matches(rest, &text[1..]),
+        }
+    }
+    let glob = glob.chars().collect::<Vec<char>>();
+    let text = text.chars().collect::<Vec<char>>();
+    matches(&glob, &text)
+}
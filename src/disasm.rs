@@ -0,0 +1,97 @@
+use capstone::prelude::*;
+
+/// The architectures accepted by `--arch`.
+pub const ARCHITECTURES: &[&str] = &[
+    "x86", "x86_16", "x86_64", "arm", "thumb", "arm64", "mips", "mips64", "riscv32", "riscv64",
+];
+
+/// Builds a Capstone disassembler for one of the `ARCHITECTURES`.
+fn disassembler(arch: &str) -> Result<Capstone, String> {
+    let cs = match arch.to_ascii_lowercase().trim() {
+        "x86" | "i386" | "x86_32" => Capstone::new()
+            .x86()
+            .mode(arch::x86::ArchMode::Mode32)
+            .build(),
+        "x86_16" | "8086" => Capstone::new()
+            .x86()
+            .mode(arch::x86::ArchMode::Mode16)
+            .build(),
+        "x86_64" | "x64" | "amd64" => Capstone::new()
+            .x86()
+            .mode(arch::x86::ArchMode::Mode64)
+            .build(),
+        "arm" | "arm32" => Capstone::new().arm().mode(arch::arm::ArchMode::Arm).build(),
+        "thumb" => Capstone::new()
+            .arm()
+            .mode(arch::arm::ArchMode::Thumb)
+            .build(),
+        "arm64" | "aarch64" => Capstone::new()
+            .arm64()
+            .mode(arch::arm64::ArchMode::Arm)
+            .build(),
+        "mips" | "mips32" => Capstone::new()
+            .mips()
+            .mode(arch::mips::ArchMode::Mips32)
+            .build(),
+        "mips64" => Capstone::new()
+            .mips()
+            .mode(arch::mips::ArchMode::Mips64)
+            .build(),
+        "riscv32" | "rv32" => Capstone::new()
+            .riscv()
+            .mode(arch::riscv::ArchMode::RiscV32)
+            .build(),
+        "riscv64" | "rv64" | "riscv" => Capstone::new()
+            .riscv()
+            .mode(arch::riscv::ArchMode::RiscV64)
+            .build(),
+        _ => {
+            return Err(format!(
+                "Unknown architecture: {}, expected one of {}",
+                arch,
+                ARCHITECTURES.join(", ")
+            ))
+        }
+    };
+    cs.map_err(|e| e.to_string())
+}
+
+/// Disassembles an array of bytes loaded at `address` for the given architecture.
+/// For exemple, with binary = &[0x55, 0x48, 0x89, 0xe5] and arch = "x86_64", the function returns:
+/// 00000000  55                     push rbp
+/// 00000001  48 89 e5               mov rbp, rsp
+pub fn binary_to_disasm(binary: &[u8], arch: &str, address: u64) -> Result<String, String> {
+    let cs = disassembler(arch)?;
+    let instructions = cs.disasm_all(binary, address).map_err(|e| e.to_string())?;
+
+    let mut out = String::new();
+    let mut end = address;
+    for instruction in instructions.iter() {
+        let bytes = instruction
+            .bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let line = format!(
+            "{:08x}  {:<22} {} {}",
+            instruction.address(),
+            bytes,
+            instruction.mnemonic().unwrap_or(""),
+            instruction.op_str().unwrap_or("")
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+        end = instruction.address() + instruction.bytes().len() as u64;
+    }
+
+    let decoded = (end - address) as usize;
+    if decoded < binary.len() {
+        out.push_str(&format!(
+            "{:08x}  ({} undecodable byte(s) left)\n",
+            end,
+            binary.len() - decoded
+        ));
+    }
+    Ok(out)
+}
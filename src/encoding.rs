@@ -0,0 +1,190 @@
+/// The alphabet of base64.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The alphabet of base64 for URLs and file names, where `-` and `_` replace `+` and `/`.
+const BASE64_URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes an array of bytes in base64, padded with `=`, with the URL-safe alphabet if
+/// `url_safe` is set.
+/// For exemple, with binary = b"abcd" and url_safe = false, the function returns "YWJjZA==".
+pub fn to_base64(binary: &[u8], url_safe: bool) -> String {
+    let alphabet = match url_safe {
+        true => BASE64_URL_ALPHABET,
+        false => BASE64_ALPHABET,
+    };
+    let mut out = String::with_capacity(binary.len().div_ceil(3) * 4);
+    for chunk in binary.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(alphabet[(group >> (18 - 6 * i)) as usize & 0x3f] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+/// Decodes base64 text, in the standard or the URL-safe alphabet. The whitespace, such as the
+/// line breaks of wrapped base64, is ignored and the `=` padding is optional.
+/// For exemple, with text = "YWJj\nZA==\n", the function returns vec![b'a', b'b', b'c', b'd'].
+pub fn from_base64(text: &str) -> Result<Vec<u8>, String> {
+    let text = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    let digits = text.trim_end_matches('=');
+    if text.len() - digits.len() > 2 || digits.len() % 4 == 1 {
+        return Err("the length of the base64 text isn't valid".to_string());
+    }
+    let values = digits
+        .chars()
+        .map(|c| match c {
+            '+' | '-' => Ok(62),
+            '/' | '_' => Ok(63),
+            c => BASE64_ALPHABET
+                .iter()
+                .position(|digit| *digit as char == c)
+                .map(|value| value as u32)
+                .ok_or(format!("{} is not a base64 character", c)),
+        })
+        .collect::<Result<Vec<u32>, String>>()?;
+    let mut out = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (i, value)| group | value << (18 - 6 * i));
+        // A chunk of n digits holds n - 1 bytes.
+        out.extend_from_slice(&group.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(out)
+}
+
+/// The alphabet of base32.
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes an array of bytes in base32, in uppercase and padded with `=`.
+/// For exemple, with binary = b"abcd", the function returns "MFRGGZA=".
+pub fn to_base32(binary: &[u8]) -> String {
+    let mut out = String::with_capacity(binary.len().div_ceil(5) * 8);
+    for chunk in binary.chunks(5) {
+        let group = chunk.iter().enumerate().fold(0u64, |group, (i, byte)| {
+            group | (*byte as u64) << (32 - 8 * i)
+        });
+        // Each character holds 5 bits, the ones holding none of the chunk are padding.
+        let characters = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            match i < characters {
+                true => out.push(BASE32_ALPHABET[(group >> (35 - 5 * i)) as usize & 0x1f] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+/// The alphabet of Z85, the base85 of ZeroMQ that can be quoted in sources.
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Writes a group of 4 bytes as 5 base85 digits, most significant first, with `alphabet`.
+fn base85_digits(group: u32, alphabet: impl Fn(u32) -> char) -> impl Iterator<Item = char> {
+    (0..5)
+        .rev()
+        .map(move |i| alphabet(group / 85u32.pow(i) % 85))
+}
+
+/// Encodes an array of bytes in Ascii85, the base85 of PostScript and PDF, without its `<~` `~>`
+/// delimiters. Groups of 4 zeros are written `z`, and the last group is shortened as its bytes.
+/// For exemple, with binary = b"abcd", the function returns "@:E_W".
+pub fn to_ascii85(binary: &[u8]) -> String {
+    let mut out = String::with_capacity(binary.len().div_ceil(4) * 5);
+    for chunk in binary.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let group = u32::from_be_bytes(group);
+        if group == 0 && chunk.len() == 4 {
+            out.push('z');
+            continue;
+        }
+        let digits = base85_digits(group, |digit| (b'!' + digit as u8) as char);
+        out.extend(digits.take(chunk.len() + 1));
+    }
+    out
+}
+
+/// Encodes an array of bytes in Z85, whose size must be a multiple of 4.
+/// For exemple, with binary = &[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b], the function
+/// returns "HelloWorld".
+pub fn to_z85(binary: &[u8]) -> Result<String, String> {
+    if !binary.len().is_multiple_of(4) {
+        return Err(format!(
+            "Z85 only encodes a multiple of 4 bytes, not {} bytes",
+            binary.len()
+        ));
+    }
+    Ok(binary
+        .chunks(4)
+        .flat_map(|chunk| {
+            let group = u32::from_be_bytes(chunk.try_into().unwrap());
+            base85_digits(group, |digit| Z85_ALPHABET[digit as usize] as char)
+        })
+        .collect())
+}
+
+/// The number of bytes of each line of uuencode, written `M`.
+const UUENCODE_BYTES_PER_LINE: usize = 45;
+
+/// Writes a 6 bits value as a uuencode character, 0 being a backquote rather than a space that
+/// could be trimmed.
+fn uuencode_char(value: u32) -> char {
+    match value & 0x3f {
+        0 => '`',
+        value => (b' ' + value as u8) as char,
+    }
+}
+
+/// Encodes an array of bytes with uuencode, between a `begin` line giving the permissions `mode`
+/// and the name of the file, and an `end` line.
+/// For exemple, with binary = b"abc", name = "test.txt" and mode = 0o644, the function returns:
+/// begin 644 test.txt
+/// #86)C
+/// `
+/// end
+pub fn to_uuencode(binary: &[u8], name: &str, mode: u32) -> String {
+    let mut out = format!("begin {:03o} {}\n", mode, name);
+    for line in binary.chunks(UUENCODE_BYTES_PER_LINE) {
+        out.push(uuencode_char(line.len() as u32));
+        for chunk in line.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let group = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+            out.extend((0..4).rev().map(|i| uuencode_char(group >> (6 * i))));
+        }
+        out.push('\n');
+    }
+    out.push_str("`\nend\n");
+    out
+}
+
+/// Splits an encoded text into lines of `columns` characters, each ended by a line break.
+/// Nothing is split when `columns` is 0.
+/// For exemple, with text = "YWJjZA==" and columns = 4, the function returns "YWJj\nZA==\n".
+pub fn wrap_text(text: &str, columns: usize) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    if columns == 0 {
+        return format!("{}\n", text);
+    }
+    // The encodings only give ASCII.
+    text.as_bytes()
+        .chunks(columns)
+        .map(|line| format!("{}\n", String::from_utf8_lossy(line)))
+        .collect()
+}
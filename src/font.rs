@@ -0,0 +1,90 @@
+/// Recognizes a font by its magic number: TrueType, OpenType, TrueType collections and BDF
+/// bitmap fonts.
+pub fn is_font(binary: &[u8]) -> bool {
+    [
+        &b"\x00\x01\x00\x00"[..],
+        b"true",
+        b"OTTO",
+        b"ttcf",
+        b"STARTFONT",
+    ]
+    .iter()
+    .any(|magic| binary.starts_with(magic))
+}
+
+fn be16(binary: &[u8], offset: usize) -> Result<usize, String> {
+    match binary.get(offset..offset + 2) {
+        Some(b) => Ok(u16::from_be_bytes([b[0], b[1]]) as usize),
+        None => Err("The font is truncated".to_string()),
+    }
+}
+
+fn be32(binary: &[u8], offset: usize) -> Result<usize, String> {
+    match binary.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize),
+        None => Err("The font is truncated".to_string()),
+    }
+}
+
+/// Reads the family name of a font, from the `name` table of TrueType/OpenType fonts (the
+/// first font of a collection) or the FAMILY_NAME property of BDF fonts.
+/// For exemple, with the DejaVu Sans TTF, the function returns Some("DejaVu Sans").
+pub fn font_family(binary: &[u8]) -> Result<Option<String>, String> {
+    if binary.starts_with(b"STARTFONT") {
+        let text = String::from_utf8_lossy(binary);
+        return Ok(text.lines().find_map(|line| {
+            line.strip_prefix("FAMILY_NAME ")
+                .map(|name| name.trim().trim_matches('"').to_string())
+        }));
+    }
+
+    let font = if binary.starts_with(b"ttcf") {
+        be32(binary, 12)?
+    } else {
+        0
+    };
+    let table_count = be16(binary, font + 4)?;
+    let mut name_table = None;
+    for i in 0..table_count {
+        let record = font + 12 + i * 16;
+        if binary.get(record..record + 4) == Some(b"name") {
+            name_table = Some(be32(binary, record + 8)?);
+        }
+    }
+    let Some(table) = name_table else {
+        return Ok(None);
+    };
+
+    let count = be16(binary, table + 2)?;
+    let strings = table + be16(binary, table + 4)?;
+    // The typographic family (16) is the one to show when present, the legacy family (1) is
+    // limited to four styles per family. Windows names are preferred, they are UTF-16.
+    let mut best: Option<(usize, String)> = None;
+    for i in 0..count {
+        let record = table + 6 + i * 12;
+        let platform = be16(binary, record)?;
+        let name_id = be16(binary, record + 6)?;
+        let length = be16(binary, record + 8)?;
+        let offset = strings + be16(binary, record + 10)?;
+        if name_id != 1 && name_id != 16 {
+            continue;
+        }
+        let Some(raw) = binary.get(offset..offset + length) else {
+            continue;
+        };
+        let name = match platform {
+            0 | 3 => String::from_utf16_lossy(
+                &raw.chunks(2)
+                    .map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]))
+                    .collect::<Vec<u16>>(),
+            ),
+            1 => raw.iter().map(|b| *b as char).collect(),
+            _ => continue,
+        };
+        let score = (name_id == 16) as usize * 2 + (platform == 3) as usize;
+        if best.as_ref().is_none_or(|(best, _)| score > *best) {
+            best = Some((score, name));
+        }
+    }
+    Ok(best.map(|(_, name)| name))
+}
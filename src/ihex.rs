@@ -0,0 +1,49 @@
+use crate::decode::decode_hex_digits;
+use crate::library::place_records;
+
+/// Reads an Intel HEX file back into the bytes of its data records, laid out by their addresses
+/// from the lowest one, the gaps filled with `fill`. The extended segment and linear address
+/// records move the following records, the start address records are ignored, and the checksum
+/// of every record is checked. The records can't span more than `max_size` bytes.
+/// For exemple, with text = ":020000040010EA\n:0100000001FE\n:0100030002FA\n:00000001FF\n",
+/// fill = 0xff and max_size = 16, the function returns vec![0x01, 0xff, 0xff, 0x02].
+pub fn ihex_to_binary(text: &str, fill: u8, max_size: usize) -> Result<Vec<u8>, String> {
+    let mut records = Vec::new();
+    let mut base = 0;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: String| format!("line {}: {}", number + 1, message);
+        let digits = line
+            .strip_prefix(':')
+            .ok_or(error("a record starts with a :".to_string()))?;
+        let bytes = decode_hex_digits(digits).map_err(error)?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(error(format!(
+                "{} doesn't have the length of its byte count",
+                line
+            )));
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err(error(format!("{} has a wrong checksum", line)));
+        }
+        let address = u16::from_be_bytes([bytes[1], bytes[2]]) as usize;
+        let data = &bytes[4..bytes.len() - 1];
+        match bytes[3] {
+            0x00 => records.push((base + address, data.to_vec())),
+            0x01 => break,
+            0x02 | 0x04 if data.len() == 2 => {
+                let value = u16::from_be_bytes([data[0], data[1]]) as usize;
+                base = match bytes[3] {
+                    0x02 => value << 4,
+                    _ => value << 16,
+                };
+            }
+            0x03 | 0x05 => (),
+            kind => return Err(error(format!("{} isn't a valid record type", kind))),
+        }
+    }
+    place_records(records, fill, max_size)
+}
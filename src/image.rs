@@ -0,0 +1,582 @@
+use crate::deflate::*;
+
+/// The image formats whose header can be read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Bmp,
+    Gif,
+}
+
+impl ImageFormat {
+    /// Parses an image format name, "png", "bmp" or "gif".
+    pub fn from_name(name: &str) -> Option<ImageFormat> {
+        match name.to_ascii_lowercase().trim() {
+            "png" => Some(ImageFormat::Png),
+            "bmp" | "dib" => Some(ImageFormat::Bmp),
+            "gif" => Some(ImageFormat::Gif),
+            _ => None,
+        }
+    }
+
+    /// Recognizes an image format by its magic number.
+    pub fn detect(binary: &[u8]) -> Option<ImageFormat> {
+        if binary.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(ImageFormat::Png)
+        } else if binary.starts_with(b"BM") {
+            Some(ImageFormat::Bmp)
+        } else if binary.starts_with(b"GIF87a") || binary.starts_with(b"GIF89a") {
+            Some(ImageFormat::Gif)
+        } else {
+            None
+        }
+    }
+}
+
+/// What the header of an image says about it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub width: usize,
+    pub height: usize,
+    /// Bits per pixel, for palette images the size of an index.
+    pub bpp: usize,
+}
+
+/// The raw pixel formats images can be converted to for embedded displays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 16 bits per pixel, 5 bits of red, 6 of green and 5 of blue, little endian.
+    Rgb565,
+    /// Same as Rgb565 but big endian, as most SPI displays expect it.
+    Rgb565Be,
+    /// 1 bit per pixel, set for light pixels, rows packed most significant bit first and padded
+    /// to a byte.
+    Mono,
+}
+
+impl PixelFormat {
+    /// Parses a pixel format name, "rgb565", "rgb565be" or "1bpp".
+    pub fn from_name(name: &str) -> Option<PixelFormat> {
+        match name.to_ascii_lowercase().replace(['-', '_'], "").trim() {
+            "rgb565" | "rgb565le" => Some(PixelFormat::Rgb565),
+            "rgb565be" => Some(PixelFormat::Rgb565Be),
+            "1bpp" | "mono" | "monochrome" => Some(PixelFormat::Mono),
+            _ => None,
+        }
+    }
+
+    /// The bits per pixel of the format.
+    pub fn bpp(self) -> usize {
+        match self {
+            PixelFormat::Rgb565 | PixelFormat::Rgb565Be => 16,
+            PixelFormat::Mono => 1,
+        }
+    }
+}
+
+/// Reads a little endian u16/u32 at an offset, if the binary is long enough.
+fn le16(binary: &[u8], offset: usize) -> Result<usize, String> {
+    match binary.get(offset..offset + 2) {
+        Some(b) => Ok(u16::from_le_bytes([b[0], b[1]]) as usize),
+        None => Err("The image is truncated".to_string()),
+    }
+}
+
+fn le32(binary: &[u8], offset: usize) -> Result<u32, String> {
+    match binary.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err("The image is truncated".to_string()),
+    }
+}
+
+fn be32(binary: &[u8], offset: usize) -> Result<usize, String> {
+    match binary.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize),
+        None => Err("The image is truncated".to_string()),
+    }
+}
+
+/// Reads the size and the bits per pixel from the header of an image.
+/// For exemple, with the header of a 16x8 RGBA PNG, the function returns
+/// ImageInfo { width: 16, height: 8, bpp: 32 }.
+pub fn image_info(binary: &[u8], format: ImageFormat) -> Result<ImageInfo, String> {
+    match format {
+        ImageFormat::Png => {
+            if binary.len() < 29 || &binary[12..16] != b"IHDR" {
+                return Err("The PNG doesn't start with an IHDR chunk".to_string());
+            }
+            let (depth, color) = (binary[24] as usize, binary[25]);
+            let (channels, depths): (usize, &[usize]) = match color {
+                0 => (1, &[1, 2, 4, 8, 16]),
+                3 => (1, &[1, 2, 4, 8]),
+                2 => (3, &[8, 16]),
+                4 => (2, &[8, 16]),
+                6 => (4, &[8, 16]),
+                _ => return Err(format!("Unknown PNG color type {}", color)),
+            };
+            if !depths.contains(&depth) {
+                return Err(format!(
+                    "Invalid PNG bit depth {} for the color type {}",
+                    depth, color
+                ));
+            }
+            Ok(ImageInfo {
+                width: be32(binary, 16)?,
+                height: be32(binary, 20)?,
+                bpp: depth * channels,
+            })
+        }
+        ImageFormat::Bmp => Ok(ImageInfo {
+            width: (le32(binary, 18)? as i32).unsigned_abs() as usize,
+            height: (le32(binary, 22)? as i32).unsigned_abs() as usize,
+            bpp: le16(binary, 28)?,
+        }),
+        ImageFormat::Gif => Ok(ImageInfo {
+            width: le16(binary, 6)?,
+            height: le16(binary, 8)?,
+            bpp: (*binary.get(10).ok_or("The image is truncated")? as usize & 7) + 1,
+        }),
+    }
+}
+
+/// Decodes an image to its RGBA pixels, row by row from the top left corner.
+/// Only the first frame of animated GIFs is decoded.
+pub fn decode_image(binary: &[u8], format: ImageFormat) -> Result<Vec<[u8; 4]>, String> {
+    match format {
+        ImageFormat::Png => decode_png(binary),
+        ImageFormat::Bmp => decode_bmp(binary),
+        ImageFormat::Gif => decode_gif(binary),
+    }
+}
+
+fn decode_png(binary: &[u8]) -> Result<Vec<[u8; 4]>, String> {
+    let info = image_info(binary, ImageFormat::Png)?;
+    let (depth, color, interlace) = (binary[24] as usize, binary[25], binary[28]);
+    if interlace != 0 {
+        return Err("Interlaced PNGs are not supported".to_string());
+    }
+    let mut palette = Vec::new();
+    let mut transparency = Vec::new();
+    let mut compressed = Vec::new();
+    let mut offset = 8;
+    while offset + 8 <= binary.len() {
+        let length = be32(binary, offset)?;
+        let kind = &binary[offset + 4..offset + 8];
+        let data = binary
+            .get(offset + 8..offset + 8 + length)
+            .ok_or("The image is truncated")?;
+        match kind {
+            b"PLTE" if data.len() % 3 != 0 => {
+                return Err("The PNG palette isn't made of RGB triplets".to_string())
+            }
+            b"PLTE" => palette = data.chunks(3).map(|c| [c[0], c[1], c[2], 255]).collect(),
+            b"tRNS" => transparency = data.to_vec(),
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            _ => (),
+        }
+        offset += length + 12;
+    }
+    for (entry, alpha) in palette.iter_mut().zip(&transparency) {
+        entry[3] = *alpha;
+    }
+
+    let bits = info.bpp;
+    let truncated = || "The PNG image data is truncated".to_string();
+    let stride = info
+        .width
+        .checked_mul(bits)
+        .ok_or_else(truncated)?
+        .div_ceil(8);
+    // The filters work on whole pixels, or on bytes below 8 bits per pixel.
+    let distance = bits.div_ceil(8);
+    let raw = zlib_decompress(&compressed)?;
+    // The size of the header is checked against the data before anything is allocated for it,
+    // every pixel taking at least a bit of the data.
+    let count = info.width.checked_mul(info.height);
+    match (stride + 1).checked_mul(info.height) {
+        Some(size) if size <= raw.len() && count.is_some_and(|c| c.div_ceil(8) <= raw.len()) => (),
+        _ => return Err(truncated()),
+    }
+    let mut previous = vec![0u8; stride];
+    let mut pixels = Vec::with_capacity(info.width * info.height);
+    for row in raw.chunks(stride + 1).take(info.height) {
+        let mut current = row[1..].to_vec();
+        for i in 0..stride {
+            let left = if i >= distance {
+                current[i - distance]
+            } else {
+                0
+            };
+            let up = previous[i];
+            let up_left = if i >= distance {
+                previous[i - distance]
+            } else {
+                0
+            };
+            let predictor = match row[0] {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                filter => return Err(format!("Unknown PNG filter {}", filter)),
+            };
+            current[i] = current[i].wrapping_add(predictor);
+        }
+
+        let sample = |index: usize| -> u8 {
+            match depth {
+                8 => current[index],
+                16 => current[index * 2],
+                _ => {
+                    let bit = index * depth;
+                    let value = (current[bit / 8] >> (8 - depth - bit % 8)) & ((1 << depth) - 1);
+                    // Palette indices are kept, gray levels are scaled to a byte.
+                    if color == 3 {
+                        value
+                    } else {
+                        (value as usize * 255 / ((1 << depth) - 1)) as u8
+                    }
+                }
+            }
+        };
+        for x in 0..info.width {
+            pixels.push(match color {
+                0 => {
+                    let gray = sample(x);
+                    [gray, gray, gray, 255]
+                }
+                2 => [sample(x * 3), sample(x * 3 + 1), sample(x * 3 + 2), 255],
+                3 => *palette
+                    .get(sample(x) as usize)
+                    .ok_or("A PNG pixel is outside of the palette")?,
+                4 => {
+                    let gray = sample(x * 2);
+                    [gray, gray, gray, sample(x * 2 + 1)]
+                }
+                _ => [
+                    sample(x * 4),
+                    sample(x * 4 + 1),
+                    sample(x * 4 + 2),
+                    sample(x * 4 + 3),
+                ],
+            });
+        }
+        previous = current;
+    }
+    Ok(pixels)
+}
+
+/// The Paeth predictor of the PNG filters.
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let distances = [
+        (estimate - left as i16).abs(),
+        (estimate - up as i16).abs(),
+        (estimate - up_left as i16).abs(),
+    ];
+    if distances[0] <= distances[1] && distances[0] <= distances[2] {
+        left
+    } else if distances[1] <= distances[2] {
+        up
+    } else {
+        up_left
+    }
+}
+
+fn decode_bmp(binary: &[u8]) -> Result<Vec<[u8; 4]>, String> {
+    let info = image_info(binary, ImageFormat::Bmp)?;
+    let data_offset = le32(binary, 10)? as usize;
+    let header_size = le32(binary, 14)? as usize;
+    let top_down = (le32(binary, 22)? as i32) < 0;
+    let compression = le32(binary, 30)?;
+    // Bitfields are only accepted in their common BGRA layout.
+    if compression != 0 && !(compression == 3 && info.bpp == 32) {
+        return Err("Compressed BMPs are not supported".to_string());
+    }
+
+    let palette = if info.bpp <= 8 {
+        let count = match le32(binary, 46)? {
+            0 => 1 << info.bpp,
+            count => count as usize,
+        };
+        let start = 14 + header_size;
+        binary
+            .get(start..start + count * 4)
+            .ok_or("The image is truncated")?
+            .chunks(4)
+            .map(|c| [c[2], c[1], c[0], 255])
+            .collect::<Vec<[u8; 4]>>()
+    } else {
+        Vec::new()
+    };
+
+    let truncated = || "The BMP image data is truncated".to_string();
+    let stride = info
+        .width
+        .checked_mul(info.bpp)
+        .ok_or_else(truncated)?
+        .div_ceil(32)
+        * 4;
+    // The size of the header is checked against the data before anything is allocated for it,
+    // every pixel taking at least a bit of the data.
+    let end = stride
+        .checked_mul(info.height)
+        .and_then(|size| size.checked_add(data_offset))
+        .filter(|end| *end <= binary.len());
+    let count = info.width.checked_mul(info.height);
+    match (end, count) {
+        (Some(_), Some(count)) if count.div_ceil(8) <= binary.len() - data_offset => (),
+        _ => return Err(truncated()),
+    }
+    let mut pixels = Vec::with_capacity(info.width * info.height);
+    for y in 0..info.height {
+        let stored_row = if top_down { y } else { info.height - 1 - y };
+        let start = data_offset + stored_row * stride;
+        let row = binary.get(start..start + stride).ok_or_else(truncated)?;
+        for x in 0..info.width {
+            pixels.push(match info.bpp {
+                1 | 4 | 8 => {
+                    let bit = x * info.bpp;
+                    let index =
+                        (row[bit / 8] as usize >> (8 - info.bpp - bit % 8)) & ((1 << info.bpp) - 1);
+                    *palette
+                        .get(index)
+                        .ok_or("A BMP pixel is outside of the palette")?
+                }
+                16 => {
+                    let value = u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]);
+                    let channel = |shift: u16| (((value >> shift) & 0x1f) * 255 / 31) as u8;
+                    [channel(10), channel(5), channel(0), 255]
+                }
+                24 => [row[x * 3 + 2], row[x * 3 + 1], row[x * 3], 255],
+                32 => [row[x * 4 + 2], row[x * 4 + 1], row[x * 4], 255],
+                bpp => {
+                    return Err(format!(
+                        "BMPs with {} bits per pixel are not supported",
+                        bpp
+                    ))
+                }
+            });
+        }
+    }
+    Ok(pixels)
+}
+
+fn decode_gif(binary: &[u8]) -> Result<Vec<[u8; 4]>, String> {
+    let info = image_info(binary, ImageFormat::Gif)?;
+    let truncated = || "The GIF is truncated".to_string();
+    let color_table = |offset: usize, flags: u8| -> Result<Vec<[u8; 4]>, String> {
+        let size = 3 << ((flags & 7) + 1);
+        Ok(binary
+            .get(offset..offset + size)
+            .ok_or_else(truncated)?
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2], 255])
+            .collect())
+    };
+
+    let flags = binary[10];
+    let mut offset = 13;
+    let mut palette = Vec::new();
+    if flags & 0x80 != 0 {
+        palette = color_table(offset, flags)?;
+        offset += palette.len() * 3;
+    }
+    let mut transparent = None;
+    loop {
+        match binary.get(offset) {
+            // Extension, only the graphic control one matters for the transparent color.
+            Some(0x21) => {
+                let label = *binary.get(offset + 1).ok_or_else(truncated)?;
+                if label == 0xf9 && binary.get(offset + 3).ok_or_else(truncated)? & 1 != 0 {
+                    transparent = Some(*binary.get(offset + 6).ok_or_else(truncated)?);
+                }
+                offset += 2;
+                sub_blocks(binary, &mut offset)?;
+            }
+            Some(0x2c) => break,
+            _ => return Err("The GIF has no image".to_string()),
+        }
+    }
+
+    let left = le16(binary, offset + 1)?;
+    let top = le16(binary, offset + 3)?;
+    let width = le16(binary, offset + 5)?;
+    let height = le16(binary, offset + 7)?;
+    let flags = *binary.get(offset + 9).ok_or_else(truncated)?;
+    offset += 10;
+    if flags & 0x80 != 0 {
+        palette = color_table(offset, flags)?;
+        offset += palette.len() * 3;
+    }
+    let minimum_code_size = *binary.get(offset).ok_or_else(truncated)?;
+    offset += 1;
+    let indices = lzw_decode(&sub_blocks(binary, &mut offset)?, minimum_code_size)?;
+
+    // Interlaced images store every 8th row from 0, every 8th from 4, every 4th from 2 then
+    // every 2nd from 1.
+    let rows = if flags & 0x40 != 0 {
+        [(0, 8), (4, 8), (2, 4), (1, 2)]
+            .iter()
+            .flat_map(|(start, step)| (*start..height).step_by(*step))
+            .collect::<Vec<usize>>()
+    } else {
+        (0..height).collect()
+    };
+    // The size of the screen is checked against the decoded pixels before it is allocated, so a
+    // forged header can't ask for gigabytes.
+    let screen = info.width * info.height;
+    if screen > indices.len() {
+        return Err(format!(
+            "The GIF image data is truncated, it holds {} pixels for a {}x{} image",
+            indices.len(),
+            info.width,
+            info.height
+        ));
+    }
+    let mut pixels = vec![[0, 0, 0, 0]; screen];
+    for (i, index) in indices.iter().enumerate().take(width * height) {
+        let (x, y) = (left + i % width, top + rows[i / width]);
+        if x >= info.width || y >= info.height || Some(*index) == transparent {
+            continue;
+        }
+        pixels[y * info.width + x] = *palette
+            .get(*index as usize)
+            .ok_or("A GIF pixel is outside of the palette")?;
+    }
+    Ok(pixels)
+}
+
+/// Concatenates the data sub-blocks of a GIF starting at offset, and moves offset after them.
+fn sub_blocks(binary: &[u8], offset: &mut usize) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    loop {
+        let size = *binary.get(*offset).ok_or("The GIF is truncated")? as usize;
+        *offset += 1;
+        if size == 0 {
+            return Ok(data);
+        }
+        data.extend_from_slice(
+            binary
+                .get(*offset..*offset + size)
+                .ok_or("The GIF is truncated")?,
+        );
+        *offset += size;
+    }
+}
+
+/// Decompresses the variable length LZW codes of a GIF image to palette indices.
+fn lzw_decode(data: &[u8], minimum_code_size: u8) -> Result<Vec<u8>, String> {
+    if !(1..=11).contains(&minimum_code_size) {
+        return Err("Invalid GIF code size".to_string());
+    }
+    let clear = 1usize << minimum_code_size;
+    let end = clear + 1;
+    // Each entry is a previous entry followed by a byte, so strings are shared.
+    let initial = (0..clear)
+        .map(|i| (None, i as u8))
+        .chain([(None, 0), (None, 0)])
+        .collect::<Vec<(Option<usize>, u8)>>();
+    let string = |table: &[(Option<usize>, u8)], mut code: usize| -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let (previous, byte) = table[code];
+            out.push(byte);
+            match previous {
+                Some(previous) => code = previous,
+                None => break,
+            }
+        }
+        out.reverse();
+        out
+    };
+
+    let mut table = initial.clone();
+    let mut code_size = minimum_code_size as usize + 1;
+    let mut previous: Option<usize> = None;
+    let mut out = Vec::new();
+    let mut position = 0;
+    while position + code_size <= data.len() * 8 {
+        let mut code = 0;
+        for i in 0..code_size {
+            let bit = (data[(position + i) / 8] >> ((position + i) % 8)) & 1;
+            code |= (bit as usize) << i;
+        }
+        position += code_size;
+
+        if code == clear {
+            table = initial.clone();
+            code_size = minimum_code_size as usize + 1;
+            previous = None;
+            continue;
+        }
+        if code == end {
+            break;
+        }
+        let entry = match previous {
+            None if code >= table.len() => return Err("Invalid GIF code".to_string()),
+            None => string(&table, code),
+            Some(previous) => {
+                let entry = if code < table.len() {
+                    string(&table, code)
+                } else if code == table.len() {
+                    let mut entry = string(&table, previous);
+                    entry.push(entry[0]);
+                    entry
+                } else {
+                    return Err("Invalid GIF code".to_string());
+                };
+                if table.len() < 4096 {
+                    table.push((Some(previous), entry[0]));
+                }
+                entry
+            }
+        };
+        out.extend_from_slice(&entry);
+        previous = Some(code);
+        if table.len() == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+    }
+    Ok(out)
+}
+
+/// Converts RGBA pixels to a raw pixel format, blending transparent pixels over black.
+/// For exemple, with pixels = &[[255, 0, 0, 255]] and format = Rgb565, the function returns
+/// vec![0x00, 0xf8].
+pub fn convert_pixels(pixels: &[[u8; 4]], width: usize, format: PixelFormat) -> Vec<u8> {
+    let opaque = |pixel: &[u8; 4]| pixel.map(|c| (c as u16 * pixel[3] as u16 / 255) as u8);
+    match format {
+        PixelFormat::Rgb565 | PixelFormat::Rgb565Be => pixels
+            .iter()
+            .flat_map(|pixel| {
+                let [r, g, b, _] = opaque(pixel);
+                let value = (r as u16 >> 3) << 11 | (g as u16 >> 2) << 5 | b as u16 >> 3;
+                if format == PixelFormat::Rgb565 {
+                    value.to_le_bytes()
+                } else {
+                    value.to_be_bytes()
+                }
+            })
+            .collect(),
+        PixelFormat::Mono => {
+            let mut out = Vec::new();
+            for row in pixels.chunks(width.max(1)) {
+                for byte in row.chunks(8) {
+                    let mut packed = 0u8;
+                    for (i, pixel) in byte.iter().enumerate() {
+                        let [r, g, b, _] = opaque(pixel);
+                        let luminance = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+                        if luminance >= 128 {
+                            packed |= 0x80 >> i;
+                        }
+                    }
+                    out.push(packed);
+                }
+            }
+            out
+        }
+    }
+}
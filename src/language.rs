@@ -0,0 +1,1579 @@
+use crate::decode::*;
+use crate::encoding::to_base64;
+use crate::library::*;
+
+/// The languages bin2const can generate constants for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    C,
+    CDefine,
+    CString,
+    Rust,
+    /// Rust with `include_bytes!` instead of an array, see binary_to_rust_include.
+    RustInclude,
+    RustBytes,
+    CSharp,
+    Python,
+    JavaScript,
+    Go,
+    Java,
+    Ca65,
+    Acme,
+    Z80,
+    Zig,
+    Swift,
+    /// Swift with Foundation's Data instead of an array of UInt8.
+    SwiftData,
+    Kotlin,
+    Nim,
+    D,
+    Pascal,
+    Ada,
+    Nasm,
+    Gas,
+    Lua,
+    /// Lua with a table of numbers instead of a string.
+    LuaTable,
+    Php,
+    /// PHP with an array of integers instead of a string.
+    PhpArray,
+    Ruby,
+    /// Ruby with a string literal instead of a packed array.
+    RubyString,
+    Perl,
+    Haskell,
+    OCaml,
+    Elixir,
+    Erlang,
+    Scala,
+    Dart,
+    Julia,
+    R,
+    Matlab,
+    ObjC,
+    Verilog,
+    Vhdl,
+    Arduino,
+    PowerShell,
+    Bash,
+    Sh,
+    Sql,
+    Postgres,
+    VbNet,
+    GdScript,
+    GdScript3,
+    FSharp,
+    Fortran,
+    Crystal,
+    Tcl,
+    Lisp,
+    Scheme,
+    Wat,
+    TypeScript,
+    Groovy,
+    Solidity,
+    Yaml,
+    Toml,
+}
+
+impl Language {
+    /// Parses a conversion type such as "rust", "rs" or "c++" into a language.
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name.to_ascii_lowercase().trim() {
+            "c" | "cpp" | "c++" | "cxx" | "h" | "hpp" | "h++" | "hxx" => Some(Language::C),
+            "cdef" | "c-def" | "c_def" | "def" | "define" | "cppdef" => Some(Language::CDefine),
+            "c-string" | "cstring" | "c_string" | "cstr" => Some(Language::CString),
+            "rust" | "rs" | "rustlang" | "rust-lang" => Some(Language::Rust),
+            "rust-include" | "rs-include" | "include-bytes" | "include_bytes" => {
+                Some(Language::RustInclude)
+            }
+            "rust-bytes" | "rs-bytes" | "rust_bytes" | "rust-byte-string" | "bytestring" => {
+                Some(Language::RustBytes)
+            }
+            "csharp" | "cs" | "c#" | "c-sharp" | "c_sharp" => Some(Language::CSharp),
+            "python" | "py" | "python3" | "py3" | "python_3" => Some(Language::Python),
+            "javascript" | "js" => Some(Language::JavaScript),
+            "go" | "golang" | "go-lang" | "go_lang" => Some(Language::Go),
+            "java" | "jvm" | "jre" | "jre8" | "jre-8" | "jre_8" | "jre11" | "jre-11" | "jre_11" => {
+                Some(Language::Java)
+            }
+            "ca65" | "cc65" | "6502" => Some(Language::Ca65),
+            "acme" => Some(Language::Acme),
+            "z80asm" | "z80" | "z88dk" => Some(Language::Z80),
+            "zig" | "ziglang" => Some(Language::Zig),
+            "swift" => Some(Language::Swift),
+            "swift-data" | "swift_data" | "swiftdata" => Some(Language::SwiftData),
+            "kotlin" | "kt" | "kts" => Some(Language::Kotlin),
+            "nim" | "nimrod" => Some(Language::Nim),
+            "d" | "dlang" => Some(Language::D),
+            "pascal" | "delphi" | "fpc" | "freepascal" | "lazarus" => Some(Language::Pascal),
+            "ada" => Some(Language::Ada),
+            "nasm" | "yasm" | "asm" | "x86asm" => Some(Language::Nasm),
+            "gas" | "att" | "gnu-as" | "gnu_as" => Some(Language::Gas),
+            "lua" => Some(Language::Lua),
+            "lua-table" | "lua_table" | "luatable" => Some(Language::LuaTable),
+            "php" => Some(Language::Php),
+            "php-array" | "php_array" | "phparray" => Some(Language::PhpArray),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "ruby-string" | "ruby_string" | "rb-string" => Some(Language::RubyString),
+            "perl" | "pl" | "pm" => Some(Language::Perl),
+            "haskell" | "hs" => Some(Language::Haskell),
+            "ocaml" | "ml" => Some(Language::OCaml),
+            "elixir" | "ex" | "exs" => Some(Language::Elixir),
+            "erlang" | "erl" | "hrl" => Some(Language::Erlang),
+            "scala" | "sc" => Some(Language::Scala),
+            "dart" | "flutter" => Some(Language::Dart),
+            "julia" | "jl" => Some(Language::Julia),
+            "r" | "rlang" => Some(Language::R),
+            "matlab" | "octave" | "m" => Some(Language::Matlab),
+            "objc" | "objective-c" | "objective_c" | "objectivec" => Some(Language::ObjC),
+            "verilog" | "systemverilog" | "sv" | "memh" | "readmemh" => Some(Language::Verilog),
+            "vhdl" | "vhd" => Some(Language::Vhdl),
+            "arduino" | "avr" | "progmem" => Some(Language::Arduino),
+            "powershell" | "ps1" | "pwsh" => Some(Language::PowerShell),
+            "bash" | "zsh" => Some(Language::Bash),
+            "sh" | "posix-sh" | "printf" => Some(Language::Sh),
+            "sql" | "sqlite" | "mysql" | "mariadb" => Some(Language::Sql),
+            "postgres" | "postgresql" | "pgsql" => Some(Language::Postgres),
+            "vb" | "vbnet" | "vb.net" | "visualbasic" => Some(Language::VbNet),
+            "gdscript" | "godot" | "godot4" | "gd" => Some(Language::GdScript),
+            "gdscript3" | "godot3" => Some(Language::GdScript3),
+            "fsharp" | "fs" | "f#" | "f-sharp" | "f_sharp" => Some(Language::FSharp),
+            "fortran" | "f90" | "f95" | "f03" | "f08" => Some(Language::Fortran),
+            "crystal" | "cr" => Some(Language::Crystal),
+            "tcl" | "tk" => Some(Language::Tcl),
+            "lisp" | "common-lisp" | "common_lisp" | "cl" => Some(Language::Lisp),
+            "scheme" | "scm" | "r7rs" => Some(Language::Scheme),
+            "wat" | "wasm" | "wast" => Some(Language::Wat),
+            "typescript" | "ts" => Some(Language::TypeScript),
+            "groovy" | "gradle" => Some(Language::Groovy),
+            "solidity" | "sol" => Some(Language::Solidity),
+            "yaml" | "yml" => Some(Language::Yaml),
+            "toml" => Some(Language::Toml),
+            _ => None,
+        }
+    }
+
+    /// Guesses the language of a source file from its extension, which is most often one of its
+    /// conversion types.
+    /// For exemple, with extension = "v", the function returns Some(Language::Verilog).
+    pub fn from_extension(extension: &str) -> Option<Language> {
+        match extension.to_ascii_lowercase().as_str() {
+            "v" | "vh" | "mem" => Some(Language::Verilog),
+            "f" | "for" | "f77" => Some(Language::Fortran),
+            "lsp" => Some(Language::Lisp),
+            "ss" | "sls" | "sld" => Some(Language::Scheme),
+            "fsx" | "fsi" => Some(Language::FSharp),
+            "psm1" => Some(Language::PowerShell),
+            "mli" => Some(Language::OCaml),
+            "cc" => Some(Language::C),
+            // MATLAB shares it, but with decimal bytes it can't be taken for hex literals.
+            "m" | "mm" => Some(Language::ObjC),
+            extension => Language::from_name(extension),
+        }
+    }
+
+    /// The name of the language, as shown in messages.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Language::C => "C/C++",
+            Language::CDefine => "C/C++",
+            Language::CString => "C/C++ string",
+            Language::Rust | Language::RustInclude | Language::RustBytes => "Rust",
+            Language::CSharp => "C#",
+            Language::Python => "Python",
+            Language::JavaScript => "Javascript",
+            Language::Go => "Go",
+            Language::Java => "Java",
+            Language::Ca65 => "ca65",
+            Language::Acme => "ACME",
+            Language::Z80 => "z80asm",
+            Language::Zig => "Zig",
+            Language::Swift | Language::SwiftData => "Swift",
+            Language::Kotlin => "Kotlin",
+            Language::Nim => "Nim",
+            Language::D => "D",
+            Language::Pascal => "Pascal",
+            Language::Ada => "Ada",
+            Language::Nasm => "NASM",
+            Language::Gas => "GNU as",
+            Language::Lua | Language::LuaTable => "Lua",
+            Language::Php | Language::PhpArray => "PHP",
+            Language::Ruby | Language::RubyString => "Ruby",
+            Language::Perl => "Perl",
+            Language::Haskell => "Haskell",
+            Language::OCaml => "OCaml",
+            Language::Elixir => "Elixir",
+            Language::Erlang => "Erlang",
+            Language::Scala => "Scala",
+            Language::Dart => "Dart",
+            Language::Julia => "Julia",
+            Language::R => "R",
+            Language::Matlab => "MATLAB",
+            Language::ObjC => "Objective-C",
+            Language::Verilog => "Verilog",
+            Language::Vhdl => "VHDL",
+            Language::Arduino => "Arduino",
+            Language::PowerShell => "PowerShell",
+            Language::Bash => "bash",
+            Language::Sh => "sh",
+            Language::Sql => "SQL",
+            Language::Postgres => "PostgreSQL",
+            Language::VbNet => "VB.NET",
+            Language::GdScript | Language::GdScript3 => "GDScript",
+            Language::FSharp => "F#",
+            Language::Fortran => "Fortran",
+            Language::Crystal => "Crystal",
+            Language::Tcl => "Tcl",
+            Language::Lisp => "Common Lisp",
+            Language::Scheme => "Scheme",
+            Language::Wat => "WebAssembly",
+            Language::TypeScript => "Typescript",
+            Language::Groovy => "Groovy",
+            Language::Solidity => "Solidity",
+            Language::Yaml => "YAML",
+            Language::Toml => "TOML",
+        }
+    }
+
+    /// The language tag of the fenced code blocks of Markdown, for --wrap-markdown.
+    pub fn markdown_tag(self) -> &'static str {
+        match self {
+            Language::C | Language::CDefine | Language::CString => "c",
+            Language::Rust | Language::RustInclude | Language::RustBytes => "rust",
+            Language::CSharp => "csharp",
+            Language::Python => "python",
+            Language::JavaScript => "javascript",
+            Language::Go => "go",
+            Language::Java => "java",
+            Language::Ca65 | Language::Acme | Language::Z80 | Language::Gas => "asm",
+            Language::Zig => "zig",
+            Language::Swift | Language::SwiftData => "swift",
+            Language::Kotlin => "kotlin",
+            Language::Nim => "nim",
+            Language::D => "d",
+            Language::Pascal => "pascal",
+            Language::Ada => "ada",
+            Language::Nasm => "nasm",
+            Language::Lua | Language::LuaTable => "lua",
+            Language::Php | Language::PhpArray => "php",
+            Language::Ruby | Language::RubyString => "ruby",
+            Language::Perl => "perl",
+            Language::Haskell => "haskell",
+            Language::OCaml => "ocaml",
+            Language::Elixir => "elixir",
+            Language::Erlang => "erlang",
+            Language::Scala => "scala",
+            Language::Dart => "dart",
+            Language::Julia => "julia",
+            Language::R => "r",
+            Language::Matlab => "matlab",
+            Language::ObjC => "objectivec",
+            Language::Verilog => "verilog",
+            Language::Vhdl => "vhdl",
+            Language::Arduino => "arduino",
+            Language::PowerShell => "powershell",
+            Language::Bash => "bash",
+            Language::Sh => "sh",
+            Language::Sql | Language::Postgres => "sql",
+            Language::VbNet => "vbnet",
+            Language::GdScript | Language::GdScript3 => "gdscript",
+            Language::FSharp => "fsharp",
+            Language::Fortran => "fortran",
+            Language::Crystal => "crystal",
+            Language::Tcl => "tcl",
+            Language::Lisp => "lisp",
+            Language::Scheme => "scheme",
+            Language::Wat => "wasm",
+            Language::TypeScript => "typescript",
+            Language::Groovy => "groovy",
+            Language::Solidity => "solidity",
+            Language::Yaml => "yaml",
+            Language::Toml => "toml",
+        }
+    }
+
+    /// The case constant names are converted to when no `--case` is given.
+    pub fn default_case(self) -> NameCase {
+        match self {
+            Language::C
+            | Language::CDefine
+            | Language::CString
+            | Language::Rust
+            | Language::RustInclude
+            | Language::RustBytes
+            | Language::CSharp
+            | Language::Python
+            | Language::JavaScript
+            | Language::Go
+            | Language::Java
+            | Language::Ca65
+            | Language::Acme
+            | Language::Z80
+            | Language::Zig
+            | Language::Swift
+            | Language::SwiftData
+            | Language::Kotlin
+            | Language::Nim
+            | Language::D
+            | Language::Pascal
+            | Language::Ada
+            | Language::Nasm
+            | Language::Gas
+            | Language::Lua
+            | Language::LuaTable
+            | Language::Php
+            | Language::PhpArray
+            | Language::Ruby
+            | Language::RubyString
+            | Language::Perl
+            | Language::Erlang
+            | Language::Scala
+            | Language::Julia
+            | Language::R
+            | Language::Matlab
+            | Language::ObjC
+            | Language::Verilog
+            | Language::Vhdl
+            | Language::Arduino
+            | Language::PowerShell
+            | Language::Bash
+            | Language::Sh
+            | Language::Sql
+            | Language::Postgres
+            | Language::VbNet
+            | Language::GdScript
+            | Language::GdScript3
+            | Language::FSharp
+            | Language::Fortran
+            | Language::Crystal
+            | Language::Tcl
+            | Language::Wat
+            | Language::TypeScript
+            | Language::Groovy
+            | Language::Solidity
+            | Language::Yaml
+            | Language::Toml => NameCase::Upper,
+            Language::Haskell => NameCase::Camel,
+            Language::OCaml => NameCase::Lower,
+            Language::Elixir => NameCase::Lower,
+            Language::Dart => NameCase::Camel,
+            Language::Lisp | Language::Scheme => NameCase::Lower,
+        }
+    }
+
+    /// The reserved words that can't be used as constant names.
+    pub fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Language::C | Language::CDefine | Language::CString => C_KEYWORDS,
+            Language::Rust | Language::RustInclude | Language::RustBytes => RUST_KEYWORDS,
+            Language::CSharp => CSHARP_KEYWORDS,
+            Language::Python => PYTHON_KEYWORDS,
+            Language::JavaScript => JAVASCRIPT_KEYWORDS,
+            Language::Go => GO_KEYWORDS,
+            Language::Java => JAVA_KEYWORDS,
+            Language::Ca65 | Language::Acme => ASM_6502_KEYWORDS,
+            Language::Z80 => Z80_KEYWORDS,
+            Language::Zig => ZIG_KEYWORDS,
+            Language::Swift | Language::SwiftData => SWIFT_KEYWORDS,
+            Language::Kotlin => KOTLIN_KEYWORDS,
+            Language::Nim => NIM_KEYWORDS,
+            Language::D => D_KEYWORDS,
+            Language::Pascal => PASCAL_KEYWORDS,
+            Language::Ada => ADA_KEYWORDS,
+            Language::Nasm => X86_KEYWORDS,
+            Language::Gas => &[],
+            Language::Lua | Language::LuaTable => LUA_KEYWORDS,
+            Language::Php | Language::PhpArray => PHP_KEYWORDS,
+            Language::Ruby | Language::RubyString => RUBY_KEYWORDS,
+            Language::Perl => PERL_KEYWORDS,
+            Language::Haskell => HASKELL_KEYWORDS,
+            Language::OCaml => OCAML_KEYWORDS,
+            Language::Elixir => ELIXIR_KEYWORDS,
+            Language::Erlang => ERLANG_KEYWORDS,
+            Language::Scala => SCALA_KEYWORDS,
+            Language::Dart => DART_KEYWORDS,
+            Language::Julia => JULIA_KEYWORDS,
+            Language::R => R_KEYWORDS,
+            Language::Matlab => MATLAB_KEYWORDS,
+            Language::ObjC => OBJC_KEYWORDS,
+            Language::Verilog => &[],
+            Language::Vhdl => VHDL_KEYWORDS,
+            Language::Arduino => C_KEYWORDS,
+            Language::PowerShell => POWERSHELL_KEYWORDS,
+            Language::Bash => SHELL_KEYWORDS,
+            Language::Sh => SHELL_KEYWORDS,
+            Language::Sql | Language::Postgres => &[],
+            Language::VbNet => VBNET_KEYWORDS,
+            Language::GdScript | Language::GdScript3 => GDSCRIPT_KEYWORDS,
+            Language::FSharp => FSHARP_KEYWORDS,
+            Language::Fortran => &[],
+            Language::Crystal => CRYSTAL_KEYWORDS,
+            Language::Tcl => &[],
+            Language::Lisp => &[],
+            Language::Scheme => SCHEME_KEYWORDS,
+            Language::Wat => &[],
+            Language::TypeScript => JAVASCRIPT_KEYWORDS,
+            Language::Groovy => GROOVY_KEYWORDS,
+            Language::Solidity => SOLIDITY_KEYWORDS,
+            Language::Yaml => &[],
+            Language::Toml => &[],
+        }
+    }
+
+    /// Whether a name is a reserved word, ignoring the case for the languages that do.
+    fn is_keyword(self, name: &str) -> bool {
+        match self {
+            Language::Pascal
+            | Language::Ada
+            | Language::Php
+            | Language::PhpArray
+            | Language::Vhdl
+            | Language::PowerShell
+            | Language::VbNet => self
+                .keywords()
+                .contains(&name.to_ascii_lowercase().as_str()),
+            Language::ObjC => C_KEYWORDS.contains(&name) || OBJC_KEYWORDS.contains(&name),
+            _ => self.keywords().contains(&name),
+        }
+    }
+
+    /// Whether the language is one of the Rust ones.
+    fn is_rust(self) -> bool {
+        matches!(
+            self,
+            Language::Rust | Language::RustInclude | Language::RustBytes
+        )
+    }
+
+    /// Whether a character can appear in an identifier, `first` is for the first character.
+    fn is_identifier_char(self, c: char, first: bool) -> bool {
+        let extra = match self {
+            Language::JavaScript
+            | Language::TypeScript
+            | Language::Java
+            | Language::Groovy
+            | Language::Solidity => c == '$',
+            Language::R => c == '.' && !first,
+            _ => false,
+        };
+        // R, MATLAB, VHDL and Fortran names start with a letter, R ones can also start with a dot
+        // that isn't used here.
+        if first
+            && matches!(
+                self,
+                Language::R | Language::Matlab | Language::Vhdl | Language::Fortran
+            )
+        {
+            return c.is_ascii_alphabetic();
+        }
+        // Names starting with an uppercase letter are the ones of types and constructors.
+        if first && matches!(self, Language::Haskell | Language::OCaml | Language::Elixir) {
+            return c == '_' || c.is_ascii_lowercase();
+        }
+        c == '_' || extra || c.is_ascii_alphabetic() || (!first && c.is_ascii_digit())
+    }
+
+    /// Checks that a name is a valid identifier and not a reserved word of the language.
+    pub fn validate_name(self, name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("the name is empty".to_string());
+        }
+        if let Some((i, c)) = name
+            .chars()
+            .enumerate()
+            .find(|(i, c)| !self.is_identifier_char(*c, *i == 0))
+        {
+            return Err(if i == 0 && c.is_ascii_digit() {
+                format!("\"{}\" starts with a digit", name)
+            } else if i == 0 && c.is_ascii_uppercase() {
+                format!(
+                    "\"{}\" starts with an uppercase letter, which {} doesn't allow for constants",
+                    name,
+                    self.display_name()
+                )
+            } else {
+                format!("\"{}\" contains the invalid character '{}'", name, c)
+            });
+        }
+        if self.is_rust() && name == "_" {
+            return Err("\"_\" can't be used as a name in Rust".to_string());
+        }
+        if self.is_keyword(name) {
+            return Err(format!(
+                "\"{}\" is a reserved word in {}",
+                name,
+                self.display_name()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Turns a name into a valid identifier: invalid characters become underscores, names
+    /// starting with a digit are prefixed with an underscore and reserved words get a trailing
+    /// underscore, for exemple "2d-sprite" becomes "_2d_sprite" and "match" becomes "match_".
+    /// An uppercase first letter is lowered for the languages that keep them for types, and R,
+    /// MATLAB, VHDL and Fortran names are prefixed with an X instead, like make.names and makeValidName do,
+    /// as they can't start with an underscore.
+    pub fn fix_name(self, name: &str) -> String {
+        let mut fixed = name
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if self.is_identifier_char(c, i == 0) || c.is_ascii_digit() {
+                    c
+                } else if i == 0 && c.is_ascii_uppercase() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        if !fixed.starts_with(|c: char| self.is_identifier_char(c, true)) {
+            let prefix = match self {
+                Language::R | Language::Vhdl | Language::Fortran => 'X',
+                Language::Matlab => 'x',
+                _ => '_',
+            };
+            fixed.insert(0, prefix);
+        }
+        if self.is_keyword(&fixed) || (self.is_rust() && fixed == "_") {
+            fixed.push('_');
+        }
+        fixed
+    }
+
+    /// Converts an array of bytes to a constant of this language.
+    pub fn convert(self, binary: &[u8], name: &str, tab_size: usize) -> String {
+        match self {
+            Language::C => binary_to_c_const(binary, name, tab_size),
+            Language::CDefine => binary_to_c_define(binary, name, tab_size),
+            Language::CString => binary_to_c_string(binary, name, tab_size),
+            // The path of the file is needed to include it, given only its bytes it is an array.
+            Language::Rust | Language::RustInclude => binary_to_rust_const(binary, name, tab_size),
+            Language::RustBytes => binary_to_rust_byte_string(binary, name, tab_size),
+            Language::CSharp => binary_to_csharp_const(binary, name, tab_size),
+            Language::Python => binary_to_python_const(binary, name, tab_size),
+            Language::JavaScript => binary_to_javascript_const(binary, name, tab_size),
+            Language::Go => binary_to_go_const(binary, name, tab_size),
+            Language::Java => binary_to_java_const(binary, name, tab_size),
+            Language::Ca65 => binary_to_6502_const(binary, name, tab_size, false),
+            Language::Acme => binary_to_6502_const(binary, name, tab_size, true),
+            Language::Z80 => binary_to_z80_const(binary, name, tab_size),
+            Language::Zig => binary_to_zig_const(binary, name, tab_size),
+            Language::Swift => binary_to_swift_const(binary, name, tab_size, false),
+            Language::SwiftData => binary_to_swift_const(binary, name, tab_size, true),
+            Language::Kotlin => binary_to_kotlin_const(binary, name, tab_size),
+            Language::Nim => binary_to_nim_const(binary, name, tab_size),
+            Language::D => binary_to_d_const(binary, name, tab_size),
+            Language::Pascal => binary_to_pascal_const(binary, name, tab_size),
+            Language::Ada => binary_to_ada_const(binary, name, tab_size),
+            Language::Nasm => binary_to_nasm_const(binary, name, tab_size),
+            Language::Gas => binary_to_gas_const(binary, name, tab_size),
+            Language::Lua => binary_to_lua_const(binary, name, tab_size, false),
+            Language::LuaTable => binary_to_lua_const(binary, name, tab_size, true),
+            Language::Php => binary_to_php_const(binary, name, tab_size, false),
+            Language::PhpArray => binary_to_php_const(binary, name, tab_size, true),
+            Language::Ruby => binary_to_ruby_const(binary, name, tab_size, false),
+            Language::RubyString => binary_to_ruby_const(binary, name, tab_size, true),
+            Language::Perl => binary_to_perl_const(binary, name, tab_size),
+            Language::Haskell => binary_to_haskell_const(binary, name, tab_size),
+            Language::OCaml => binary_to_ocaml_const(binary, name, tab_size),
+            Language::Elixir => binary_to_elixir_const(binary, name, tab_size),
+            Language::Erlang => binary_to_erlang_const(binary, name, tab_size),
+            Language::Scala => binary_to_scala_const(binary, name, tab_size),
+            Language::Dart => binary_to_dart_const(binary, name, tab_size),
+            Language::Julia => binary_to_julia_const(binary, name, tab_size),
+            Language::R => binary_to_r_const(binary, name, tab_size),
+            Language::Matlab => binary_to_matlab_const(binary, name, tab_size),
+            Language::ObjC => binary_to_objc_const(binary, name, tab_size),
+            Language::Verilog => binary_to_verilog_memh(binary, name),
+            Language::Vhdl => binary_to_vhdl_const(binary, name, tab_size),
+            Language::Arduino => binary_to_arduino_const(binary, name, tab_size),
+            Language::PowerShell => binary_to_powershell_const(binary, name, tab_size),
+            Language::Bash => binary_to_bash_const(binary, name, tab_size),
+            Language::Sh => binary_to_sh_function(binary, name),
+            Language::Sql => binary_to_sql_insert(binary, name, false),
+            Language::Postgres => binary_to_sql_insert(binary, name, true),
+            Language::VbNet => binary_to_vbnet_const(binary, name, tab_size),
+            Language::GdScript => binary_to_gdscript_const(binary, name, tab_size, false),
+            Language::GdScript3 => binary_to_gdscript_const(binary, name, tab_size, true),
+            Language::FSharp => binary_to_fsharp_const(binary, name, tab_size),
+            Language::Fortran => binary_to_fortran_const(binary, name, tab_size),
+            Language::Crystal => binary_to_crystal_const(binary, name, tab_size),
+            Language::Tcl => binary_to_tcl_const(binary, name, tab_size),
+            Language::Lisp => binary_to_lisp_const(binary, name, tab_size, false),
+            Language::Scheme => binary_to_lisp_const(binary, name, tab_size, true),
+            // The data is placed at the --address by convert_to_language.
+            Language::Wat => binary_to_wat_data(binary, name, tab_size, 0),
+            Language::TypeScript => binary_to_typescript_const(binary, name, tab_size, false),
+            Language::Groovy => binary_to_groovy_const(binary, name, tab_size),
+            Language::Solidity => binary_to_solidity_const(binary, name, tab_size),
+            Language::Yaml => binary_to_yaml(binary, name, tab_size),
+            Language::Toml => binary_to_toml(binary, name, tab_size),
+        }
+    }
+
+    /// The names of the constants convert declares for the constant `name`.
+    pub fn declared_names(self, name: &str) -> Vec<String> {
+        match self {
+            Language::CDefine => vec![format!("{}_SIZE", name), name.to_string()],
+            Language::CString => vec![format!("{}_LEN", name), name.to_string()],
+            _ => vec![name.to_string()],
+        }
+    }
+
+    /// Declares an integer constant, for the parameters emitted next to the data.
+    /// For exemple, with name = "TEST_TXT_WIDTH" and value = 16, the function returns
+    /// "const TEST_TXT_WIDTH: usize = 16;\n" for Rust.
+    pub fn integer_const(self, name: &str, value: usize) -> String {
+        match self {
+            Language::C | Language::CDefine | Language::CString => {
+                format!("#define {} {}\n", name, value)
+            }
+            Language::Rust | Language::RustInclude | Language::RustBytes => {
+                format!("const {}: usize = {};\n", name, value)
+            }
+            Language::CSharp => format!("public const int {} = {};\n", name, value),
+            Language::Python => format!("{} = {}\n", name, value),
+            Language::JavaScript => format!("const {} = {};\n", name, value),
+            Language::Go => format!("const {} = {}\n", name, value),
+            Language::Java => format!("public static final int {} = {};\n", name, value),
+            Language::Ca65 | Language::Acme => format!("{} = {}\n", name, value),
+            Language::Z80 => format!("{} equ {}\n", name, value),
+            Language::Zig => format!("pub const {}: usize = {};\n", name, value),
+            Language::Swift | Language::SwiftData => format!("let {} = {}\n", name, value),
+            Language::Kotlin => format!("const val {} = {}\n", name, value),
+            Language::Nim => format!("const {} = {}\n", name, value),
+            Language::D => format!("enum size_t {} = {};\n", name, value),
+            Language::Pascal => format!("const {} = {};\n", name, value),
+            Language::Ada => format!("{} : constant := {};\n", name, value),
+            Language::Nasm => format!("{} equ {}\n", name, value),
+            Language::Gas => format!("    .set {}, {}\n", name, value),
+            Language::Lua | Language::LuaTable => format!("local {} = {}\n", name, value),
+            Language::Php | Language::PhpArray => format!("const {} = {};\n", name, value),
+            Language::Ruby | Language::RubyString => format!("{} = {}\n", name, value),
+            Language::Perl => format!("my ${} = {};\n", name, value),
+            Language::Haskell => format!("{0} :: Int\n{0} = {1}\n", name, value),
+            Language::OCaml => format!("let {} = {}\n", name, value),
+            Language::Elixir => format!("@{} {}\n", name, value),
+            Language::Erlang => format!("-define({}, {}).\n", name, value),
+            Language::Scala => format!("val {}: Int = {}\n", name, value),
+            Language::Dart => format!("const int {} = {};\n", name, value),
+            Language::Julia => format!("const {} = {}\n", name, value),
+            Language::R => format!("{} <- {}L\n", name, value),
+            Language::Matlab => format!("{} = {};\n", name, value),
+            Language::ObjC => format!("#define {} {}\n", name, value),
+            Language::Verilog => format!("// {} = {}\n", name, value),
+            Language::Vhdl => format!("constant {} : natural := {};\n", name, value),
+            Language::Arduino => format!("#define {} {}\n", name, value),
+            Language::PowerShell => format!("[int]${} = {}\n", name, value),
+            Language::Bash => format!("{}={}\n", name, value),
+            Language::Sh => format!("{}={}\n", name, value),
+            Language::Sql | Language::Postgres => format!("-- {} = {}\n", name, value),
+            Language::VbNet => format!("Public Const {} As Integer = {}\n", name, value),
+            Language::GdScript | Language::GdScript3 => format!("const {} = {}\n", name, value),
+            Language::FSharp => format!("let {} = {}\n", name, value),
+            Language::Fortran => format!("integer, parameter :: {} = {}\n", name, value),
+            Language::Crystal => format!("{} = {}\n", name, value),
+            Language::Tcl => format!("set {} {}\n", name, value),
+            Language::Lisp => format!("(defconstant +{}+ {})\n", name.replace('_', "-"), value),
+            Language::Scheme => format!("(define {} {})\n", name.replace('_', "-"), value),
+            Language::Wat => format!("(global ${} i32 (i32.const {}))\n", name, value),
+            Language::TypeScript => format!("export const {}: number = {};\n", name, value),
+            Language::Groovy => format!("static final int {} = {}\n", name, value),
+            Language::Solidity => format!("uint256 constant {} = {};\n", name, value),
+            Language::Yaml => format!("- name: {}\n  value: {}\n", name, value),
+            Language::Toml => format!("[[constants]]\nname = \"{}\"\nvalue = {}\n", name, value),
+        }
+    }
+
+    /// Declares a string constant, for the parameters emitted next to the data.
+    /// For exemple, with name = "TEST_TXT_FAMILY" and value = "DejaVu Sans", the function returns
+    /// "const TEST_TXT_FAMILY: &str = \"DejaVu Sans\";\n" for Rust.
+    pub fn string_const(self, name: &str, value: &str) -> String {
+        let text = value
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect::<String>();
+        // The escapes most of these languages share are enough for names read from files.
+        let value = text.replace('\\', "\\\\").replace('"', "\\\"");
+        match self {
+            Language::C | Language::CDefine | Language::CString => {
+                format!("#define {} \"{}\"\n", name, value)
+            }
+            Language::Rust | Language::RustInclude | Language::RustBytes => {
+                format!("const {}: &str = \"{}\";\n", name, value)
+            }
+            Language::CSharp => format!("public const string {} = \"{}\";\n", name, value),
+            Language::Python => format!("{} = \"{}\"\n", name, value),
+            Language::JavaScript => format!("const {} = \"{}\";\n", name, value),
+            Language::Go => format!("const {} = \"{}\"\n", name, value),
+            Language::Java => format!("public static final String {} = \"{}\";\n", name, value),
+            // The assemblers have no string constants, the text is stored under a label.
+            Language::Ca65 => format!("{}:\n    .byte \"{}\"\n", name, value),
+            Language::Acme => format!("{}\n    !text \"{}\"\n", name, value),
+            Language::Z80 => format!("{}:\n    db \"{}\"\n", name, value),
+            Language::Zig => format!("pub const {} = \"{}\";\n", name, value),
+            Language::Swift | Language::SwiftData => format!("let {} = \"{}\"\n", name, value),
+            Language::Kotlin => {
+                format!("const val {} = \"{}\"\n", name, value.replace('$', "\\$"))
+            }
+            Language::Nim => format!("const {} = \"{}\"\n", name, value),
+            Language::D => format!("enum string {} = \"{}\";\n", name, value),
+            Language::Pascal => format!("const {} = '{}';\n", name, text.replace('\'', "''")),
+            Language::Ada => format!(
+                "{} : constant String := \"{}\";\n",
+                name,
+                text.replace('"', "\"\"")
+            ),
+            Language::Nasm => format!("{}:\n    db \"{}\"\n", name, value),
+            Language::Gas => format!("{}:\n    .ascii \"{}\"\n", name, value),
+            Language::Lua | Language::LuaTable => format!("local {} = \"{}\"\n", name, value),
+            Language::Php | Language::PhpArray => format!(
+                "const {} = '{}';\n",
+                name,
+                text.replace('\\', "\\\\").replace('\'', "\\'")
+            ),
+            Language::Ruby | Language::RubyString => format!(
+                "{} = '{}'\n",
+                name,
+                text.replace('\\', "\\\\").replace('\'', "\\'")
+            ),
+            Language::Perl => format!(
+                "my ${} = '{}';\n",
+                name,
+                text.replace('\\', "\\\\").replace('\'', "\\'")
+            ),
+            Language::Haskell => format!("{0} :: String\n{0} = \"{1}\"\n", name, value),
+            Language::OCaml => format!("let {} = \"{}\"\n", name, value),
+            Language::Elixir => format!("@{} \"{}\"\n", name, value.replace('#', "\\#")),
+            Language::Erlang => format!("-define({}, <<\"{}\">>).\n", name, value),
+            Language::Scala => format!("val {}: String = \"{}\"\n", name, value),
+            Language::Dart => format!(
+                "const String {} = '{}';\n",
+                name,
+                text.replace('\\', "\\\\")
+                    .replace('\'', "\\'")
+                    .replace('$', "\\$")
+            ),
+            Language::Julia => format!("const {} = \"{}\"\n", name, value.replace('$', "\\$")),
+            Language::R => format!("{} <- \"{}\"\n", name, value),
+            Language::Matlab => format!("{} = '{}';\n", name, text.replace('\'', "''")),
+            Language::ObjC => format!("#define {} @\"{}\"\n", name, value),
+            Language::Verilog => format!("// {} = \"{}\"\n", name, value),
+            Language::Vhdl => format!(
+                "constant {} : string := \"{}\";\n",
+                name,
+                text.replace('"', "\"\"")
+            ),
+            Language::Arduino => format!("#define {} \"{}\"\n", name, value),
+            Language::PowerShell => format!("[string]${} = '{}'\n", name, text.replace('\'', "''")),
+            Language::Bash => format!("{}='{}'\n", name, text.replace('\'', "'\\''")),
+            Language::Sh => format!("{}='{}'\n", name, text.replace('\'', "'\\''")),
+            Language::Sql | Language::Postgres => format!("-- {} = '{}'\n", name, text),
+            Language::VbNet => format!(
+                "Public Const {} As String = \"{}\"\n",
+                name,
+                text.replace('"', "\"\"")
+            ),
+            Language::GdScript | Language::GdScript3 => format!("const {} = \"{}\"\n", name, value),
+            Language::FSharp => format!("let {} = \"{}\"\n", name, value),
+            Language::Fortran => format!(
+                "character(len=*), parameter :: {} = \"{}\"\n",
+                name,
+                text.replace('"', "\"\"")
+            ),
+            Language::Crystal => format!("{} = \"{}\"\n", name, value.replace('#', "\\#")),
+            Language::Tcl => format!(
+                "set {} \"{}\"\n",
+                name,
+                value.replace('$', "\\$").replace('[', "\\[")
+            ),
+            Language::Lisp => format!("(defconstant +{}+ \"{}\")\n", name.replace('_', "-"), value),
+            Language::Scheme => format!("(define {} \"{}\")\n", name.replace('_', "-"), value),
+            Language::Wat => format!(";; {} = \"{}\"\n", name, value),
+            Language::TypeScript => format!("export const {}: string = \"{}\";\n", name, value),
+            Language::Groovy => format!(
+                "static final String {} = '{}'\n",
+                name,
+                text.replace('\\', "\\\\").replace('\'', "\\'")
+            ),
+            Language::Solidity => format!("string constant {} = \"{}\";\n", name, value),
+            Language::Yaml => format!("- name: {}\n  value: \"{}\"\n", name, value),
+            Language::Toml => format!(
+                "[[constants]]\nname = \"{}\"\nvalue = \"{}\"\n",
+                name, value
+            ),
+        }
+    }
+
+    /// Converts an array of bytes to a constant holding it encoded in base64, for --base64. It is
+    /// a string constant but for the formats that give the size of the data next to it.
+    pub fn convert_base64(self, binary: &[u8], name: &str, url_safe: bool) -> String {
+        let encoded = to_base64(binary, url_safe);
+        match self {
+            Language::Yaml => base64_to_yaml(&encoded, binary.len(), name),
+            Language::Toml => base64_to_toml(&encoded, binary.len(), name),
+            _ => self.string_const(name, &encoded),
+        }
+    }
+
+    /// Converts an array of bytes to a constant aligned to `alignment` bytes, for the languages
+    /// that let the alignment of a constant be chosen.
+    pub fn convert_aligned(
+        self,
+        binary: &[u8],
+        name: &str,
+        tab_size: usize,
+        alignment: usize,
+    ) -> Option<String> {
+        match self {
+            Language::C => Some(binary_to_aligned_c_const(binary, name, tab_size, alignment)),
+            Language::Rust => Some(binary_to_aligned_rust_const(
+                binary, name, tab_size, alignment,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Places a constant generated by convert or convert_aligned in a linker section, for the
+    /// languages that let the section of a constant be chosen.
+    pub fn in_section(
+        self,
+        constant: &str,
+        name: &str,
+        section: &str,
+        tab_size: usize,
+    ) -> Option<String> {
+        match self {
+            Language::C => Some(c_const_in_section(constant, name, section)),
+            Language::Gas => Some(gas_const_in_section(constant, section, tab_size)),
+            _ => None,
+        }
+    }
+
+    /// Generates a function finding the constants by the path of their file, from their paths,
+    /// names and sizes, sorted by path.
+    pub fn lookup(
+        self,
+        entries: &[(&str, String, usize)],
+        function: &str,
+        tab_size: usize,
+    ) -> Option<String> {
+        match self {
+            Language::C => Some(lookup_to_cpp(entries, function, tab_size)),
+            Language::Rust | Language::RustInclude | Language::RustBytes => {
+                Some(lookup_to_rust(entries, function, tab_size))
+            }
+            _ => None,
+        }
+    }
+
+    /// Declares the type of the --webasset entries, for the languages that need one.
+    pub fn web_asset_type(self, tab_size: usize) -> Option<String> {
+        match self {
+            Language::C => Some(web_asset_type_to_c(tab_size)),
+            Language::Rust => Some(web_asset_type_to_rust(tab_size)),
+            Language::Go => Some(web_asset_type_to_go(tab_size)),
+            _ => None,
+        }
+    }
+
+    /// Declares the --webasset entry of the constant `name`, gathering its data and what a web
+    /// server needs to serve it, for the languages that have structs or tables.
+    pub fn web_asset(
+        self,
+        entry: &str,
+        name: &str,
+        asset: &WebAsset,
+        tab_size: usize,
+    ) -> Option<String> {
+        match self {
+            Language::C => Some(web_asset_to_c(entry, name, asset, tab_size)),
+            Language::Rust => Some(web_asset_to_rust(entry, name, asset, tab_size)),
+            Language::Go => Some(web_asset_to_go(entry, name, asset, tab_size)),
+            Language::Python => Some(web_asset_to_table(entry, name, asset, tab_size, false)),
+            Language::JavaScript => Some(web_asset_to_table(entry, name, asset, tab_size, true)),
+            _ => None,
+        }
+    }
+
+    /// Names the accessor of a constant in the casing of the language, for the languages that
+    /// have one.
+    pub fn accessor_name(self, name: &str) -> Option<String> {
+        match self {
+            Language::C => Some(format!("{}_data", NameCase::Lower.apply(name))),
+            Language::Rust | Language::RustInclude | Language::RustBytes => {
+                Some(NameCase::Lower.apply(name))
+            }
+            Language::CSharp => Some(NameCase::Pascal.apply(name)),
+            _ => None,
+        }
+    }
+
+    /// Generates an accessor for a constant of `size` bytes, as a function or a property named
+    /// by accessor_name.
+    pub fn accessor(self, name: &str, size: usize, tab_size: usize) -> Option<String> {
+        let function = self.accessor_name(name)?;
+        match self {
+            Language::C => Some(accessor_to_c(name, &function, size, tab_size)),
+            Language::Rust | Language::RustInclude | Language::RustBytes => {
+                Some(accessor_to_rust(name, &function, tab_size))
+            }
+            Language::CSharp => Some(accessor_to_csharp(name, &function)),
+            _ => None,
+        }
+    }
+
+    /// Declares the package of a source file, for the languages that need one.
+    /// For exemple, with source = "var TEST_TXT = []byte{}\n" and package = "assets", the function
+    /// returns "package assets\n\nvar TEST_TXT = []byte{}\n" for Go.
+    pub fn in_package(self, source: &str, package: &str) -> Result<String, String> {
+        match self {
+            Language::Go => {
+                self.validate_name(package)
+                    .map_err(|e| format!("Invalid package name: {}", e))?;
+                Ok(format!("package {}\n\n{}", package, source))
+            }
+            Language::Java => {
+                if !source.starts_with("public final class ") {
+                    return Err("--package needs --class for Java".to_string());
+                }
+                for part in package.split('.') {
+                    self.validate_name(part)
+                        .map_err(|e| format!("Invalid package name: {}", e))?;
+                }
+                Ok(format!("package {};\n\n{}", package, source))
+            }
+            _ => Err(format!(
+                "--package isn't supported for {}",
+                self.display_name()
+            )),
+        }
+    }
+
+    /// Wraps the constants of a source file in a class, for the languages whose constants can't
+    /// be declared on their own.
+    /// For exemple, with source = "public static final byte[] TEST_TXT = new byte[0];\n" and
+    /// class = "Assets", the function returns for Java:
+    /// public final class Assets {
+    ///     public static final byte[] TEST_TXT = new byte[0];
+    /// }
+    pub fn in_class(self, source: &str, class: &str, tab_size: usize) -> Result<String, String> {
+        match self {
+            Language::Java => {
+                self.validate_name(class)
+                    .map_err(|e| format!("Invalid class name: {}", e))?;
+                let indent = " ".repeat(tab_size);
+                let body = source
+                    .trim_end()
+                    .lines()
+                    .map(|line| match line {
+                        "" => "\n".to_string(),
+                        line => format!("{}{}\n", indent, line),
+                    })
+                    .collect::<String>();
+                Ok(format!("public final class {} {{\n{}}}\n", class, body))
+            }
+            _ => Err(format!(
+                "--class isn't supported for {}",
+                self.display_name()
+            )),
+        }
+    }
+
+    /// Converts a text to a string constant, for the languages that have one.
+    pub fn convert_text(
+        self,
+        text: &str,
+        name: &str,
+        tab_size: usize,
+        escape: Escape,
+    ) -> Option<String> {
+        match self {
+            Language::C => Some(text_to_c_const(text, name, tab_size, escape)),
+            Language::Rust => Some(text_to_rust_const(text, name, tab_size, escape)),
+            Language::Python => Some(text_to_python_const(text, name, tab_size, escape)),
+            _ => None,
+        }
+    }
+
+    /// Converts lines of text to an array of strings, for the languages that have one.
+    pub fn convert_lines(
+        self,
+        lines: &[&str],
+        name: &str,
+        tab_size: usize,
+        escape: Escape,
+    ) -> Option<String> {
+        match self {
+            Language::C => Some(lines_to_c_const(lines, name, tab_size, escape)),
+            Language::Rust => Some(lines_to_rust_const(lines, name, tab_size, escape)),
+            Language::Python => Some(lines_to_python_const(lines, name, tab_size, escape)),
+            _ => None,
+        }
+    }
+
+    /// Decodes the bytes of a constant generated by convert back from its source, for --verify.
+    pub fn decode(self, source: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Language::Php | Language::RubyString | Language::OCaml | Language::CString => {
+                decode_escapes(source)
+            }
+            // The sigils of the variables would be read as Pascal hexadecimal literals.
+            Language::Perl => decode_array(&source.replace("my $", "my ")),
+            Language::Matlab => decode_decimal(source),
+            // The type of an empty array constructor would be read as a byte.
+            Language::Fortran => decode_decimal(
+                &source
+                    .replace("integer(kind=1) ::", "")
+                    .replace("(/", "[")
+                    .replace("/)", "]"),
+            ),
+            Language::Sh => decode_octal_escapes(source),
+            Language::VbNet => decode_array(&source.replace("&H", "0x")),
+            Language::FSharp => decode_array(&source.replace("uy", "")),
+            Language::Sql | Language::Postgres => decode_hex_strings(source),
+            Language::Tcl => decode_tcl_hex(source),
+            Language::Solidity => decode_solidity_hex(source),
+            Language::Wat => decode_wat_strings(source),
+            Language::Lisp | Language::Scheme => decode_decimal(
+                &source
+                    .replace("#u8(", "[")
+                    .replace("#(", "[")
+                    .replace(')', "]"),
+            ),
+            // The variables are all typed, which is how their sigil is told from a literal.
+            Language::PowerShell => decode_array(&source.replace("]$", "] ")),
+            Language::Verilog => decode_memh(source),
+            Language::Vhdl => decode_bit_strings(source),
+            Language::RustBytes => decode_byte_strings(source),
+            _ => decode_array(source),
+        }
+    }
+
+    /// Generates a test checking a constant generated by convert against its source, for the
+    /// languages that have one.
+    pub fn test(self, binary: &[u8], name: &str, tab_size: usize) -> Option<String> {
+        match self {
+            Language::C => Some(binary_to_c_test(binary, name, tab_size)),
+            Language::Rust | Language::RustInclude | Language::RustBytes => {
+                Some(binary_to_rust_test(binary, name, tab_size))
+            }
+            Language::Python => Some(binary_to_python_test(binary, name, tab_size)),
+            _ => None,
+        }
+    }
+}
+
+/// How constant names are cased.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameCase {
+    /// The name is used as given.
+    Preserve,
+    /// SCREAMING_SNAKE_CASE, for exemple "testTxt" becomes "TEST_TXT".
+    Upper,
+    /// snake_case, for exemple "TestTxt" becomes "test_txt".
+    Lower,
+    /// camelCase, for exemple "test_txt" becomes "testTxt".
+    Camel,
+    /// PascalCase, for exemple "test_txt" becomes "TestTxt".
+    Pascal,
+}
+
+impl NameCase {
+    /// Parses a case policy name such as "upper", "lower", "camel", "pascal" or "preserve".
+    pub fn from_name(name: &str) -> Option<NameCase> {
+        match name.to_ascii_lowercase().trim() {
+            "preserve" | "keep" | "none" | "verbatim" => Some(NameCase::Preserve),
+            "upper" | "screaming" | "screaming_snake" | "constant" => Some(NameCase::Upper),
+            "lower" | "snake" => Some(NameCase::Lower),
+            "camel" | "camelcase" => Some(NameCase::Camel),
+            "pascal" | "pascalcase" => Some(NameCase::Pascal),
+            _ => None,
+        }
+    }
+
+    /// Applies the case policy to a name. Words are separated by underscores, dashes, dots,
+    /// spaces, and lowercase to uppercase transitions, so "test_txt", "test.txt" and "testTxt"
+    /// all become "TEST_TXT" in upper case.
+    pub fn apply(self, name: &str) -> String {
+        let mut words: Vec<String> = Vec::new();
+        let mut previous: Option<char> = None;
+        for c in name.chars() {
+            if matches!(c, '_' | '-' | '.' | ' ') {
+                words.push(String::new());
+            } else {
+                let boundary = c.is_uppercase()
+                    && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit());
+                match words.last_mut() {
+                    Some(word) if !boundary => word.push(c),
+                    _ => words.push(c.to_string()),
+                }
+            }
+            previous = Some(c);
+        }
+        let words = words
+            .into_iter()
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<String>>();
+
+        let capitalized = |word: &String| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        };
+        match self {
+            NameCase::Preserve => name.to_string(),
+            NameCase::Upper => words
+                .iter()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<String>>()
+                .join("_"),
+            NameCase::Lower => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<String>>()
+                .join("_"),
+            NameCase::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalized(word)
+                    }
+                })
+                .collect(),
+            NameCase::Pascal => words.iter().map(capitalized).collect(),
+        }
+    }
+}
+
+/// The keywords of C and C++, as the C backends are used for both.
+#[rustfmt::skip]
+const C_KEYWORDS: &[&str] = &[
+    "alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor", "bool", "break",
+    "case", "catch", "char", "char8_t", "char16_t", "char32_t", "class", "co_await", "co_return",
+    "co_yield", "compl", "concept", "const", "const_cast", "consteval", "constexpr", "constinit",
+    "continue", "decltype", "default", "delete", "do", "double", "dynamic_cast", "else", "enum",
+    "explicit", "export", "extern", "false", "float", "for", "friend", "goto", "if", "inline",
+    "int", "long", "mutable", "namespace", "new", "noexcept", "not", "not_eq", "nullptr",
+    "operator", "or", "or_eq", "private", "protected", "public", "register", "reinterpret_cast",
+    "requires", "restrict", "return", "short", "signed", "sizeof", "static", "static_assert",
+    "static_cast", "struct", "switch", "template", "this", "thread_local", "throw", "true", "try",
+    "typedef", "typeid", "typename", "union", "unsigned", "using", "virtual", "void", "volatile",
+    "wchar_t", "while", "xor", "xor_eq", "_Alignas", "_Alignof", "_Atomic", "_Bool", "_Complex",
+    "_Generic", "_Imaginary", "_Noreturn", "_Static_assert", "_Thread_local",
+];
+
+/// The strict and reserved keywords of Rust.
+#[rustfmt::skip]
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// The keywords of C#, contextual keywords are valid identifiers and are not listed.
+#[rustfmt::skip]
+const CSHARP_KEYWORDS: &[&str] = &[
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class",
+    "const", "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event",
+    "explicit", "extern", "false", "finally", "fixed", "float", "for", "foreach", "goto", "if",
+    "implicit", "in", "int", "interface", "internal", "is", "lock", "long", "namespace", "new",
+    "null", "object", "operator", "out", "override", "params", "private", "protected", "public",
+    "readonly", "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc", "static",
+    "string", "struct", "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong",
+    "unchecked", "unsafe", "ushort", "using", "virtual", "void", "volatile", "while",
+];
+
+/// The keywords of Python 3.
+#[rustfmt::skip]
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// The reserved words of Javascript and Typescript.
+#[rustfmt::skip]
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+    "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for", "function",
+    "if", "implements", "import", "in", "instanceof", "interface", "let", "new", "null", "package",
+    "private", "protected", "public", "return", "static", "super", "switch", "this", "throw",
+    "true", "try", "typeof", "var", "void", "while", "with", "yield",
+];
+
+/// The registers of the 6502, which can't be used as labels.
+const ASM_6502_KEYWORDS: &[&str] = &["A", "X", "Y", "a", "x", "y"];
+
+/// The registers and conditions of the Z80, which can't be used as labels.
+#[rustfmt::skip]
+const Z80_KEYWORDS: &[&str] = &[
+    "A", "B", "C", "D", "E", "H", "L", "I", "R", "AF", "BC", "DE", "HL", "IX", "IY", "SP", "NZ",
+    "Z", "NC", "PO", "PE", "P", "M", "a", "b", "c", "d", "e", "h", "l", "i", "r", "af", "bc", "de",
+    "hl", "ix", "iy", "sp", "nz", "z", "nc", "po", "pe", "p", "m",
+];
+
+/// The keywords of Go.
+#[rustfmt::skip]
+const GO_KEYWORDS: &[&str] = &[
+    "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough", "for",
+    "func", "go", "goto", "if", "import", "interface", "map", "package", "range", "return",
+    "select", "struct", "switch", "type", "var",
+];
+
+/// The keywords and literals of Java.
+#[rustfmt::skip]
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short",
+    "static", "strictfp", "super", "switch", "synchronized", "this", "throw", "throws", "transient",
+    "true", "try", "void", "volatile", "while", "_",
+];
+
+/// The keywords and literals of Zig.
+#[rustfmt::skip]
+const ZIG_KEYWORDS: &[&str] = &[
+    "addrspace", "align", "allowzero", "and", "anyframe", "anytype", "asm", "async", "await",
+    "break", "callconv", "catch", "comptime", "const", "continue", "defer", "else", "enum",
+    "errdefer", "error", "export", "extern", "false", "fn", "for", "if", "inline", "linksection",
+    "noalias", "noinline", "nosuspend", "null", "opaque", "or", "orelse", "packed", "pub", "resume",
+    "return", "struct", "suspend", "switch", "test", "threadlocal", "true", "try", "undefined",
+    "union", "unreachable", "usingnamespace", "var", "volatile", "while",
+];
+
+/// The keywords of Swift that can't be used as identifiers without backticks.
+#[rustfmt::skip]
+const SWIFT_KEYWORDS: &[&str] = &[
+    "Any", "Self", "as", "associatedtype", "break", "case", "catch", "class", "continue", "default",
+    "defer", "deinit", "do", "else", "enum", "extension", "fallthrough", "false", "fileprivate",
+    "for", "func", "guard", "if", "import", "in", "init", "inout", "internal", "is", "let", "nil",
+    "open", "operator", "private", "precedencegroup", "protocol", "public", "repeat", "rethrows",
+    "return", "self", "static", "struct", "subscript", "super", "switch", "throw", "throws", "true",
+    "try", "typealias", "var", "where", "while",
+];
+
+/// The hard keywords of Kotlin, soft keywords are valid identifiers and are not listed.
+#[rustfmt::skip]
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in",
+    "interface", "is", "null", "object", "package", "return", "super", "this", "throw", "true",
+    "try", "typealias", "typeof", "val", "var", "when", "while",
+];
+
+/// The keywords of Nim.
+#[rustfmt::skip]
+const NIM_KEYWORDS: &[&str] = &[
+    "addr", "and", "as", "asm", "bind", "block", "break", "case", "cast", "concept", "const",
+    "continue", "converter", "defer", "discard", "distinct", "div", "do", "elif", "else", "end",
+    "enum", "except", "export", "finally", "for", "from", "func", "if", "import", "in", "include",
+    "interface", "is", "isnot", "iterator", "let", "macro", "method", "mixin", "mod", "nil", "not",
+    "notin", "object", "of", "or", "out", "proc", "ptr", "raise", "ref", "return", "shl", "shr",
+    "static", "template", "try", "tuple", "type", "using", "var", "when", "while", "xor", "yield",
+];
+
+/// The keywords of D.
+#[rustfmt::skip]
+const D_KEYWORDS: &[&str] = &[
+    "abstract", "alias", "align", "asm", "assert", "auto", "body", "bool", "break", "byte", "case",
+    "cast", "catch", "cdouble", "cent", "cfloat", "char", "class", "const", "continue", "creal",
+    "dchar", "debug", "default", "delegate", "delete", "deprecated", "do", "double", "else", "enum",
+    "export", "extern", "false", "final", "finally", "float", "for", "foreach", "foreach_reverse",
+    "function", "goto", "idouble", "if", "ifloat", "immutable", "import", "in", "inout", "int",
+    "interface", "invariant", "ireal", "is", "lazy", "long", "macro", "mixin", "module", "new",
+    "nothrow", "null", "out", "override", "package", "pragma", "private", "protected", "public",
+    "pure", "real", "ref", "return", "scope", "shared", "short", "static", "struct", "super",
+    "switch", "synchronized", "template", "this", "throw", "true", "try", "typeid", "typeof",
+    "ubyte", "ucent", "uint", "ulong", "union", "unittest", "ushort", "version", "void", "wchar",
+    "while", "with",
+];
+
+/// The reserved words of Free Pascal and Delphi, in lowercase as Pascal ignores the case.
+#[rustfmt::skip]
+const PASCAL_KEYWORDS: &[&str] = &[
+    "and", "array", "as", "asm", "begin", "case", "class", "const", "constructor", "destructor",
+    "dispinterface", "div", "do", "downto", "else", "end", "except", "exports", "file",
+    "finalization", "finally", "for", "function", "goto", "if", "implementation", "in", "inherited",
+    "initialization", "inline", "interface", "is", "label", "library", "mod", "nil", "not",
+    "object", "of", "on", "operator", "or", "out", "packed", "procedure", "program", "property",
+    "raise", "record", "repeat", "resourcestring", "self", "set", "shl", "shr", "string", "then",
+    "threadvar", "to", "try", "type", "unit", "until", "uses", "var", "while", "with", "xor",
+];
+
+/// The reserved words of Ada, in lowercase as Ada ignores the case.
+#[rustfmt::skip]
+const ADA_KEYWORDS: &[&str] = &[
+    "abort", "abs", "abstract", "accept", "access", "aliased", "all", "and", "array", "at", "begin",
+    "body", "case", "constant", "declare", "delay", "delta", "digits", "do", "else", "elsif", "end",
+    "entry", "exception", "exit", "for", "function", "generic", "goto", "if", "in", "interface",
+    "is", "limited", "loop", "mod", "new", "not", "null", "of", "or", "others", "out", "overriding",
+    "package", "parallel", "pragma", "private", "procedure", "protected", "raise", "range",
+    "record", "rem", "renames", "requeue", "return", "reverse", "select", "separate", "some",
+    "subtype", "synchronized", "tagged", "task", "terminate", "then", "type", "until", "use",
+    "when", "while", "with", "xor",
+];
+
+/// The registers of x86 and x86-64, which can't be used as labels.
+#[rustfmt::skip]
+const X86_KEYWORDS: &[&str] = &[
+    "al", "ah", "ax", "eax", "rax", "bl", "bh", "bx", "ebx", "rbx", "cl", "ch", "cx", "ecx", "rcx",
+    "dl", "dh", "dx", "edx", "rdx", "si", "esi", "rsi", "sil", "di", "edi", "rdi", "dil", "bp",
+    "ebp", "rbp", "bpl", "sp", "esp", "rsp", "spl", "r8", "r9", "r10", "r11", "r12", "r13", "r14",
+    "r15", "cs", "ds", "es", "fs", "gs", "ss", "AL", "AH", "AX", "EAX", "RAX", "BL", "BH", "BX",
+    "EBX", "RBX", "CL", "CH", "CX", "ECX", "RCX", "DL", "DH", "DX", "EDX", "RDX", "SI", "ESI",
+    "RSI", "SIL", "DI", "EDI", "RDI", "DIL", "BP", "EBP", "RBP", "BPL", "SP", "ESP", "RSP", "SPL",
+    "R8", "R9", "R10", "R11", "R12", "R13", "R14", "R15", "CS", "DS", "ES", "FS", "GS", "SS",
+];
+
+/// The keywords of Lua.
+#[rustfmt::skip]
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// The reserved words of PHP, in lowercase as PHP ignores their case.
+#[rustfmt::skip]
+const PHP_KEYWORDS: &[&str] = &[
+    "__halt_compiler", "abstract", "and", "array", "as", "break", "callable", "case", "catch",
+    "class", "clone", "const", "continue", "declare", "default", "die", "do", "echo", "else",
+    "elseif", "empty", "enddeclare", "endfor", "endforeach", "endif", "endswitch", "endwhile",
+    "enum", "eval", "exit", "extends", "false", "final", "finally", "fn", "for", "foreach",
+    "function", "global", "goto", "if", "implements", "include", "include_once", "instanceof",
+    "insteadof", "interface", "isset", "list", "match", "namespace", "new", "null", "or", "print",
+    "private", "protected", "public", "readonly", "require", "require_once", "return", "static",
+    "switch", "throw", "trait", "true", "try", "unset", "use", "var", "while", "xor", "yield",
+];
+
+/// The keywords of Ruby.
+#[rustfmt::skip]
+const RUBY_KEYWORDS: &[&str] = &[
+    "BEGIN", "END", "__ENCODING__", "__FILE__", "__LINE__", "alias", "and", "begin", "break",
+    "case", "class", "def", "defined?", "do", "else", "elsif", "end", "ensure", "false", "for",
+    "if", "in", "module", "next", "nil", "not", "or", "redo", "rescue", "retry", "return", "self",
+    "super", "then", "true", "undef", "unless", "until", "when", "while", "yield",
+];
+
+/// The special variables of Perl that can't be declared with my, or shouldn't be.
+#[rustfmt::skip]
+const PERL_KEYWORDS: &[&str] = &[
+    "_", "a", "b",
+];
+
+/// The reserved words of Haskell.
+#[rustfmt::skip]
+const HASKELL_KEYWORDS: &[&str] = &[
+    "_", "case", "class", "data", "default", "deriving", "do", "else", "foreign", "if", "import",
+    "in", "infix", "infixl", "infixr", "instance", "let", "module", "newtype", "of", "then", "type",
+    "where",
+];
+
+/// The reserved words of OCaml.
+#[rustfmt::skip]
+const OCAML_KEYWORDS: &[&str] = &[
+    "_", "and", "as", "assert", "asr", "begin", "class", "constraint", "do", "done", "downto",
+    "else", "end", "exception", "external", "false", "for", "fun", "function", "functor", "if",
+    "in", "include", "inherit", "initializer", "land", "lazy", "let", "lor", "lsl", "lsr", "lxor",
+    "match", "method", "mod", "module", "mutable", "new", "nonrec", "object", "of", "open", "or",
+    "private", "rec", "sig", "struct", "then", "to", "true", "try", "type", "val", "virtual",
+    "when", "while", "with",
+];
+
+/// The module attributes Elixir gives a meaning to.
+#[rustfmt::skip]
+const ELIXIR_KEYWORDS: &[&str] = &[
+    "after_compile", "before_compile", "behaviour", "callback", "compile", "derive", "deprecated",
+    "dialyzer", "doc", "enforce_keys", "external_resource", "file", "impl", "macrocallback",
+    "moduledoc", "on_definition", "on_load", "opaque", "optional_callbacks", "since", "spec",
+    "type", "typedoc", "typep", "vsn",
+];
+
+/// The macros Erlang predefines.
+#[rustfmt::skip]
+const ERLANG_KEYWORDS: &[&str] = &[
+    "MODULE", "MODULE_STRING", "FILE", "LINE", "MACHINE", "FUNCTION_NAME", "FUNCTION_ARITY",
+    "OTP_RELEASE", "FEATURE_AVAILABLE", "FEATURE_ENABLED",
+];
+
+/// The reserved words of Scala.
+#[rustfmt::skip]
+const SCALA_KEYWORDS: &[&str] = &[
+    "_", "abstract", "case", "catch", "class", "def", "do", "else", "enum", "export", "extends",
+    "false", "final", "finally", "for", "forSome", "given", "if", "implicit", "import", "lazy",
+    "macro", "match", "new", "null", "object", "override", "package", "private", "protected",
+    "return", "sealed", "super", "then", "this", "throw", "trait", "true", "try", "type", "val",
+    "var", "while", "with", "yield",
+];
+
+/// The reserved words of Dart.
+#[rustfmt::skip]
+const DART_KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+/// The reserved words of Julia.
+#[rustfmt::skip]
+const JULIA_KEYWORDS: &[&str] = &[
+    "baremodule", "begin", "break", "catch", "const", "continue", "do", "else", "elseif", "end",
+    "export", "false", "finally", "for", "function", "global", "if", "import", "let", "local",
+    "macro", "module", "quote", "return", "struct", "true", "try", "using", "while",
+];
+
+/// The reserved words of R.
+#[rustfmt::skip]
+const R_KEYWORDS: &[&str] = &[
+    "if", "else", "repeat", "while", "function", "for", "in", "next", "break", "TRUE", "FALSE",
+    "NULL", "Inf", "NaN", "NA", "NA_integer_", "NA_real_", "NA_character_", "NA_complex_",
+];
+
+/// The reserved words of MATLAB and Octave.
+#[rustfmt::skip]
+const MATLAB_KEYWORDS: &[&str] = &[
+    "break", "case", "catch", "classdef", "continue", "do", "else", "elseif", "end",
+    "end_try_catch", "end_unwind_protect", "endfor", "endfunction", "endif", "endwhile", "for",
+    "function", "global", "if", "otherwise", "parfor", "persistent", "return", "spmd", "switch",
+    "try", "until", "unwind_protect", "while",
+];
+
+/// The keywords Objective-C adds to the ones of C.
+#[rustfmt::skip]
+const OBJC_KEYWORDS: &[&str] = &[
+    "BOOL", "Class", "IMP", "NO", "SEL", "YES", "id", "nil", "self", "super",
+];
+
+/// The reserved words of VHDL.
+#[rustfmt::skip]
+const VHDL_KEYWORDS: &[&str] = &[
+    "abs", "access", "after", "alias", "all", "and", "architecture", "array", "assert", "attribute",
+    "begin", "block", "body", "buffer", "bus", "case", "component", "configuration", "constant",
+    "disconnect", "downto", "else", "elsif", "end", "entity", "exit", "file", "for", "function",
+    "generate", "generic", "group", "guarded", "if", "impure", "in", "inertial", "inout", "is",
+    "label", "library", "linkage", "literal", "loop", "map", "mod", "nand", "new", "next", "nor",
+    "not", "null", "of", "on", "open", "or", "others", "out", "package", "port", "postponed",
+    "procedure", "process", "pure", "range", "record", "register", "reject", "rem", "report",
+    "return", "rol", "ror", "select", "severity", "signal", "shared", "sla", "sll", "sra", "srl",
+    "subtype", "then", "to", "transport", "type", "unaffected", "units", "until", "use", "variable",
+    "wait", "when", "while", "with", "xnor", "xor",
+];
+
+/// The automatic variables of PowerShell, which can't be assigned.
+#[rustfmt::skip]
+const POWERSHELL_KEYWORDS: &[&str] = &[
+    "_", "args", "error", "event", "false", "foreach", "home", "host", "input", "matches",
+    "myinvocation", "null", "pid", "profile", "psitem", "pwd", "sender", "this", "true",
+];
+
+/// The variables the shells set or read themselves.
+#[rustfmt::skip]
+const SHELL_KEYWORDS: &[&str] = &[
+    "BASH", "BASHPID", "CDPATH", "ENV", "EUID", "GROUPS", "HOME", "HOSTNAME", "IFS", "LANG",
+    "LINENO", "OLDPWD", "OPTARG", "OPTIND", "PATH", "PPID", "PS1", "PS2", "PS4", "PWD", "RANDOM",
+    "SECONDS", "SHELL", "SHELLOPTS", "UID",
+];
+
+/// The reserved words of VB.NET, which like its names ignore the case.
+#[rustfmt::skip]
+const VBNET_KEYWORDS: &[&str] = &[
+    "addhandler", "addressof", "alias", "and", "andalso", "as", "boolean", "byref", "byte", "byval",
+    "call", "case", "catch", "cbool", "cbyte", "cchar", "cdate", "cdbl", "cdec", "char", "cint",
+    "class", "clng", "cobj", "const", "continue", "csbyte", "cshort", "csng", "cstr", "ctype",
+    "cuint", "culng", "cushort", "date", "decimal", "declare", "default", "delegate", "dim",
+    "directcast", "do", "double", "each", "else", "elseif", "end", "endif", "enum", "erase",
+    "error", "event", "exit", "false", "finally", "for", "friend", "function", "get", "gettype",
+    "getxmlnamespace", "global", "gosub", "goto", "handles", "if", "implements", "imports", "in",
+    "inherits", "integer", "interface", "is", "isnot", "let", "lib", "like", "long", "loop", "me",
+    "mod", "module", "mustinherit", "mustoverride", "mybase", "myclass", "namespace", "narrowing",
+    "new", "next", "not", "nothing", "notinheritable", "notoverridable", "object", "of", "on",
+    "operator", "option", "optional", "or", "orelse", "overloads", "overridable", "overrides",
+    "paramarray", "partial", "private", "property", "protected", "public", "raiseevent", "readonly",
+    "redim", "rem", "removehandler", "resume", "return", "sbyte", "select", "set", "shadows",
+    "shared", "short", "single", "static", "step", "stop", "string", "structure", "sub", "synclock",
+    "then", "throw", "to", "true", "try", "trycast", "typeof", "uinteger", "ulong", "ushort",
+    "using", "variant", "wend", "when", "while", "widening", "with", "withevents", "writeonly",
+    "xor",
+];
+
+/// The reserved words and the constants of GDScript.
+#[rustfmt::skip]
+const GDSCRIPT_KEYWORDS: &[&str] = &[
+    "INF", "NAN", "PI", "TAU", "and", "as", "assert", "await", "break", "breakpoint", "class",
+    "class_name", "const", "continue", "elif", "else", "enum", "extends", "false", "for", "func",
+    "if", "in", "is", "match", "not", "null", "or", "pass", "preload", "return", "self", "signal",
+    "static", "super", "true", "var", "void", "while", "yield",
+];
+
+/// The reserved words of F#.
+#[rustfmt::skip]
+const FSHARP_KEYWORDS: &[&str] = &[
+    "_", "abstract", "and", "as", "assert", "base", "begin", "class", "default", "delegate", "do",
+    "done", "downcast", "downto", "elif", "else", "end", "exception", "extern", "false", "finally",
+    "fixed", "for", "fun", "function", "global", "if", "in", "inherit", "inline", "interface",
+    "internal", "lazy", "let", "match", "member", "module", "mutable", "namespace", "new", "not",
+    "null", "of", "open", "or", "override", "private", "public", "rec", "return", "select", "sig",
+    "static", "struct", "then", "to", "true", "try", "type", "upcast", "use", "val", "void", "when",
+    "while", "with", "yield", "const",
+];
+
+/// The reserved words of Crystal.
+#[rustfmt::skip]
+const CRYSTAL_KEYWORDS: &[&str] = &[
+    "abstract", "alias", "annotation", "as", "asm", "begin", "break", "case", "class", "def", "do",
+    "else", "elsif", "end", "ensure", "enum", "extend", "false", "for", "fun", "if", "in",
+    "include", "instance_sizeof", "is_a?", "lib", "macro", "module", "next", "nil", "nil?", "of",
+    "offsetof", "out", "pointerof", "private", "protected", "require", "rescue", "responds_to?",
+    "return", "select", "self", "sizeof", "struct", "super", "then", "true", "type", "typeof",
+    "uninitialized", "union", "unless", "until", "verbatim", "when", "while", "with", "yield",
+];
+
+/// The syntactic keywords of Scheme.
+#[rustfmt::skip]
+const SCHEME_KEYWORDS: &[&str] = &[
+    "and", "begin", "case", "cond", "define", "define-record-type", "define-syntax", "delay", "do",
+    "else", "guard", "if", "lambda", "let", "let*", "letrec", "letrec*", "or", "quasiquote",
+    "quote", "set!", "unless", "unquote", "when",
+];
+
+/// The keywords of Groovy.
+#[rustfmt::skip]
+const GROOVY_KEYWORDS: &[&str] = &[
+    "abstract", "as", "assert", "boolean", "break", "byte", "case", "catch", "char", "class",
+    "const", "continue", "def", "default", "do", "double", "else", "enum", "extends", "false",
+    "final", "finally", "float", "for", "goto", "if", "implements", "import", "in", "instanceof",
+    "int", "interface", "long", "native", "new", "null", "package", "private", "protected",
+    "public", "return", "short", "static", "strictfp", "super", "switch", "synchronized", "this",
+    "threadsafe", "throw", "throws", "trait", "transient", "true", "try", "var", "void", "volatile",
+    "while",
+];
+
+/// The keywords and reserved words of Solidity.
+#[rustfmt::skip]
+const SOLIDITY_KEYWORDS: &[&str] = &[
+    "abstract", "address", "after", "alias", "anonymous", "apply", "assembly", "auto", "bool",
+    "break", "byte", "bytes", "calldata", "case", "catch", "constant", "constructor", "continue",
+    "contract", "copyof", "default", "define", "delete", "do", "else", "emit", "enum", "error",
+    "event", "external", "fallback", "false", "final", "for", "function", "if", "immutable",
+    "implements", "import", "in", "indexed", "inline", "interface", "internal", "is", "let",
+    "library", "macro", "mapping", "match", "memory", "modifier", "mutable", "new", "null", "of",
+    "override", "partial", "payable", "pragma", "private", "promise", "public", "pure", "receive",
+    "reference", "relocatable", "return", "returns", "revert", "sealed", "sizeof", "static",
+    "storage", "string", "struct", "supports", "switch", "this", "true", "try", "type", "typedef",
+    "typeof", "uint", "unchecked", "using", "var", "view", "virtual", "while",
+];
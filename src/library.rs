@@ -1,287 +1,3357 @@
+use crate::hash::*;
+use crate::language::NameCase;
+use std::ffi::OsString;
+use std::io::Read;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
 /// Loads a Vec<u8> from a file.
-pub fn file_to_binary(file: &str) -> Result<Vec<u8>, std::io::Error> {
-    std::fs::read(file)
+pub fn file_to_binary(file: &Path) -> Result<Vec<u8>, std::io::Error> {
+    std::fs::read(long_path(file))
 }
 
-/// Converts an array of bytes to hex disassembly.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], the function returns:
-/// 00000000  00 01 02 03                                       |....|
-/// 00000004
-pub fn binary_to_hex(binary: &[u8]) -> String {
-    let mut out = String::new();
-    let mut i = 0;
-    while i < binary.len() {
-        out.push_str(&format!("{:08x}  ", i));
-        for j in 0..16 {
-            if i + j < binary.len() {
-                out.push_str(&format!("{:02x} ", binary[i + j]));
-            } else {
-                out.push_str("   ");
+/// Loads a Vec<u8> from a reader, such as stdin.
+pub fn reader_to_binary(mut reader: impl Read) -> Result<Vec<u8>, std::io::Error> {
+    let mut binary = Vec::new();
+    reader.read_to_end(&mut binary)?;
+    Ok(binary)
+}
+
+/// Whether an input file is `-`, which stands for stdin.
+pub fn is_stdin(file: &Path) -> bool {
+    file.as_os_str() == "-"
+}
+
+/// The name of the constant of an input given without one, its file name with the characters
+/// that can't be in a name replaced by `_`. None for stdin, which has no file name.
+/// For exemple, with file = "assets/logo.png", the function returns Some("logo_png").
+pub fn name_from_file(file: &Path) -> Option<String> {
+    if is_stdin(file) {
+        return None;
+    }
+    let file_name = file.file_name()?.to_string_lossy();
+    Some(file_name.replace(|c: char| !c.is_alphanumeric(), "_"))
+}
+
+/// Loads a Vec<u8> from an input file, or from stdin when the file is `-`.
+pub fn input_to_binary(file: &Path) -> Result<Vec<u8>, std::io::Error> {
+    match is_stdin(file) {
+        true => reader_to_binary(std::io::stdin().lock()),
+        false => file_to_binary(file),
+    }
+}
+
+/// Makes long paths usable on Windows by giving them the `\\?\` verbatim prefix (or
+/// `\\?\UNC\` for network shares), which lifts the 260 characters limit of the Win32 API.
+/// Short paths, already verbatim paths and paths on other platforms are returned as is.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::{OsStrExt, OsStringExt};
+        use std::path::{Component, Prefix};
+
+        if path.as_os_str().len() < 248 {
+            return path.to_path_buf();
+        }
+        // absolute() resolves `..` and turns slashes into backslashes, which verbatim paths need.
+        let absolute = match std::path::absolute(path) {
+            Ok(absolute) => absolute,
+            Err(_) => return path.to_path_buf(),
+        };
+        // `C:\dir` becomes `\\?\C:\dir` and `\\server\share` becomes `\\?\UNC\server\share`.
+        let (mut verbatim, skipped) = match absolute.components().next() {
+            Some(Component::Prefix(prefix)) => match prefix.kind() {
+                Prefix::Disk(_) => (OsString::from(r"\\?\"), 0),
+                Prefix::UNC(..) => (OsString::from(r"\\?\UNC"), 1),
+                _ => return absolute,
+            },
+            _ => return absolute,
+        };
+        let rest = absolute
+            .as_os_str()
+            .encode_wide()
+            .skip(skipped)
+            .collect::<Vec<u16>>();
+        verbatim.push(OsString::from_wide(&rest));
+        PathBuf::from(verbatim)
+    }
+    #[cfg(not(windows))]
+    path.to_path_buf()
+}
+
+/// Writes a file atomically: the content goes to a temporary file next to it, which is then
+/// renamed over it, so an interrupted run or a full disk never leaves a truncated file behind.
+/// With `fsync`, the data (and on unix the directory entry) is flushed to the disk too.
+pub fn write_atomic(file: &Path, content: &[u8], fsync: bool) -> Result<(), std::io::Error> {
+    use std::io::Write;
+
+    let path = long_path(file);
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a file path"))?;
+    let mut temporary_name = OsString::from(".");
+    temporary_name.push(file_name);
+    temporary_name.push(format!(".{}.tmp", std::process::id()));
+    let temporary = directory.join(temporary_name);
+
+    let result = (|| {
+        let mut output = std::fs::File::create(&temporary)?;
+        output.write_all(content)?;
+        if fsync {
+            output.sync_all()?;
+        }
+        drop(output);
+        std::fs::rename(&temporary, &path)?;
+        #[cfg(unix)]
+        if fsync {
+            std::fs::File::open(directory)?.sync_all()?;
+        }
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Returns the path of a file relative to a directory, going up with `..` where needed, or its
+/// absolute path when there is none, like between two Windows drives.
+/// For exemple, with file = "assets/logo.png" and directory = "src", the function returns
+/// "../assets/logo.png".
+pub fn relative_path(file: &Path, directory: &Path) -> PathBuf {
+    let absolute = |path: &Path| {
+        std::fs::canonicalize(path)
+            .or_else(|_| std::path::absolute(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+    let (file, directory) = (absolute(file), absolute(directory));
+    let file_components = file.components().collect::<Vec<_>>();
+    let directory_components = directory.components().collect::<Vec<_>>();
+    if file_components.first() != directory_components.first() {
+        return file;
+    }
+    let common = file_components
+        .iter()
+        .zip(&directory_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in common..directory_components.len() {
+        relative.push("..");
+    }
+    for component in &file_components[common..] {
+        relative.push(component);
+    }
+    relative
+}
+
+/// Summarizes the line differences between the `existing` content of a file and the
+/// `expected` one, or returns None if they are identical. Lines are compared by position,
+/// and only the first 5 differing lines are shown, for exemple:
+/// 1 line(s) differ, 12 line(s) existing, 12 line(s) expected
+/// line 3:
+/// -     0x00, 0x01, 0x02, 0x03
+/// +     0x00, 0x01, 0x02, 0x04
+pub fn diff_summary(existing: &str, expected: &str) -> Option<String> {
+    if existing == expected {
+        return None;
+    }
+    let existing_lines = existing.lines().collect::<Vec<&str>>();
+    let expected_lines = expected.lines().collect::<Vec<&str>>();
+    let differing = (0..existing_lines.len().max(expected_lines.len()))
+        .filter(|&i| existing_lines.get(i) != expected_lines.get(i))
+        .collect::<Vec<usize>>();
+
+    let mut out = format!(
+        "{} line(s) differ, {} line(s) existing, {} line(s) expected\n",
+        differing.len(),
+        existing_lines.len(),
+        expected_lines.len()
+    );
+    if differing.is_empty() {
+        // Same lines, so the difference is in the line endings or the final newline.
+        out.push_str("the line endings or the final newline differ\n");
+    }
+    for &i in differing.iter().take(5) {
+        out.push_str(&format!("line {}:\n", i + 1));
+        if let Some(line) = existing_lines.get(i) {
+            out.push_str(&format!("- {}\n", line));
+        }
+        if let Some(line) = expected_lines.get(i) {
+            out.push_str(&format!("+ {}\n", line));
+        }
+    }
+    if differing.len() > 5 {
+        out.push_str(&format!("... and {} more\n", differing.len() - 5));
+    }
+    Some(out)
+}
+
+/// Replaces the lines between the `bin2const:begin NAME` and `bin2const:end` markers of a
+/// source file with `content`, keeping the markers and everything around them. The markers can
+/// be in any kind of comment, and the source must use \n line endings.
+/// For exemple, with source = "// bin2const:begin LOGO\nold\n// bin2const:end\nfn main() {}\n",
+/// name = "LOGO" and content = "new\n", the function returns:
+/// // bin2const:begin LOGO
+/// new
+/// // bin2const:end
+/// fn main() {}
+pub fn replace_region(source: &str, name: &str, content: &str) -> Result<String, String> {
+    let lines = source.split_inclusive('\n').collect::<Vec<&str>>();
+    let is_begin = |line: &str| match line.split_once("bin2const:begin") {
+        Some((_, rest)) => {
+            let rest = rest.trim();
+            // Block comments are closed on the same line.
+            let rest = rest
+                .trim_end_matches("*/")
+                .trim_end_matches("-->")
+                .trim_end();
+            rest == name
+        }
+        None => false,
+    };
+    let begin = lines
+        .iter()
+        .position(|line| is_begin(line))
+        .ok_or(format!("No \"bin2const:begin {}\" marker", name))?;
+    let end = lines[begin + 1..]
+        .iter()
+        .position(|line| line.contains("bin2const:end"))
+        .map(|i| begin + 1 + i)
+        .ok_or(format!(
+            "No \"bin2const:end\" marker after the {} one",
+            name
+        ))?;
+
+    let mut out = lines[..=begin].concat();
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(content);
+    if !content.is_empty() && !content.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&lines[end..].concat());
+    Ok(out)
+}
+
+/// Replaces the value of the constant `name` of a source file with the one it has in
+/// `declaration`, keeping the rest of the file and of its declaration (visibility, type,
+/// attributes) untouched. A length in the declared type, like the 3 of `[u8; 3]`, is updated too.
+/// For exemple, with source = "pub static TEST_TXT: [u8; 2] = [0x00, 0x01];\n" and
+/// declaration = "const TEST_TXT: [u8; 3] = [\n    0x61, 0x62, 0x63\n];\n", the function returns:
+/// pub static TEST_TXT: [u8; 3] = [
+///     0x61, 0x62, 0x63
+/// ];
+pub fn replace_constant(source: &str, name: &str, declaration: &str) -> Result<String, String> {
+    let (new_head, new_value) = find_initializer(declaration, name)
+        .ok_or(format!("{} isn't declared in the generated code", name))?;
+    let (head, value) =
+        find_initializer(source, name).ok_or(format!("No constant named {}", name))?;
+
+    let mut out = source[..head.start].to_string();
+    match (
+        type_length(&source[head.clone()]),
+        type_length(&declaration[new_head.clone()]),
+    ) {
+        (Some(length), Some(new_length)) => {
+            let new_length = &declaration[new_head.start..][new_length];
+            out.push_str(&source[head.start..head.start + length.start]);
+            out.push_str(new_length);
+            out.push_str(&source[head.start + length.end..head.end]);
+        }
+        _ => out.push_str(&source[head]),
+    }
+    out.push_str(&declaration[new_value]);
+    out.push_str(&source[value.end..]);
+    Ok(out)
+}
+
+/// Lays out the data of the records of an Intel HEX or S-record file by their addresses, from the
+/// lowest one, the gaps between them filled with `fill`. Records can't overlap, and can't span
+/// more than `max_size` bytes, as sparse records such as the option bytes of a microcontroller
+/// far from its flash would take gigabytes once their gap is filled.
+/// For exemple, with records = vec![(0x102, vec![0x02]), (0x100, vec![0x00])], fill = 0xff and
+/// max_size = 16, the function returns vec![0x00, 0xff, 0x02].
+pub fn place_records(
+    mut records: Vec<(usize, Vec<u8>)>,
+    fill: u8,
+    max_size: usize,
+) -> Result<Vec<u8>, String> {
+    records.retain(|(_, data)| !data.is_empty());
+    records.sort_by_key(|(address, _)| *address);
+    let Some(base) = records.first().map(|(address, _)| *address) else {
+        return Ok(Vec::new());
+    };
+    let end = records
+        .iter()
+        .map(|(address, data)| address.saturating_add(data.len()))
+        .max()
+        .unwrap_or(base);
+    if end - base > max_size {
+        return Err(format!(
+            "the records span 0x{:x} to 0x{:x}, {} bytes, more than the --max-size of {} bytes, \
+             split the file by address or raise --max-size",
+            base,
+            end,
+            end - base,
+            max_size
+        ));
+    }
+    let mut out = Vec::new();
+    for (address, data) in records {
+        if address < base + out.len() {
+            return Err(format!(
+                "the record at 0x{:x} overlaps the data before it, up to 0x{:x}",
+                address,
+                base + out.len()
+            ));
+        }
+        out.resize(address - base, fill);
+        out.extend_from_slice(&data);
+    }
+    Ok(out)
+}
+
+/// Returns the value the constant `name` is declared with in a source file, like its array.
+/// For exemple, with source = "const TEST_TXT: [u8; 2] = [0x00, 0xff];" and name = "TEST_TXT",
+/// the function returns Some("[0x00, 0xff]").
+pub fn constant_value<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    find_initializer(source, name).map(|(_, value)| &source[value])
+}
+
+/// Finds where the constant `name` is declared in a source file, as the part of its
+/// declaration between its name and its value, and its value. A declaration is a line where the
+/// name is followed by an `=`, or a `#define` of the name.
+fn find_initializer(source: &str, name: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    for (i, _) in source.match_indices(name) {
+        let name_end = i + name.len();
+        if source[..i].ends_with(is_identifier) || source[name_end..].starts_with(is_identifier) {
+            continue;
+        }
+        let line_start = source[..i].rfind('\n').map_or(0, |n| n + 1);
+        let line_end = source[name_end..]
+            .find('\n')
+            .map_or(source.len(), |n| name_end + n);
+        let after = &source[name_end..line_end];
+        let value_start = if source[line_start..i].trim() == "#define" {
+            name_end + (after.len() - after.trim_start_matches([' ', '\t']).len())
+        } else {
+            match after.find('=') {
+                Some(equal) if !after[equal + 1..].starts_with('=') => {
+                    let value = &source[name_end + equal + 1..];
+                    name_end + equal + 1 + (value.len() - value.trim_start().len())
+                }
+                _ => continue,
+            }
+        };
+        let value_end = value_start + value_length(&source[value_start..]);
+        return Some((name_end..value_start, value_start..value_end));
+    }
+    None
+}
+
+/// Returns the length of the value at the start of `source`, up to the `;` or the end of line
+/// ending it. Brackets and strings are skipped, as are the line breaks between the concatenated
+/// string literals of C and the `\` continued lines of the #defines.
+fn value_length(source: &str) -> usize {
+    let bytes = source.as_bytes();
+    let (mut depth, mut i) = (0usize, 0);
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' if source[i..].starts_with("\"\"\"") => {
+                i += source[i + 3..]
+                    .find("\"\"\"")
+                    .map_or(source.len() - i, |n| n + 6);
+                continue;
             }
-            if j % 4 == 3 {
-                out.push_str(" ");
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
             }
-        }
-        out.push_str(" |");
-        for j in 0..16 {
-            if i + j < binary.len() {
-                let c = binary[i + j];
-                if c >= 0x20 && c <= 0x7e {
-                    out.push(c as char);
-                } else {
-                    out.push('.');
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => break,
+            b'\n' if depth == 0 => {
+                let continued = source[..i].trim_end().ends_with('\\')
+                    || source[i..].trim_start().starts_with('"');
+                if !continued {
+                    break;
                 }
-            } else {
-                out.push(' ');
             }
+            _ => (),
+        }
+        i += 1;
+    }
+    source[..i.min(bytes.len())].trim_end().len()
+}
+
+/// Finds the length written in a declared type, as the last number closed by a `]`, like the 3
+/// of `: [u8; 3] ` or of `[3] `.
+fn type_length(head: &str) -> Option<Range<usize>> {
+    let bytes = head.as_bytes();
+    let end = head.rfind(']')?;
+    let digits_end = head[..end].trim_end().len();
+    let digits_start = head[..digits_end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |n| n + 1);
+    let valid = digits_start < digits_end
+        && (digits_start == 0 || !(bytes[digits_start - 1] as char).is_alphanumeric());
+    valid.then_some(digits_start..digits_end)
+}
+
+/// The encoding used to decode the text column of the disassemblies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Ebcdic,
+}
+
+impl Charset {
+    /// Parses a charset name such as "ascii", "utf8" or "utf-16le".
+    pub fn from_name(name: &str) -> Option<Charset> {
+        match name.to_ascii_lowercase().replace(['-', '_'], "").trim() {
+            "ascii" => Some(Charset::Ascii),
+            "utf8" => Some(Charset::Utf8),
+            "utf16" | "utf16le" => Some(Charset::Utf16Le),
+            "utf16be" => Some(Charset::Utf16Be),
+            "ebcdic" | "cp037" | "ibm037" => Some(Charset::Ebcdic),
+            _ => None,
         }
-        out.push_str("|\n");
+    }
+}
+
+/// The characters of the EBCDIC code page 037 (US/Canada), with a dot for control characters.
+#[rustfmt::skip]
+const EBCDIC: [char; 256] = [
+    '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.',
+    '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.',
+    '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.',
+    '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.',
+    ' ', '\u{a0}', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|',
+    '&', 'é', 'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬',
+    '-', '/', 'Â', 'Ä', 'À', 'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?',
+    'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', '`', ':', '#', '@', '\'', '=', '"',
+    'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«', '»', 'ð', 'ý', 'þ', '±',
+    '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸', 'Æ', '¤',
+    'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®',
+    '^', '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '.', 'ô', 'ö', 'ò', 'ó', 'õ',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ',
+    '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü', 'Ù', 'Ú', '.',
+];
+
+/// Shown in the text column for the bytes that continue a multi-byte character.
+const CONTINUATION_MARKER: char = '•';
+
+/// Converts an array of bytes to hex disassembly, starting at the offset `start`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and start = 0, the function returns:
+/// 00000000  00 01 02 03                                       |....|
+/// 00000004
+pub fn binary_to_hex(binary: &[u8], start: usize, charset: Charset) -> String {
+    let mut out = String::new();
+    let mut i = start;
+    while i < binary.len() {
+        hex_row(binary, i, charset, &mut out);
         i += 16;
     }
     out
 }
 
-/// Converts an array of bytes to binary disassembly.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], the function returns:
+/// Converts an array of bytes to binary disassembly, starting at the offset `start`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and start = 0, the function returns:
 /// 00000000  00000000 00000001 00000010 00000011                 |....|
 /// 00000004
-pub fn binary_to_binary(binary: &[u8]) -> String {
+pub fn binary_to_binary(binary: &[u8], start: usize, charset: Charset) -> String {
+    let mut out = String::new();
+    let mut i = start;
+    while i < binary.len() {
+        binary_row(binary, i, charset, &mut out);
+        i += 16;
+    }
+    out
+}
+
+/// Converts an array of bytes to the hex dump xxd prints per default, starting at the offset
+/// `start`: bytes grouped by 2 and an ASCII text column.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and start = 0, the function returns:
+/// 00000000: 0001 0203                                ....
+pub fn binary_to_xxd(binary: &[u8], start: usize) -> String {
     let mut out = String::new();
-    let mut i = 0;
+    let mut i = start;
     while i < binary.len() {
-        out.push_str(&format!("{:08x}  ", i));
+        out.push_str(&format!("{:08x}: ", i));
         for j in 0..16 {
-            if i + j < binary.len() {
-                out.push_str(&format!("{:08b} ", binary[i + j]));
-            } else {
-                out.push_str("         ");
-            }
-            if j % 4 == 3 {
-                out.push_str(" ");
+            match binary.get(i + j) {
+                Some(byte) => out.push_str(&format!("{:02x}", byte)),
+                None => out.push_str("  "),
             }
-        }
-        out.push_str(" |");
-        for j in 0..16 {
-            if i + j < binary.len() {
-                let c = binary[i + j];
-                if c >= 0x20 && c <= 0x7e {
-                    out.push(c as char);
-                } else {
-                    out.push('.');
-                }
-            } else {
+            if j % 2 == 1 {
                 out.push(' ');
             }
         }
-        out.push_str("|\n");
+        out.push(' ');
+        for byte in &binary[i..binary.len().min(i + 16)] {
+            out.push(match byte {
+                0x20..=0x7e => *byte as char,
+                _ => '.',
+            });
+        }
+        out.push('\n');
         i += 16;
     }
     out
 }
 
-/// Converts an array of bytes to a C constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// const unsigned char TEST_TXT[] = {
-///    0x00, 0x01, 0x02, 0x03
-/// };
-pub fn binary_to_c_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+/// Converts an array of bytes to the octal dump `od -b` prints, starting at the offset `start`:
+/// octal offsets and bytes, the lines repeating the one before them replaced by a `*`, and the
+/// offset of the end.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and start = 0, the function returns:
+/// 0000000 000 001 002 377
+/// 0000004
+pub fn binary_to_octal(binary: &[u8], start: usize) -> String {
     let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!(
-        "const unsigned char {}[] = {{\n{}",
-        name, generated_tabs
-    ));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
-        }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
+    let mut previous: Option<&[u8]> = None;
+    let mut squeezed = false;
+    let mut i = start;
+    while i < binary.len() {
+        let line = &binary[i..binary.len().min(i + 16)];
+        if previous == Some(line) {
+            if !squeezed {
+                out.push_str("*\n");
+                squeezed = true;
+            }
+        } else {
+            let bytes = line.iter().map(|byte| format!(" {:03o}", byte));
+            out.push_str(&format!("{:07o}{}\n", i, bytes.collect::<String>()));
+            squeezed = false;
         }
+        previous = Some(line);
+        i += 16;
     }
-    out.push_str("\n};\n");
+    out.push_str(&format!("{:07o}\n", binary.len().max(start)));
     out
 }
 
-/// Converts an array of bytes to a C/C++ #define.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// #define TEST_TXT_SIZE 4
-/// #define TEST_TXT { 0x00, 0x01, 0x02, 0x03 }
-/// It is capable of multi-line #define, for exemple:
-/// #define TEST_TXT { 0x00, 0x01, 0x02, 0x03, \
-///                   0x04, 0x05, 0x06, 0x07 }
-pub fn binary_to_c_define(binary: &[u8], name: &str, tab_size: usize) -> String {
-    let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
+/// Converts an array of bytes to a table with a row per byte, starting at the offset `start`:
+/// its offset, its value in hex and in decimal, and its character when it is printable ASCII.
+/// The cells are separated by `separator`, a comma for CSV, where a character needing it is
+/// quoted, or a tab for TSV, where the tab and newline characters are escaped as `\t` and `\n`.
+/// For exemple, with binary = &[0x2c, 0x61, 0xff], start = 0 and separator = ',', the function
+/// returns:
+/// offset,hex,dec,ascii
+/// 0,2c,44,","
+/// 1,61,97,a
+/// 2,ff,255,
+pub fn binary_to_table(binary: &[u8], start: usize, separator: char) -> String {
+    let mut out = ["offset", "hex", "dec", "ascii"].join(&separator.to_string());
+    out.push('\n');
+    let tsv = separator == '\t';
+    for (i, byte) in binary.iter().enumerate().skip(start) {
+        let ascii = match *byte as char {
+            '\t' if tsv => "\\t".to_string(),
+            '\n' if tsv => "\\n".to_string(),
+            c @ ' '..='~' if tsv => c.to_string(),
+            '"' => "\"\"\"\"".to_string(),
+            c @ ' '..='~' if c == separator => format!("\"{}\"", c),
+            c @ ' '..='~' => c.to_string(),
+            _ => String::new(),
+        };
+        out.push_str(&format!(
+            "{1}{0}{2:02x}{0}{2}{0}{3}\n",
+            separator, i, byte, ascii
+        ));
+    }
+    out
+}
+
+/// The style of the HTML hex dump, the byte classes only being colored with --color.
+const HTML_DUMP_STYLE: &str = "\
+<style>
+.bin2const-dump { border-collapse: collapse; font-family: monospace; }
+.bin2const-dump th, .bin2const-dump td { padding: 0 0.75em; text-align: left; white-space: pre; }
+.bin2const-dump th { border-bottom: 1px solid #888; }
+.bin2const-dump .offset { color: #888; }
+.bin2const-dump .null { color: #aaa; }
+.bin2const-dump .printable { color: #1a7f37; }
+.bin2const-dump .high { color: #cf222e; }
+</style>
+";
+
+/// The class of a byte in the colored HTML hex dump, None for the other control characters.
+fn html_byte_class(byte: u8) -> Option<&'static str> {
+    match byte {
+        0x00 => Some("null"),
+        0x20..=0x7e => Some("printable"),
+        0x80..=0xff => Some("high"),
+        _ => None,
+    }
+}
 
-    out.push_str(&format!("#define {}_SIZE {}\n", name, binary.len()));
-    out.push_str(&format!("#define {} {{", name));
+/// Converts an array of bytes to a hex dump in an HTML table, starting at the offset `start`,
+/// with its style: a row of 16 bytes per line with their offset, hex values and ASCII text.
+/// With `colored`, each byte is in a span of its class: null, printable or high (bit set).
+/// For exemple, with binary = &[0x00, 0x41, 0xff], start = 0 and colored = false, the function
+/// returns the style followed by:
+/// <table class="bin2const-dump">
+/// <tr><th>offset</th><th>hex</th><th>ascii</th></tr>
+/// <tr><td class="offset">00000000</td><td>00 41 ff</td><td>.A.</td></tr>
+/// </table>
+pub fn binary_to_html(binary: &[u8], start: usize, colored: bool) -> String {
+    let mut out = HTML_DUMP_STYLE.to_string();
+    out.push_str("<table class=\"bin2const-dump\">\n");
+    out.push_str("<tr><th>offset</th><th>hex</th><th>ascii</th></tr>\n");
+    let mut i = start;
+    while i < binary.len() {
+        let line = &binary[i..binary.len().min(i + 16)];
+        let cell = |text: String, byte: u8| match html_byte_class(byte) {
+            Some(class) if colored => format!("<span class=\"{}\">{}</span>", class, text),
+            _ => text,
+        };
+        let hex = line.iter().map(|byte| cell(format!("{:02x}", byte), *byte));
+        let ascii = line.iter().map(|byte| {
+            let text = match byte {
+                b'&' => "&amp;".to_string(),
+                b'<' => "&lt;".to_string(),
+                b'>' => "&gt;".to_string(),
+                0x20..=0x7e => (*byte as char).to_string(),
+                _ => ".".to_string(),
+            };
+            cell(text, *byte)
+        });
+        out.push_str(&format!(
+            "<tr><td class=\"offset\">{:08x}</td><td>{}</td><td>{}</td></tr>\n",
+            i,
+            hex.collect::<Vec<_>>().join(" "),
+            ascii.collect::<String>()
+        ));
+        i += 16;
+    }
+    out.push_str("</table>\n");
+    out
+}
 
-    let mut count = 0;
-    let mut line_start = false;
-    for (i, byte) in binary.iter().enumerate() {
-        if !line_start {
-            out.push_str(&format!("{}    ", generated_tabs));
-            line_start = true;
+/// Pushes the hex disassembly of the 16 bytes starting at `i` to `out`.
+fn hex_row(binary: &[u8], i: usize, charset: Charset, out: &mut String) {
+    out.push_str(&format!("{:08x}  ", i));
+    for j in 0..16 {
+        if i + j < binary.len() {
+            out.push_str(&format!("{:02x} ", binary[i + j]));
+        } else {
+            out.push_str("   ");
+        }
+        if j % 4 == 3 {
+            out.push(' ');
         }
+    }
+    text_column(binary, i, charset, out);
+}
 
-        out.push_str(&format!("0x{:02x}", byte));
+/// Pushes the binary disassembly of the 16 bytes starting at `i` to `out`.
+fn binary_row(binary: &[u8], i: usize, charset: Charset, out: &mut String) {
+    out.push_str(&format!("{:08x}  ", i));
+    for j in 0..16 {
+        if i + j < binary.len() {
+            out.push_str(&format!("{:08b} ", binary[i + j]));
+        } else {
+            out.push_str("         ");
+        }
+        if j % 4 == 3 {
+            out.push(' ');
+        }
+    }
+    text_column(binary, i, charset, out);
+}
 
-        if i < binary.len() - 1 {
-            out.push_str(", ");
+/// Pushes the text column of the 16 bytes starting at `i` to `out`.
+/// Multi-byte characters are shown on their first byte, the bytes that follow are marked
+/// with `CONTINUATION_MARKER`, and bytes that can't be shown are replaced by a dot.
+fn text_column(binary: &[u8], i: usize, charset: Charset, out: &mut String) {
+    out.push_str(" |");
+    let cells = decode_cells(binary, i, charset);
+    for (j, cell) in cells.iter().enumerate() {
+        if i + j < binary.len() {
+            out.push(*cell);
+        } else {
+            out.push(' ');
         }
+    }
+    out.push_str("|\n");
+}
 
-        count += 1;
+/// Decodes the characters shown for the 16 bytes starting at `i`.
+/// Decoding starts a few bytes earlier so characters started on the previous row are
+/// recognized, utf-8 and utf-16 both resynchronize on their own after invalid bytes.
+fn decode_cells(binary: &[u8], i: usize, charset: Charset) -> [char; 16] {
+    let mut cells = ['.'; 16];
+    let end = (i + 16).min(binary.len());
+    let mut set = |position: usize, c: char| {
+        if position >= i && position < end {
+            cells[position - i] = c;
+        }
+    };
+    let printable = |c: char| if c.is_control() { '.' } else { c };
 
-        if count % 8 == 0 && i < binary.len() - 1 {
-            out.push_str("\\\n");
-            line_start = false;
+    match charset {
+        Charset::Ascii => {
+            for (j, c) in binary[i..end].iter().enumerate() {
+                if (0x20..=0x7e).contains(c) {
+                    set(i + j, *c as char);
+                }
+            }
+        }
+        Charset::Ebcdic => {
+            for (j, c) in binary[i..end].iter().enumerate() {
+                set(i + j, EBCDIC[*c as usize]);
+            }
+        }
+        Charset::Utf8 => {
+            let mut position = i.saturating_sub(3);
+            while position < end {
+                let len = match binary[position] {
+                    0x00..=0x7f => 1,
+                    0xc2..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    0xf0..=0xf4 => 4,
+                    _ => 0,
+                };
+                let decoded = binary
+                    .get(position..position + len)
+                    .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                    .and_then(|text| text.chars().next());
+                match decoded {
+                    Some(c) => {
+                        set(position, printable(c));
+                        for continuation in position + 1..position + len {
+                            set(continuation, CONTINUATION_MARKER);
+                        }
+                        position += len;
+                    }
+                    None => {
+                        set(position, '.');
+                        position += 1;
+                    }
+                }
+            }
+        }
+        Charset::Utf16Le | Charset::Utf16Be => {
+            let unit = |position: usize| {
+                binary.get(position..position + 2).map(|bytes| {
+                    if charset == Charset::Utf16Le {
+                        u16::from_le_bytes([bytes[0], bytes[1]])
+                    } else {
+                        u16::from_be_bytes([bytes[0], bytes[1]])
+                    }
+                })
+            };
+            let mut position = i.saturating_sub(2) / 2 * 2;
+            while position < end {
+                let units = [unit(position), unit(position + 2)];
+                let (decoded, len) = match units {
+                    [Some(high @ 0xd800..=0xdbff), Some(low @ 0xdc00..=0xdfff)] => (
+                        char::decode_utf16([high, low]).next().and_then(|c| c.ok()),
+                        4,
+                    ),
+                    [Some(single), _] => {
+                        (char::decode_utf16([single]).next().and_then(|c| c.ok()), 2)
+                    }
+                    [None, _] => (None, 1),
+                };
+                match decoded {
+                    Some(c) => {
+                        set(position, printable(c));
+                        for continuation in position + 1..position + len {
+                            set(continuation, CONTINUATION_MARKER);
+                        }
+                    }
+                    None => {
+                        for invalid in position..position + len {
+                            set(invalid, '.');
+                        }
+                    }
+                }
+                position += len;
+            }
         }
     }
+    cells
+}
 
-    out.push_str("}\n");
-    out
+/// A field of a layout description, see `parse_layout`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub kind: String,
 }
 
-/// Converts an array of bytes to a Rust constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// const TEST_TXT: [u8; 4] = [0x00, 0x01, 0x02, 0x03];
-pub fn binary_to_rust_const(binary: &[u8], name: &str, tab_size: usize) -> String {
-    let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!(
-        "const {}: [u8; {}] = [\n{}",
-        name,
-        binary.len(),
-        generated_tabs
-    ));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
+/// Parses a layout description, made of one field per line given as `name offset size type`,
+/// where type is one of u8, u16, u32, u64, i8, i16, i32, i64, f32, f64 (little endian per default,
+/// with a `le` or `be` suffix to choose), str for text or bytes. Blank lines and `#` comments are
+/// ignored, offsets and sizes can be written in hexadecimal with a 0x prefix, for exemple:
+/// # ELF header
+/// magic    0x00 4 bytes
+/// class    0x04 1 u8
+/// entry    0x18 8 u64le
+pub fn parse_layout(layout: &str) -> Result<Vec<Field>, String> {
+    let number = |value: &str| match value.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => value.parse::<usize>(),
+    };
+
+    let mut fields = Vec::new();
+    for (line_number, line) in layout.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        let columns = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|column| !column.is_empty())
+            .collect::<Vec<&str>>();
+        if columns.is_empty() {
+            continue;
         }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
+        let error = |message: &str| format!("line {}: {}", line_number + 1, message);
+        if columns.len() != 4 {
+            return Err(error("expected `name offset size type`"));
+        }
+        let offset = number(columns[1]).map_err(|_| error("invalid offset"))?;
+        let size = number(columns[2]).map_err(|_| error("invalid size"))?;
+        let kind = columns[3].to_ascii_lowercase();
+        match field_width(&kind) {
+            Some(0) => (),
+            Some(width) if width == size => (),
+            Some(width) => {
+                return Err(error(&format!(
+                    "{} fields are {} byte(s) long",
+                    kind, width
+                )));
+            }
+            None => return Err(error(&format!("unknown type {}", kind))),
         }
+        fields.push(Field {
+            name: columns[0].to_string(),
+            offset,
+            size,
+            kind,
+        });
     }
-    out.push_str("\n];\n");
-    out
+    fields.sort_by_key(|field| field.offset);
+    Ok(fields)
 }
 
-/// Converts an array of bytes to a python constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// TEST_TXT = bytes([0x00, 0x01, 0x02, 0x03])
-pub fn binary_to_python_const(binary: &[u8], name: &str, tab_size: usize) -> String {
-    let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!("{} = bytes([\n{}", name, generated_tabs));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
-        }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
+/// The size of a layout type, 0 for the types that can have any size.
+fn field_width(kind: &str) -> Option<usize> {
+    let base = kind.trim_end_matches("le").trim_end_matches("be");
+    match base {
+        "u8" | "i8" => Some(1),
+        "u16" | "i16" => Some(2),
+        "u32" | "i32" | "f32" => Some(4),
+        "u64" | "i64" | "f64" => Some(8),
+        "str" | "bytes" => Some(0),
+        _ => None,
+    }
+}
+
+/// Decodes the value of a layout field, as shown in the annotated dumps.
+fn decode_field(binary: &[u8], field: &Field) -> String {
+    let end = field.offset.checked_add(field.size);
+    let bytes = match end.and_then(|end| binary.get(field.offset..end)) {
+        Some(bytes) => bytes,
+        None => return "<out of range>".to_string(),
+    };
+    let mut ordered = [0u8; 8];
+    ordered[..bytes.len().min(8)].copy_from_slice(&bytes[..bytes.len().min(8)]);
+    if field.kind.ends_with("be") {
+        ordered[..bytes.len().min(8)].reverse();
+    }
+    let unsigned = u64::from_le_bytes(ordered);
+    let signed = |bits: u32| ((unsigned << (64 - bits)) as i64) >> (64 - bits);
+
+    match field.kind.trim_end_matches("le").trim_end_matches("be") {
+        "u8" | "u16" | "u32" | "u64" => format!("{} (0x{:x})", unsigned, unsigned),
+        "i8" | "i16" | "i32" | "i64" => format!("{}", signed(field.size as u32 * 8)),
+        "f32" => format!("{}", f32::from_bits(unsigned as u32)),
+        "f64" => format!("{}", f64::from_bits(unsigned)),
+        "str" => format!(
+            "{:?}",
+            String::from_utf8_lossy(bytes).trim_end_matches('\0')
+        ),
+        _ => {
+            let mut out = bytes
+                .iter()
+                .take(16)
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<String>>()
+                .join(" ");
+            if bytes.len() > 16 {
+                out.push_str(" ...");
+            }
+            out
         }
     }
-    out.push_str("\n])\n");
-    out
 }
 
-/// Converts an array of bytes to a C# constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// public static readonly byte[] TEST_TXT = new byte[] {
-///    0x00, 0x01, 0x02, 0x03
-/// };
-pub fn binary_to_csharp_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+/// Converts an array of bytes to hex disassembly annotated with the fields of a layout,
+/// starting at the offset `start`.
+/// Every field gets a line below the row it starts on, with a caret under its first byte
+/// followed by its name, type, size and decoded value, for exemple:
+/// 00000000  7f 45 4c 46  02 01 01 00  00 00 00 00  00 00 00 00   |.ELF............|
+///           ^ magic: bytes[4] = 7f 45 4c 46
+///                       ^ class: u8[1] = 2 (0x2)
+pub fn binary_to_layout_dump(
+    binary: &[u8],
+    start: usize,
+    fields: &[Field],
+    charset: Charset,
+) -> String {
     let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!(
-        "public static readonly byte[] {} = new byte[] {{\n{}",
-        name, generated_tabs
-    ));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
-        }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
+    let mut i = start;
+    while i < binary.len() {
+        hex_row(binary, i, charset, &mut out);
+        for field in fields
+            .iter()
+            .filter(|f| f.offset >= i && f.offset < (i + 16).min(binary.len()))
+        {
+            let column = 10 + (field.offset - i) * 3 + (field.offset - i) / 4;
+            out.push_str(&format!(
+                "{}^ {}: {}[{}] = {}\n",
+                " ".repeat(column),
+                field.name,
+                field.kind,
+                field.size,
+                decode_field(binary, field)
+            ));
         }
+        i += 16;
     }
-    out.push_str("\n};\n");
     out
 }
 
-/// Converts an array of bytes to a Javascript constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// const TEST_TXT = new Uint8Array([
-///    0x00, 0x01, 0x02, 0x03
-/// ]);
-pub fn binary_to_javascript_const(binary: &[u8], name: &str, tab_size: usize) -> String {
-    let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!(
-        "const {} = new Uint8Array([\n{}",
-        name, generated_tabs
-    ));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
+/// Parses a search pattern into (value, mask) pairs, a byte matches when `byte & mask == value`.
+/// In hex mode the pattern is made of hex bytes, optionally separated by spaces or commas and
+/// prefixed by 0x, where `?` is a wildcard nibble, for exemple "de ad ?? e?".
+/// In text mode every character is matched as is, except `?` which matches any byte.
+pub fn parse_pattern(pattern: &str, text: bool) -> Result<Vec<(u8, u8)>, String> {
+    if text {
+        return Ok(pattern
+            .bytes()
+            .map(|c| if c == b'?' { (0, 0) } else { (c, 0xff) })
+            .collect());
+    }
+
+    let mut out = Vec::new();
+    for token in pattern.split(|c: char| c.is_whitespace() || c == ',') {
+        let token = token.trim_start_matches("0x").trim_start_matches("0X");
+        if token.len() % 2 != 0 {
+            return Err(format!("\"{}\" is not made of whole bytes", token));
         }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
+        let nibbles = token.chars().collect::<Vec<char>>();
+        for pair in nibbles.chunks(2) {
+            let mut value = 0;
+            let mut mask = 0;
+            for c in pair {
+                value <<= 4;
+                mask <<= 4;
+                if *c != '?' {
+                    match c.to_digit(16) {
+                        Some(digit) => value |= digit as u8,
+                        None => return Err(format!("invalid hex digit '{}'", c)),
+                    }
+                    mask |= 0xf;
+                }
+            }
+            out.push((value, mask));
         }
     }
-    out.push_str("\n]);\n");
-    out
+    if out.is_empty() {
+        return Err("empty pattern".to_string());
+    }
+    Ok(out)
 }
 
-/// Converts an array of bytes to a Go constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// var TEST_TXT = []byte{
-///   0x00, 0x01, 0x02, 0x03
-/// }
-pub fn binary_to_go_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+/// Returns the offsets of every (possibly overlapping) occurence of a parsed pattern.
+pub fn find_pattern(binary: &[u8], pattern: &[(u8, u8)]) -> Vec<usize> {
+    if pattern.is_empty() || pattern.len() > binary.len() {
+        return Vec::new();
+    }
+    (0..=binary.len() - pattern.len())
+        .filter(|&i| {
+            pattern
+                .iter()
+                .enumerate()
+                .all(|(j, (value, mask))| binary[i + j] & mask == *value)
+        })
+        .collect()
+}
+
+/// Lists the offsets of the matches of a parsed pattern found from the offset `start`,
+/// each followed by the hex disassembly of the match and `context` bytes around it.
+/// For exemple, searching "02 ??" in &[0x00, 0x01, 0x02, 0x03] from 0 returns:
+/// match at 0x00000002 (2)
+/// 00000000  00 01 02 03                                       |....|
+///
+/// 1 match(es)
+pub fn binary_to_find(
+    binary: &[u8],
+    start: usize,
+    pattern: &[(u8, u8)],
+    context: usize,
+    charset: Charset,
+) -> String {
     let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!("var {} = []byte{{\n{}", name, generated_tabs));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
-        }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
+    let matches = find_pattern(&binary[start..], pattern);
+    for offset in matches.iter().map(|offset| offset + start) {
+        out.push_str(&format!("match at 0x{:08x} ({})\n", offset, offset));
+        let first_row = offset.saturating_sub(context) / 16 * 16;
+        let end = (offset + pattern.len() + context).min(binary.len());
+        let mut i = first_row;
+        while i < end {
+            hex_row(&binary[..end], i, charset, &mut out);
+            i += 16;
         }
+        out.push('\n');
     }
-    out.push_str("\n}\n");
+    out.push_str(&format!("{} match(es)\n", matches.len()));
     out
 }
 
-/// Converts an array of bytes to a Java constant.
-/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
-/// public static final byte[] TEST_TXT = new byte[] {
-///   0x00, 0x01, 0x02, 0x03
+/// The number of bytes per line in the generated constants.
+pub const BYTES_PER_LINE: usize = 16;
+
+/// Formats every byte as a 0x prefixed lowercase hex literal, the most common byte syntax.
+pub fn hex_literals(binary: &[u8]) -> Vec<String> {
+    binary
+        .iter()
+        .map(|byte| format!("0x{:02x}", byte))
+        .collect()
+}
+
+/// Lays out elements in lines of `per_line` elements separated by `separator`, every line
+/// indented by `indent`. All lines but the last end with the separator (without its trailing
+/// spaces) and are joined by `line_break`, so the last element never gets a separator.
+/// For exemple, with elements = ["0x00", "0x01", "0x02"], separator = ", ", indent = "    ",
+/// per_line = 2 and line_break = "\n", the function returns:
+///     0x00, 0x01,
+///     0x02
+pub fn wrap_elements(
+    elements: &[String],
+    separator: &str,
+    indent: &str,
+    per_line: usize,
+    line_break: &str,
+) -> String {
+    elements
+        .chunks(per_line.max(1))
+        .map(|line| format!("{}{}", indent, line.join(separator)))
+        .collect::<Vec<String>>()
+        .join(&format!("{}{}", separator.trim_end(), line_break))
+}
+
+/// Converts an array of bytes to a C constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// const unsigned char TEST_TXT[] = {
+///     0x00, 0x01, 0x02, 0x03
 /// };
-pub fn binary_to_java_const(binary: &[u8], name: &str, tab_size: usize) -> String {
-    let mut out = String::new();
-    let generated_tabs = " ".repeat(tab_size);
-    out.push_str(&format!(
-        "public static final byte[] {} = new byte[] {{\n{}",
-        name, generated_tabs
-    ));
-    for (i, byte) in binary.iter().enumerate() {
-        out.push_str(&format!("0x{:02x}", byte));
-        if i < binary.len() - 1 {
-            out.push_str(", ");
-        }
-        if i % 16 == 15 {
-            out.push_str(format!("{}\n", generated_tabs).as_str());
-        }
+/// C arrays can't be empty, so an empty binary gives a single zero byte.
+pub fn binary_to_c_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!(
+            "/* The input is empty, padded to one byte as C arrays can't be empty */\n\
+             const unsigned char {}[1] = {{ 0 }};\n",
+            name
+        );
+    }
+    format!(
+        "const unsigned char {}[] = {{\n{}\n}};\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a C constant aligned to `alignment` bytes, with `alignas` in
+/// C++ and `_Alignas` in C.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and alignment = 4,
+/// the function returns:
+/// #ifdef __cplusplus
+/// alignas(4)
+/// #else
+/// _Alignas(4)
+/// #endif
+/// const unsigned char TEST_TXT[] = {
+///     0x00, 0x01, 0x02, 0x03
+/// };
+pub fn binary_to_aligned_c_const(
+    binary: &[u8],
+    name: &str,
+    tab_size: usize,
+    alignment: usize,
+) -> String {
+    format!(
+        "#ifdef __cplusplus\nalignas({})\n#else\n_Alignas({})\n#endif\n{}",
+        alignment,
+        alignment,
+        binary_to_c_const(binary, name, tab_size)
+    )
+}
+
+/// Converts an array of bytes to a 6502 assembly label followed by its bytes and its size, for
+/// ca65, or for ACME with `acme`, whose labels have no colon and whose directives start with `!`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT:
+///     .byte $00, $01, $02, $03
+/// TEST_TXT_SIZE = 4
+pub fn binary_to_6502_const(binary: &[u8], name: &str, tab_size: usize, acme: bool) -> String {
+    let (label, directive) = if acme {
+        ("", "!byte ")
+    } else {
+        (":", ".byte ")
+    };
+    let bytes = binary
+        .iter()
+        .map(|byte| format!("${:02x}", byte))
+        .collect::<Vec<String>>();
+    assembly_const(&bytes, name, label, directive, tab_size)
+        + &format!("{}_SIZE = {}\n", name, binary.len())
+}
+
+/// Converts an array of bytes to a Z80 assembly label followed by its bytes and its size, for
+/// z80asm. The hex literals start with a 0 so they can't be read as labels.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// TEST_TXT:
+///     db 000h, 001h, 002h, 0ffh
+/// TEST_TXT_SIZE equ 4
+pub fn binary_to_z80_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let bytes = binary
+        .iter()
+        .map(|byte| format!("{:03x}h", byte))
+        .collect::<Vec<String>>();
+    assembly_const(&bytes, name, ":", "db ", tab_size)
+        + &format!("{}_SIZE equ {}\n", name, binary.len())
+}
+
+/// Converts an array of bytes to an x86 assembly label followed by its bytes, for NASM and YASM,
+/// with a symbol for its length computed by the assembler.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT:
+///     db 0x00, 0x01, 0x02, 0x03
+/// TEST_TXT_len equ $ - TEST_TXT
+pub fn binary_to_nasm_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    assembly_const(&hex_literals(binary), name, ":", "db ", tab_size)
+        + &format!("{}_len equ $ - {}\n", name, name)
+}
+
+/// Converts an array of bytes to a global label followed by its bytes, for the GNU assembler,
+/// with a global symbol for its size like the ones of `objcopy`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+///     .global TEST_TXT
+///     .global TEST_TXT_size
+/// TEST_TXT:
+///     .byte 0x00, 0x01, 0x02, 0x03
+///     .set TEST_TXT_size, . - TEST_TXT
+pub fn binary_to_gas_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "{indent}.global {name}\n{indent}.global {name}_size\n{}{indent}.set {name}_size, . - {name}\n",
+        assembly_const(&hex_literals(binary), name, ":", ".byte ", tab_size)
+    )
+}
+
+/// Places a constant generated by binary_to_gas_const in a section, allocated but neither
+/// writable nor executable.
+/// For exemple, with section = ".assets", the constant starts with:
+///     .section .assets, "a"
+pub fn gas_const_in_section(constant: &str, section: &str, tab_size: usize) -> String {
+    format!(
+        "{}.section {}, \"a\"\n{}",
+        " ".repeat(tab_size),
+        section,
+        constant
+    )
+}
+
+/// The label and the data lines shared by the assembly outputs, each line starting with the
+/// directive as assemblers have no line continuation.
+fn assembly_const(
+    literals: &[String],
+    name: &str,
+    label: &str,
+    directive: &str,
+    tab_size: usize,
+) -> String {
+    let mut out = format!("{}{}\n", name, label);
+    for line in literals.chunks(BYTES_PER_LINE) {
+        out.push_str(&format!(
+            "{}{}{}\n",
+            " ".repeat(tab_size),
+            directive,
+            line.join(", ")
+        ));
     }
-    out.push_str("\n};\n");
     out
 }
+
+/// Places a C/C++ constant generated by binary_to_c_const in a linker section, with the section
+/// attribute of GCC and Clang, marked as used so it is kept even when the code doesn't use it.
+/// For exemple, with name = "TEST_TXT" and section = ".assets", the constant starts with:
+/// const unsigned char TEST_TXT[] __attribute__((section(".assets"), used)) = {
+pub fn c_const_in_section(constant: &str, name: &str, section: &str) -> String {
+    let Some(brackets) = constant.find(&format!("{}[", name)) else {
+        return constant.to_string();
+    };
+    let end = brackets + constant[brackets..].find(']').map_or(0, |i| i + 1);
+    format!(
+        "{} __attribute__((section(\"{}\"), used)){}",
+        &constant[..end],
+        section,
+        &constant[end..]
+    )
+}
+
+/// Generates a GNU ld linker script fragment placing a section at an address, in a memory
+/// region of its own named after it.
+/// For exemple, with section = ".assets", address = 0x08040000 and length = 4096, the function
+/// returns:
+/// MEMORY
+/// {
+///     ASSETS (r) : ORIGIN = 0x08040000, LENGTH = 4096
+/// }
+///
+/// SECTIONS
+/// {
+///     .assets :
+///     {
+///         KEEP(*(.assets))
+///     } > ASSETS
+/// }
+pub fn linker_script(section: &str, address: usize, length: usize, tab_size: usize) -> String {
+    let region = section
+        .trim_start_matches('.')
+        .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        .to_ascii_uppercase();
+    let indent = " ".repeat(tab_size);
+    format!(
+        "MEMORY\n{{\n{indent}{region} (r) : ORIGIN = {:#010x}, LENGTH = {length}\n}}\n\n\
+         SECTIONS\n{{\n{indent}{section} :\n{indent}{{\n{indent}{indent}KEEP(*({section}))\n\
+         {indent}}} > {region}\n}}\n",
+        address
+    )
+}
+
+/// Declares a file embedded by the build of ESP-IDF, with the CMake line embedding it and the
+/// symbols the build gives its start and end, which are named after the file name.
+/// For exemple, with file = "main/logo.png", start = "TEST_TXT_START", end = "TEST_TXT_END" and
+/// size = "TEST_TXT_SIZE", the function returns:
+/// // In the CMakeLists.txt of the component:
+/// // target_add_binary_data(${COMPONENT_LIB} "main/logo.png" BINARY)
+/// #include <stddef.h>
+/// #include <stdint.h>
+///
+/// extern const uint8_t TEST_TXT_START[] asm("_binary_logo_png_start");
+/// extern const uint8_t TEST_TXT_END[] asm("_binary_logo_png_end");
+/// #define TEST_TXT_SIZE ((size_t)(TEST_TXT_END - TEST_TXT_START))
+pub fn espidf_binary_data(file: &str, start: &str, end: &str, size: &str) -> String {
+    let file_name = file.rsplit('/').next().unwrap_or(file);
+    let symbol = file_name.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    format!(
+        "// In the CMakeLists.txt of the component:\n\
+         // target_add_binary_data(${{COMPONENT_LIB}} \"{file}\" BINARY)\n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         \n\
+         extern const uint8_t {start}[] asm(\"_binary_{symbol}_start\");\n\
+         extern const uint8_t {end}[] asm(\"_binary_{symbol}_end\");\n\
+         #define {size} ((size_t)({end} - {start}))\n"
+    )
+}
+
+/// Converts an array of bytes to a C/C++ #define.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// #define TEST_TXT_SIZE 4
+/// #define TEST_TXT { \
+///     0x00, 0x01, 0x02, 0x03 \
+/// }
+/// With 8 bytes per line, so longer arrays span multiple lines:
+/// #define TEST_TXT { \
+///     0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, \
+///     0x08, 0x09 \
+/// }
+pub fn binary_to_c_define(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("#define {}_SIZE 0\n#define {} {{}}\n", name, name);
+    }
+    format!(
+        "#define {}_SIZE {}\n#define {} {{ \\\n{} \\\n}}\n",
+        name,
+        binary.len(),
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            8,
+            " \\\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a C string literal, with every byte escaped and the lines
+/// concatenated by the compiler, and its length without the terminating null byte.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// #define TEST_TXT_LEN 4
+/// const unsigned char TEST_TXT[] =
+///     "\x00\x01\x02\x03";
+pub fn binary_to_c_string(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!(
+            "#define {}_LEN 0\nconst unsigned char {}[] = \"\";\n",
+            name, name
+        );
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let escapes = line.iter().map(|byte| format!("\\x{:02x}", byte));
+            format!(
+                "{}\"{}\"",
+                " ".repeat(tab_size),
+                escapes.collect::<String>()
+            )
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "#define {}_LEN {}\nconst unsigned char {}[] =\n{};\n",
+        name,
+        binary.len(),
+        name,
+        lines.join("\n")
+    )
+}
+
+/// Converts an array of bytes to a Rust constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// const TEST_TXT: [u8; 4] = [
+///     0x00, 0x01, 0x02, 0x03
+/// ];
+pub fn binary_to_rust_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("const {}: [u8; 0] = [];\n", name);
+    }
+    format!(
+        "const {}: [u8; {}] = [\n{}\n];\n",
+        name,
+        binary.len(),
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Escapes a byte for a Rust byte string, keeping the printable ASCII characters as they are.
+fn escape_rust_byte(byte: u8) -> String {
+    match byte {
+        b'"' => "\\\"".to_string(),
+        b'\\' => "\\\\".to_string(),
+        b'\t' => "\\t".to_string(),
+        b'\n' => "\\n".to_string(),
+        b'\r' => "\\r".to_string(),
+        0x20..=0x7e => (byte as char).to_string(),
+        _ => format!("\\x{:02x}", byte),
+    }
+}
+
+/// Converts an array of bytes to a Rust byte string constant, which compiles faster than an
+/// array for large inputs. The lines are continued with a backslash, which also skips the
+/// indentation of the next line, so a space starting a line is escaped.
+/// For exemple, with binary = b"a \"b\"\n" and name = "TEST_TXT", the function returns:
+/// const TEST_TXT: &[u8; 6] = b"\
+///     a \"b\"\n";
+pub fn binary_to_rust_byte_string(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("const {}: &[u8; 0] = b\"\";\n", name);
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let escaped = line.iter().enumerate().map(|(i, byte)| match (i, byte) {
+                (0, b' ') => "\\x20".to_string(),
+                _ => escape_rust_byte(*byte),
+            });
+            format!("{}{}", " ".repeat(tab_size), escaped.collect::<String>())
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "const {}: &[u8; {}] = b\"\\\n{}\";\n",
+        name,
+        binary.len(),
+        lines.join("\\\n")
+    )
+}
+
+/// Converts an array of bytes to a Rust constant aligned to `alignment` bytes. Rust can only
+/// align types, so the array is wrapped in an aligned struct and the constant is a reference
+/// to it.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and alignment = 4,
+/// the function returns:
+/// #[repr(C, align(4))]
+/// struct TestTxtAligned<T>(T);
+///
+/// const TEST_TXT: &[u8; 4] = &TestTxtAligned([
+///     0x00, 0x01, 0x02, 0x03
+/// ]).0;
+pub fn binary_to_aligned_rust_const(
+    binary: &[u8],
+    name: &str,
+    tab_size: usize,
+    alignment: usize,
+) -> String {
+    let wrapper = format!("{}Aligned", NameCase::Pascal.apply(name));
+    let array = if binary.is_empty() {
+        "[]".to_string()
+    } else {
+        format!(
+            "[\n{}\n]",
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        )
+    };
+    format!(
+        "#[repr(C, align({}))]\nstruct {}<T>(T);\n\nconst {}: &[u8; {}] = &{}({}).0;\n",
+        alignment,
+        wrapper,
+        name,
+        binary.len(),
+        wrapper,
+        array
+    )
+}
+
+/// Declares a Rust static including a file with `include_bytes!` instead of an array of its
+/// bytes, which compiles a lot faster for big files. `file` is relative to the source file.
+/// With an alignment, the included array is wrapped in an aligned struct like in
+/// binary_to_aligned_rust_const.
+/// For exemple, with file = "../assets/logo.png" and name = "TEST_TXT", the function returns:
+/// pub static TEST_TXT: &[u8] = include_bytes!("../assets/logo.png");
+pub fn binary_to_rust_include(file: &Path, name: &str, alignment: Option<usize>) -> String {
+    let file = file
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let include = format!(
+        "include_bytes!(\"{}\")",
+        escape_rust(&file, Escape::Minimal)
+    );
+    match alignment {
+        Some(alignment) => {
+            let wrapper = format!("{}Aligned", NameCase::Pascal.apply(name));
+            format!(
+                "#[repr(C, align({}))]\nstruct {}<T>(T);\n\npub static {}: &[u8] = &{}(*{}).0;\n",
+                alignment, wrapper, name, wrapper, include
+            )
+        }
+        None => format!("pub static {}: &[u8] = {};\n", name, include),
+    }
+}
+
+/// Converts an array of bytes to a python constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT = bytes([
+///     0x00, 0x01, 0x02, 0x03
+/// ])
+pub fn binary_to_python_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("{} = bytes()\n", name);
+    }
+    format!(
+        "{} = bytes([\n{}\n])\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a C# constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// public static readonly byte[] TEST_TXT = new byte[] {
+///     0x00, 0x01, 0x02, 0x03
+/// };
+pub fn binary_to_csharp_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("public static readonly byte[] {} = new byte[0];\n", name);
+    }
+    format!(
+        "public static readonly byte[] {} = new byte[] {{\n{}\n}};\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Javascript constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// const TEST_TXT = new Uint8Array([
+///     0x00, 0x01, 0x02, 0x03
+/// ]);
+pub fn binary_to_javascript_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("const {} = new Uint8Array(0);\n", name);
+    }
+    format!(
+        "const {} = new Uint8Array([\n{}\n]);\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Go constant.
+/// Go needs a separator after the last element of a multi-line literal, so every line ends
+/// with a comma. For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT",
+/// the function returns:
+/// var TEST_TXT = []byte{
+///     0x00, 0x01, 0x02, 0x03,
+/// }
+pub fn binary_to_go_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("var {} = []byte{{}}\n", name);
+    }
+    format!(
+        "var {} = []byte{{\n{},\n}}\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Java constant.
+/// Java bytes are signed, so the bytes above 0x7f are cast.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// public static final byte[] TEST_TXT = new byte[] {
+///     0x00, 0x01, 0x02, (byte) 0xff
+/// };
+pub fn binary_to_java_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("public static final byte[] {} = new byte[0];\n", name);
+    }
+    let elements = binary
+        .iter()
+        .map(|byte| {
+            if *byte > 0x7f {
+                format!("(byte) 0x{:02x}", byte)
+            } else {
+                format!("0x{:02x}", byte)
+            }
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "public static final byte[] {} = new byte[] {{\n{}\n}};\n",
+        name,
+        wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to a Zig constant.
+/// Like Go, every line of a multi-line literal ends with a comma, as `zig fmt` writes them.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// pub const TEST_TXT = [_]u8{
+///     0x00, 0x01, 0x02, 0x03,
+/// };
+pub fn binary_to_zig_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("pub const {} = [_]u8{{}};\n", name);
+    }
+    format!(
+        "pub const {} = [_]u8{{\n{},\n}};\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Swift constant, as an array of UInt8 or with `data` as
+/// Foundation's Data.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and data = false,
+/// the function returns:
+/// let TEST_TXT: [UInt8] = [
+///     0x00, 0x01, 0x02, 0x03
+/// ]
+pub fn binary_to_swift_const(binary: &[u8], name: &str, tab_size: usize, data: bool) -> String {
+    let array = if binary.is_empty() {
+        "[]".to_string()
+    } else {
+        format!(
+            "[\n{}\n]",
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        )
+    };
+    match data {
+        true if binary.is_empty() => format!("import Foundation\n\nlet {} = Data()\n", name),
+        true => format!("import Foundation\n\nlet {} = Data({})\n", name, array),
+        false => format!("let {}: [UInt8] = {}\n", name, array),
+    }
+}
+
+/// Converts an array of bytes to a Kotlin constant.
+/// Kotlin bytes are signed and its literals are Ints, so the bytes above 0x7f are converted.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// val TEST_TXT = byteArrayOf(
+///     0x00, 0x01, 0x02, 0xff.toByte()
+/// )
+pub fn binary_to_kotlin_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("val {} = byteArrayOf()\n", name);
+    }
+    let elements = binary
+        .iter()
+        .map(|byte| {
+            if *byte > 0x7f {
+                format!("0x{:02x}.toByte()", byte)
+            } else {
+                format!("0x{:02x}", byte)
+            }
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "val {} = byteArrayOf(\n{}\n)\n",
+        name,
+        wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to a Nim constant.
+/// The first literal is typed with `'u8`, the type of the others follows from it.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// const TEST_TXT: array[4, uint8] = [
+///     0x00'u8, 0x01, 0x02, 0x03
+/// ]
+pub fn binary_to_nim_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("const {}: array[0, uint8] = []\n", name);
+    }
+    let mut elements = hex_literals(binary);
+    elements[0].push_str("'u8");
+    format!(
+        "const {}: array[{}, uint8] = [\n{}\n]\n",
+        name,
+        binary.len(),
+        wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to a D constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// immutable ubyte[] TEST_TXT = [
+///     0x00, 0x01, 0x02, 0x03
+/// ];
+pub fn binary_to_d_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("immutable ubyte[] {} = [];\n", name);
+    }
+    format!(
+        "immutable ubyte[] {} = [\n{}\n];\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Pascal constant, with its `$` hex literals.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// const TEST_TXT: array[0..3] of Byte = (
+///     $00, $01, $02, $03
+/// );
+/// Pascal arrays can't be empty, so an empty binary gives a single zero byte.
+pub fn binary_to_pascal_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!(
+            "{{ The input is empty, padded to one byte as Pascal arrays can't be empty }}\n\
+             const {}: array[0..0] of Byte = (0);\n",
+            name
+        );
+    }
+    let elements = binary
+        .iter()
+        .map(|byte| format!("${:02x}", byte))
+        .collect::<Vec<String>>();
+    format!(
+        "const {}: array[0..{}] of Byte = (\n{}\n);\n",
+        name,
+        binary.len() - 1,
+        wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to an Ada constant of Ada.Streams, with its `16#00#` literals.
+/// The bounds are given, as a positional aggregate needs at least two elements and would
+/// otherwise start at the first Stream_Element_Offset.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT : constant Stream_Element_Array (0 .. 3) := (
+///     16#00#, 16#01#, 16#02#, 16#03#
+/// );
+pub fn binary_to_ada_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let elements = binary
+        .iter()
+        .map(|byte| format!("16#{:02x}#", byte))
+        .collect::<Vec<String>>();
+    let aggregate = match elements.len() {
+        0 => "(others => 0)".to_string(),
+        1 => format!("(0 => {})", elements[0]),
+        _ => format!(
+            "(\n{}\n)",
+            wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+        ),
+    };
+    format!(
+        "{} : constant Stream_Element_Array (0 .. {}) := {};\n",
+        name,
+        binary.len() as i64 - 1,
+        aggregate
+    )
+}
+
+/// Converts an array of bytes to a Lua constant, as a string or with `table` as a table of
+/// numbers. A Lua call takes at most about 250 arguments, so the string is built with a call to
+/// string.char per line.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and table = false,
+/// the function returns:
+/// local TEST_TXT = table.concat({
+///     string.char(0x00, 0x01, 0x02, 0x03),
+/// })
+pub fn binary_to_lua_const(binary: &[u8], name: &str, tab_size: usize, table: bool) -> String {
+    let indent = " ".repeat(tab_size);
+    match table {
+        _ if binary.is_empty() => {
+            format!("local {} = {}\n", name, if table { "{}" } else { "\"\"" })
+        }
+        true => format!(
+            "local {} = {{\n{}\n}}\n",
+            name,
+            wrap_elements(&hex_literals(binary), ", ", &indent, BYTES_PER_LINE, "\n")
+        ),
+        false => {
+            let mut out = format!("local {} = table.concat({{\n", name);
+            for line in hex_literals(binary).chunks(BYTES_PER_LINE) {
+                out.push_str(&format!("{}string.char({}),\n", indent, line.join(", ")));
+            }
+            out + "})\n"
+        }
+    }
+}
+
+/// Converts an array of bytes to a PHP constant, as a string with every byte escaped, so no `$`
+/// is interpolated, or with `array` as an array of integers. The string is split in a line per
+/// 16 bytes.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and array = false,
+/// the function returns:
+/// const TEST_TXT = "\x00\x01\x02\x03";
+pub fn binary_to_php_const(binary: &[u8], name: &str, tab_size: usize, array: bool) -> String {
+    if array {
+        if binary.is_empty() {
+            return format!("const {} = [];\n", name);
+        }
+        return format!(
+            "const {} = [\n{}\n];\n",
+            name,
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        );
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let escaped = line.iter().map(|byte| format!("\\x{:02x}", byte));
+            format!("\"{}\"", escaped.collect::<String>())
+        })
+        .collect::<Vec<String>>();
+    match lines.is_empty() {
+        true => format!("const {} = \"\";\n", name),
+        false => format!(
+            "const {} = {};\n",
+            name,
+            lines.join(&format!("\n{}. ", " ".repeat(tab_size)))
+        ),
+    }
+}
+
+/// Converts an array of bytes to a Ruby constant, packed from an array of integers or with
+/// `string` as a binary string literal with every byte escaped.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and string = false,
+/// the function returns:
+/// TEST_TXT = [
+///     0x00, 0x01, 0x02, 0x03
+/// ].pack('C*').freeze
+pub fn binary_to_ruby_const(binary: &[u8], name: &str, tab_size: usize, string: bool) -> String {
+    let indent = " ".repeat(tab_size);
+    match string {
+        _ if binary.is_empty() && string => format!("{} = \"\".b.freeze\n", name),
+        _ if binary.is_empty() => format!("{} = [].pack('C*').freeze\n", name),
+        true => {
+            let lines = binary
+                .chunks(BYTES_PER_LINE)
+                .map(|line| {
+                    let escaped = line.iter().map(|byte| format!("\\x{:02x}", byte));
+                    format!("{}\"{}\"", indent, escaped.collect::<String>())
+                })
+                .collect::<Vec<String>>();
+            format!("{} = (\n{}\n).b.freeze\n", name, lines.join(" \\\n"))
+        }
+        false => format!(
+            "{} = [\n{}\n].pack('C*').freeze\n",
+            name,
+            wrap_elements(&hex_literals(binary), ", ", &indent, BYTES_PER_LINE, "\n")
+        ),
+    }
+}
+
+/// Converts an array of bytes to a Perl variable, packed from a list of integers.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// my $TEST_TXT = pack('C*',
+///     0x00, 0x01, 0x02, 0x03
+/// );
+pub fn binary_to_perl_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("my ${} = '';\n", name);
+    }
+    format!(
+        "my ${} = pack('C*',\n{}\n);\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Haskell ByteString, packed from a list of integers, with the
+/// import it needs.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "testTxt", the function returns:
+/// import qualified Data.ByteString as BS
+///
+/// testTxt :: BS.ByteString
+/// testTxt = BS.pack [
+///     0x00, 0x01, 0x02, 0x03
+///     ]
+pub fn binary_to_haskell_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    let list = match binary.is_empty() {
+        true => "[]".to_string(),
+        // The closing bracket is indented, or it would start a new declaration.
+        false => format!(
+            "[\n{}\n{}]",
+            wrap_elements(&hex_literals(binary), ", ", &indent, BYTES_PER_LINE, "\n"),
+            indent
+        ),
+    };
+    format!(
+        "import qualified Data.ByteString as BS\n\n{0} :: BS.ByteString\n{0} = BS.pack {1}\n",
+        name, list
+    )
+}
+
+/// Converts an array of bytes to an OCaml value, made from a string literal with every byte
+/// escaped. The lines of the literal are joined by the escaped line breaks of OCaml, which also
+/// skip the indentation of the next line.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
+/// let test_txt = Bytes.of_string "\
+///     \x00\x01\x02\x03"
+pub fn binary_to_ocaml_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("let {} = Bytes.of_string \"\"\n", name);
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let escaped = line.iter().map(|byte| format!("\\x{:02x}", byte));
+            format!("{}{}", " ".repeat(tab_size), escaped.collect::<String>())
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "let {} = Bytes.of_string \"\\\n{}\"\n",
+        name,
+        lines.join("\\\n")
+    )
+}
+
+/// Converts an array of bytes to an Elixir module attribute holding a binary, to be placed in a
+/// module.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "test_txt", the function returns:
+/// @test_txt <<
+///     0x00, 0x01, 0x02, 0x03
+/// >>
+pub fn binary_to_elixir_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("@{} <<>>\n", name);
+    }
+    format!(
+        "@{} <<\n{}\n>>\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to an Erlang macro holding a binary.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// -define(TEST_TXT, <<
+///     16#00, 16#01, 16#02, 16#03
+/// >>).
+pub fn binary_to_erlang_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("-define({}, <<>>).\n", name);
+    }
+    let literals = binary
+        .iter()
+        .map(|byte| format!("16#{:02x}", byte))
+        .collect::<Vec<String>>();
+    format!(
+        "-define({}, <<\n{}\n>>).\n",
+        name,
+        wrap_elements(&literals, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to a Scala constant.
+/// Scala bytes are signed and its literals are Ints, so every byte is converted with `toByte`,
+/// which also gives its type to the array.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// val TEST_TXT: Array[Byte] = Array(
+///     0x00.toByte, 0x01.toByte, 0x02.toByte, 0xff.toByte
+/// )
+pub fn binary_to_scala_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("val {}: Array[Byte] = Array.emptyByteArray\n", name);
+    }
+    let elements = binary
+        .iter()
+        .map(|byte| format!("0x{:02x}.toByte", byte))
+        .collect::<Vec<String>>();
+    format!(
+        "val {}: Array[Byte] = Array(\n{}\n)\n",
+        name,
+        wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to a Dart Uint8List, with the import it needs.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "testTxt", the function returns:
+/// import 'dart:typed_data';
+///
+/// final Uint8List testTxt = Uint8List.fromList([
+///     0x00, 0x01, 0x02, 0x03
+/// ]);
+pub fn binary_to_dart_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let list = match binary.is_empty() {
+        true => "[]".to_string(),
+        false => format!(
+            "[\n{}\n]",
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        ),
+    };
+    format!(
+        "import 'dart:typed_data';\n\nfinal Uint8List {} = Uint8List.fromList({});\n",
+        name, list
+    )
+}
+
+/// Converts an array of bytes to a Julia constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// const TEST_TXT = UInt8[
+///     0x00, 0x01, 0x02, 0x03
+/// ]
+pub fn binary_to_julia_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("const {} = UInt8[]\n", name);
+    }
+    format!(
+        "const {} = UInt8[\n{}\n]\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to an R raw vector.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT <- as.raw(c(
+///     0x00, 0x01, 0x02, 0x03
+/// ))
+pub fn binary_to_r_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("{} <- raw(0)\n", name);
+    }
+    format!(
+        "{} <- as.raw(c(\n{}\n))\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a MATLAB/Octave constant, with the bytes in decimal.
+/// Every line is continued with `...`, as a line break in brackets starts a new row.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// TEST_TXT = uint8([ ...
+///     0 1 2 255 ...
+/// ]);
+pub fn binary_to_matlab_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("{} = uint8([]);\n", name);
+    }
+    let elements = binary
+        .iter()
+        .map(|byte| byte.to_string())
+        .collect::<Vec<String>>();
+    format!(
+        "{} = uint8([ ...\n{} ...\n]);\n",
+        name,
+        wrap_elements(
+            &elements,
+            " ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            " ...\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to an Objective-C constant: a C array, and a function wrapping it in
+/// an NSData without copying it.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// #import <Foundation/Foundation.h>
+///
+/// static const unsigned char TEST_TXT[] = {
+///     0x00, 0x01, 0x02, 0x03
+/// };
+///
+/// static NSData *testTxtData(void)
+/// {
+///     return [NSData dataWithBytesNoCopy:(void *)TEST_TXT length:4 freeWhenDone:NO];
+/// }
+pub fn binary_to_objc_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let array = binary_to_c_const(binary, name, tab_size)
+        .replace("const unsigned char", "static const unsigned char");
+    // The length is given rather than a sizeof, as empty inputs are padded to one byte.
+    format!(
+        "#import <Foundation/Foundation.h>\n\n{}\nstatic NSData *{}Data(void)\n{{\n\
+         {}return [NSData dataWithBytesNoCopy:(void *){} length:{} freeWhenDone:NO];\n}}\n",
+        array,
+        NameCase::Camel.apply(name),
+        " ".repeat(tab_size),
+        name,
+        binary.len()
+    )
+}
+
+/// Converts an array of bytes to a memory file for the `$readmemh` task of Verilog, a byte per
+/// word. The name is only given in a comment, as the memory is declared by the design loading it.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// // TEST_TXT, for $readmemh, size 4
+/// 00 01 02 03
+pub fn binary_to_verilog_memh(binary: &[u8], name: &str) -> String {
+    let mut out = format!("// {}, for $readmemh, size {}\n", name, binary.len());
+    for line in binary.chunks(BYTES_PER_LINE) {
+        let words = line
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<String>>();
+        out.push_str(&words.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// Converts an array of bytes to a VHDL constant array of `std_logic_vector`, with its type.
+/// Like in Ada, a positional aggregate needs at least two elements.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// type TEST_TXT_type is array (0 to 3) of std_logic_vector(7 downto 0);
+/// constant TEST_TXT : TEST_TXT_type := (
+///     x"00", x"01", x"02", x"03"
+/// );
+pub fn binary_to_vhdl_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let elements = binary
+        .iter()
+        .map(|byte| format!("x\"{:02x}\"", byte))
+        .collect::<Vec<String>>();
+    let aggregate = match elements.len() {
+        0 => "(others => (others => '0'))".to_string(),
+        1 => format!("(0 => {})", elements[0]),
+        _ => format!(
+            "(\n{}\n)",
+            wrap_elements(&elements, ", ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+        ),
+    };
+    format!(
+        "type {0}_type is array (0 to {1}) of std_logic_vector(7 downto 0);\n\
+         constant {0} : {0}_type := {2};\n",
+        name,
+        binary.len() as i64 - 1,
+        aggregate
+    )
+}
+
+/// Converts an array of bytes to a C constant placed in the flash of AVR boards with PROGMEM,
+/// with the include declaring it. It is read with the pgm_read_byte macros.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// #include <avr/pgmspace.h>
+///
+/// const unsigned char TEST_TXT[] PROGMEM = {
+///     0x00, 0x01, 0x02, 0x03
+/// };
+pub fn binary_to_arduino_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    format!(
+        "#include <avr/pgmspace.h>\n\n{}",
+        binary_to_c_const(binary, name, tab_size).replacen("] = {", "] PROGMEM = {", 1)
+    )
+}
+
+/// Converts an array of bytes to a PowerShell variable, with the lines continued by backticks.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// [byte[]]$TEST_TXT = `
+///     0x00, 0x01, 0x02, 0x03
+pub fn binary_to_powershell_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("[byte[]]${} = @()\n", name);
+    }
+    format!(
+        "[byte[]]${} = `\n{}\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            " `\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a bash array.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT=(
+///     0x00 0x01 0x02 0x03
+/// )
+pub fn binary_to_bash_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("{}=()\n", name);
+    }
+    format!(
+        "{}=(\n{}\n)\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            " ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a shell function printing them, as shell variables can't hold
+/// a null byte. The bytes are escaped in octal, as the printf of POSIX doesn't know `\x`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// TEST_TXT() { printf '\000\001\002\377'; }
+pub fn binary_to_sh_function(binary: &[u8], name: &str) -> String {
+    let escaped = binary
+        .iter()
+        .map(|byte| format!("\\{:03o}", byte))
+        .collect::<String>();
+    format!("{}() {{ printf '{}'; }}\n", name, escaped)
+}
+
+/// Converts an array of bytes to an SQL statement inserting them in a `blobs(name, data)` table,
+/// as an `X''` blob literal for SQLite and MySQL or with `postgres` as a `bytea` in hex format.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and postgres = false,
+/// the function returns:
+/// INSERT INTO blobs(name, data) VALUES ('TEST_TXT', X'00010203');
+pub fn binary_to_sql_insert(binary: &[u8], name: &str, postgres: bool) -> String {
+    let hex = binary
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    let blob = match postgres {
+        true => format!("'\\x{}'::bytea", hex),
+        false => format!("X'{}'", hex),
+    };
+    format!(
+        "INSERT INTO blobs(name, data) VALUES ('{}', {});\n",
+        name, blob
+    )
+}
+
+/// Converts an array of bytes to a VB.NET constant, with its `&H00` literals and the lines
+/// continued by underscores.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// Public Shared ReadOnly TEST_TXT As Byte() = New Byte() { _
+///     &H00, &H01, &H02, &H03 _
+/// }
+pub fn binary_to_vbnet_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!(
+            "Public Shared ReadOnly {} As Byte() = New Byte() {{}}\n",
+            name
+        );
+    }
+    let literals = binary
+        .iter()
+        .map(|byte| format!("&H{:02X}", byte))
+        .collect::<Vec<String>>();
+    format!(
+        "Public Shared ReadOnly {} As Byte() = New Byte() {{ _\n{} _\n}}\n",
+        name,
+        wrap_elements(
+            &literals,
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            " _\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a GDScript constant for Godot 4, or with `godot3` to a variable
+/// for Godot 3, whose PoolByteArray constructor can't be used in a constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and godot3 = false,
+/// the function returns:
+/// const TEST_TXT: PackedByteArray = PackedByteArray([
+///     0x00, 0x01, 0x02, 0x03
+/// ])
+pub fn binary_to_gdscript_const(
+    binary: &[u8],
+    name: &str,
+    tab_size: usize,
+    godot3: bool,
+) -> String {
+    let array = match binary.is_empty() {
+        true => "[]".to_string(),
+        false => format!(
+            "[\n{}\n]",
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        ),
+    };
+    match godot3 {
+        true => format!("var {} = PoolByteArray({})\n", name, array),
+        false => format!(
+            "const {}: PackedByteArray = PackedByteArray({})\n",
+            name, array
+        ),
+    }
+}
+
+/// Converts an array of bytes to an F# array, with the `uy` suffix of the byte literals.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// let TEST_TXT : byte[] = [|
+///     0x00uy; 0x01uy; 0x02uy; 0x03uy
+/// |]
+pub fn binary_to_fsharp_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("let {} : byte[] = [||]\n", name);
+    }
+    let literals = binary
+        .iter()
+        .map(|byte| format!("0x{:02x}uy", byte))
+        .collect::<Vec<String>>();
+    format!(
+        "let {} : byte[] = [|\n{}\n|]\n",
+        name,
+        wrap_elements(&literals, "; ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+    )
+}
+
+/// Converts an array of bytes to a Fortran constant, with the lines continued by ampersands.
+/// The integers of kind 1 are signed, so the bytes above 127 are written as negative numbers.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff] and name = "TEST_TXT", the function returns:
+/// integer(kind=1), parameter :: TEST_TXT(4) = (/ &
+///     0, 1, 2, -1 &
+/// /)
+pub fn binary_to_fortran_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!(
+            "integer(kind=1), parameter :: {}(0) = (/ integer(kind=1) :: /)\n",
+            name
+        );
+    }
+    let elements = binary
+        .iter()
+        .map(|byte| (*byte as i8).to_string())
+        .collect::<Vec<String>>();
+    format!(
+        "integer(kind=1), parameter :: {}({}) = (/ &\n{} &\n/)\n",
+        name,
+        binary.len(),
+        wrap_elements(
+            &elements,
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            " &\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Crystal constant.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// TEST_TXT = Bytes[
+///     0x00, 0x01, 0x02, 0x03,
+/// ]
+pub fn binary_to_crystal_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("{} = Bytes.empty\n", name);
+    }
+    format!(
+        "{} = Bytes[\n{},\n]\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Tcl variable, decoded from hex digits whose whitespace is
+/// ignored by `binary decode hex`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// set TEST_TXT [binary decode hex "
+///     00010203
+/// "]
+pub fn binary_to_tcl_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("set {} [binary decode hex \"\"]\n", name);
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let digits = line.iter().map(|byte| format!("{:02x}", byte));
+            format!("{}{}\n", " ".repeat(tab_size), digits.collect::<String>())
+        })
+        .collect::<String>();
+    format!("set {} [binary decode hex \"\n{}\"]\n", name, lines)
+}
+
+/// Converts an array of bytes to a Common Lisp constant, or with `scheme` to a Scheme bytevector,
+/// in decimal. Their names are written with dashes rather than underscores.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0xff], name = "test_txt" and scheme = false,
+/// the function returns:
+/// (defconstant +test-txt+ #(
+///     0 1 2 255))
+pub fn binary_to_lisp_const(binary: &[u8], name: &str, tab_size: usize, scheme: bool) -> String {
+    let elements = binary
+        .iter()
+        .map(|byte| byte.to_string())
+        .collect::<Vec<String>>();
+    let vector = match binary.is_empty() {
+        true => "()".to_string(),
+        false => format!(
+            "(\n{})",
+            wrap_elements(&elements, " ", &" ".repeat(tab_size), BYTES_PER_LINE, "\n")
+        ),
+    };
+    let name = name.replace('_', "-");
+    match scheme {
+        true => format!("(define {} #u8{})\n", name, vector),
+        false => format!("(defconstant +{}+ #{})\n", name, vector),
+    }
+}
+
+/// Converts an array of bytes to a data segment of the WebAssembly text format, named after the
+/// constant and placed in the memory at `offset`. Every byte is escaped, a line of the segment
+/// being a string.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and offset = 0, the
+/// function returns:
+/// (data $TEST_TXT (i32.const 0)
+///     "\00\01\02\03"
+/// )
+pub fn binary_to_wat_data(binary: &[u8], name: &str, tab_size: usize, offset: usize) -> String {
+    if binary.is_empty() {
+        return format!("(data ${} (i32.const {}) \"\")\n", name, offset);
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let escaped = line.iter().map(|byte| format!("\\{:02x}", byte));
+            format!(
+                "{}\"{}\"\n",
+                " ".repeat(tab_size),
+                escaped.collect::<String>()
+            )
+        })
+        .collect::<String>();
+    format!("(data ${} (i32.const {})\n{})\n", name, offset, lines)
+}
+
+/// Converts an array of bytes to an exported TypeScript constant, typed as a Uint8Array or with
+/// `readonly` as a read-only one, whose bytes can't be assigned.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], name = "TEST_TXT" and readonly = false,
+/// the function returns:
+/// export const TEST_TXT: Uint8Array = new Uint8Array([
+///     0x00, 0x01, 0x02, 0x03
+/// ]);
+pub fn binary_to_typescript_const(
+    binary: &[u8],
+    name: &str,
+    tab_size: usize,
+    readonly: bool,
+) -> String {
+    let array_type = match readonly {
+        true => "Readonly<Uint8Array>",
+        false => "Uint8Array",
+    };
+    if binary.is_empty() {
+        return format!(
+            "export const {}: {} = new Uint8Array(0);\n",
+            name, array_type
+        );
+    }
+    format!(
+        "export const {}: {} = new Uint8Array([\n{}\n]);\n",
+        name,
+        array_type,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Groovy constant, coerced to a byte array, which also converts
+/// the bytes above 0x7f.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// static final byte[] TEST_TXT = [
+///     0x00, 0x01, 0x02, 0x03
+/// ] as byte[]
+pub fn binary_to_groovy_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("static final byte[] {} = [] as byte[]\n", name);
+    }
+    format!(
+        "static final byte[] {} = [\n{}\n] as byte[]\n",
+        name,
+        wrap_elements(
+            &hex_literals(binary),
+            ", ",
+            &" ".repeat(tab_size),
+            BYTES_PER_LINE,
+            "\n"
+        )
+    )
+}
+
+/// Converts an array of bytes to a Solidity constant, as hex literals that Solidity concatenates
+/// when they follow each other.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// bytes constant TEST_TXT =
+///     hex"00010203";
+pub fn binary_to_solidity_const(binary: &[u8], name: &str, tab_size: usize) -> String {
+    if binary.is_empty() {
+        return format!("bytes constant {} = hex\"\";\n", name);
+    }
+    let lines = binary
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let digits = line.iter().map(|byte| format!("{:02x}", byte));
+            format!(
+                "{}hex\"{}\"",
+                " ".repeat(tab_size),
+                digits.collect::<String>()
+            )
+        })
+        .collect::<Vec<String>>();
+    format!("bytes constant {} =\n{};\n", name, lines.join("\n"))
+}
+
+/// Converts an array of bytes to an entry of a YAML list, with the name and the size of the
+/// data next to it, for the fixtures of test suites. Every constant is an entry of the list, so
+/// the outputs of several inputs can be concatenated.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// ```yaml
+/// - name: TEST_TXT
+///   size: 4
+///   data: [
+///       0x00, 0x01, 0x02, 0x03
+///   ]
+/// ```
+pub fn binary_to_yaml(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let data = match binary.is_empty() {
+        true => "[]".to_string(),
+        false => format!(
+            "[\n{}\n  ]",
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size + 2),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        ),
+    };
+    format!(
+        "- name: {}\n  size: {}\n  data: {}\n",
+        name,
+        binary.len(),
+        data
+    )
+}
+
+/// Converts an array of bytes encoded in base64 to an entry of a YAML list, tagged as binary.
+/// For exemple, with encoded = "AAECAw==", size = 4 and name = "TEST_TXT", the function returns:
+/// - name: TEST_TXT
+///   size: 4
+///   data: !!binary "AAECAw=="
+pub fn base64_to_yaml(encoded: &str, size: usize, name: &str) -> String {
+    format!(
+        "- name: {}\n  size: {}\n  data: !!binary \"{}\"\n",
+        name, size, encoded
+    )
+}
+
+/// Converts an array of bytes to a table of the `constants` array of a TOML file, with the name
+/// and the size of the data next to it, for the fixtures of test suites.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03] and name = "TEST_TXT", the function returns:
+/// [[constants]]
+/// name = "TEST_TXT"
+/// size = 4
+/// data = [
+///     0x00, 0x01, 0x02, 0x03,
+/// ]
+pub fn binary_to_toml(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let data = match binary.is_empty() {
+        true => "[]".to_string(),
+        false => format!(
+            "[\n{},\n]",
+            wrap_elements(
+                &hex_literals(binary),
+                ", ",
+                &" ".repeat(tab_size),
+                BYTES_PER_LINE,
+                "\n"
+            )
+        ),
+    };
+    format!(
+        "[[constants]]\nname = \"{}\"\nsize = {}\ndata = {}\n",
+        name,
+        binary.len(),
+        data
+    )
+}
+
+/// Converts an array of bytes encoded in base64 to a table of the `constants` array of a TOML
+/// file.
+/// For exemple, with encoded = "AAECAw==", size = 4 and name = "TEST_TXT", the function returns:
+/// [[constants]]
+/// name = "TEST_TXT"
+/// size = 4
+/// data = "AAECAw=="
+pub fn base64_to_toml(encoded: &str, size: usize, name: &str) -> String {
+    format!(
+        "[[constants]]\nname = \"{}\"\nsize = {}\ndata = \"{}\"\n",
+        name, size, encoded
+    )
+}
+
+/// SHA-256 for the generated Rust tests, with its constants derived from the primes so the test
+/// needs no dependency and has no `0x` literal that --verify would take for the data.
+const RUST_TEST: &str = r#"#[cfg(test)]
+mod {module} {
+	fn sha256(data: &[u8]) -> String {
+		let primes = (2u32..).filter(|n| (2..*n).all(|d| n % d != 0));
+		let fraction = |x: f64| ((x - x.floor()) * 4294967296.0) as u32;
+		let k: Vec<u32> = primes.clone().take(64).map(|p| fraction((p as f64).cbrt())).collect();
+		let mut h: Vec<u32> = primes.take(8).map(|p| fraction((p as f64).sqrt())).collect();
+		let mut message = data.to_vec();
+		message.push(128);
+		while message.len() % 64 != 56 {
+			message.push(0);
+		}
+		message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+		for chunk in message.chunks(64) {
+			let mut w = [0u32; 64];
+			for i in 0..64 {
+				w[i] = if i < 16 {
+					let b = &chunk[i * 4..i * 4 + 4];
+					u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+				} else {
+					let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+					let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+					w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1)
+				};
+			}
+			let mut v = h.clone();
+			for (k, w) in k.iter().zip(w) {
+				let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+				let ch = (v[4] & v[5]) ^ (!v[4] & v[6]);
+				let t1 = v[7].wrapping_add(s1).wrapping_add(ch).wrapping_add(*k).wrapping_add(w);
+				let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+				let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+				v.rotate_right(1);
+				v[0] = t1.wrapping_add(s0).wrapping_add(maj);
+				v[4] = v[4].wrapping_add(t1);
+			}
+			for (h, v) in h.iter_mut().zip(v) {
+				*h = h.wrapping_add(v);
+			}
+		}
+		h.iter().map(|word| format!("{:08x}", word)).collect()
+	}
+
+	#[test]
+	fn matches_its_source() {
+		assert_eq!(super::{name}.len(), {size});
+		assert_eq!(sha256(&super::{name}[..]), "{sha256}");
+	}
+}
+"#;
+
+/// Generates a Rust test checking the length and the SHA-256 of a constant.
+/// For exemple, with binary = b"abc" and name = "TEST_TXT", the function returns a
+/// `mod test_txt_test` whose test asserts:
+/// assert_eq!(super::TEST_TXT.len(), 3);
+/// assert_eq!(sha256(&super::TEST_TXT[..]), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+pub fn binary_to_rust_test(binary: &[u8], name: &str, tab_size: usize) -> String {
+    RUST_TEST
+        .replace('\t', &" ".repeat(tab_size))
+        .replace("{module}", &format!("{}_test", name.to_lowercase()))
+        .replace("{name}", name)
+        .replace("{size}", &binary.len().to_string())
+        .replace("{sha256}", &to_hex_string(&sha256(binary)))
+}
+
+/// Generates a C/C++ compile time check of the size of a constant. C can't hash at compile
+/// time, so the SHA-256 is only given in a comment.
+/// For exemple, with binary = b"abc" and name = "TEST_TXT", the function returns:
+/// /* TEST_TXT: 3 bytes, sha256 ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad */
+/// #ifdef __cplusplus
+/// static_assert(sizeof(TEST_TXT) == 3, "TEST_TXT doesn't match its source");
+/// #else
+/// _Static_assert(sizeof(TEST_TXT) == 3, "TEST_TXT doesn't match its source");
+/// #endif
+pub fn binary_to_c_test(binary: &[u8], name: &str, _tab_size: usize) -> String {
+    // Empty inputs are padded to one byte by binary_to_c_const.
+    let assert = format!(
+        "(sizeof({}) == {}, \"{} doesn't match its source\");",
+        name,
+        binary.len().max(1),
+        name
+    );
+    format!(
+        "/* {}: {} bytes, sha256 {} */\n#ifdef __cplusplus\nstatic_assert{}\n#else\n_Static_assert{}\n#endif\n",
+        name,
+        binary.len(),
+        to_hex_string(&sha256(binary)),
+        assert,
+        assert
+    )
+}
+
+/// Generates a pytest checking the length and the SHA-256 of a constant.
+/// For exemple, with binary = b"abc" and name = "TEST_TXT", the function returns:
+/// def test_test_txt():
+///     import hashlib
+///     assert len(TEST_TXT) == 3
+///     assert hashlib.sha256(TEST_TXT).hexdigest() == "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+pub fn binary_to_python_test(binary: &[u8], name: &str, tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "\ndef test_{}():\n{}import hashlib\n{}assert len({}) == {}\n{}assert hashlib.sha256({}).hexdigest() == \"{}\"\n",
+        name.to_lowercase(),
+        indent,
+        indent,
+        name,
+        binary.len(),
+        indent,
+        name,
+        to_hex_string(&sha256(binary))
+    )
+}
+
+/// Which characters of a text are escaped in the string constants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Escape {
+    /// Only what the string literal can't contain as is: quotes, backslashes and control characters.
+    Minimal,
+    /// Everything that isn't printable ASCII too, for source files that must stay ASCII.
+    Ascii,
+}
+
+impl Escape {
+    /// Parses an escaping name, "minimal" or "ascii".
+    pub fn from_name(name: &str) -> Option<Escape> {
+        match name.to_ascii_lowercase().trim() {
+            "minimal" => Some(Escape::Minimal),
+            "ascii" => Some(Escape::Ascii),
+            _ => None,
+        }
+    }
+
+    /// Whether a character is written as is, tabs and newlines are left to the emitters.
+    fn keeps(self, c: char) -> bool {
+        !c.is_control() && (self == Escape::Minimal || c.is_ascii())
+    }
+}
+
+/// Guesses the MIME type of a file served by a web server from its extension.
+/// For exemple, with file = "www/index.html", the function returns "text/html".
+pub fn mime_type(file: &Path) -> &'static str {
+    let extension = file
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase());
+    match extension.as_deref().unwrap_or("") {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" | "map" => "application/json",
+        "txt" => "text/plain",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// What a web server needs to serve an embedded file, besides its data.
+pub struct WebAsset {
+    pub mime: &'static str,
+    /// The Content-Encoding of the data.
+    pub encoding: &'static str,
+    /// A strong ETag, quotes included.
+    pub etag: String,
+    /// The size of the file before its compression.
+    pub length: usize,
+}
+
+/// Declares the C/C++ struct of the web assets, guarded so that several generated headers can
+/// be included together, for exemple:
+/// #ifndef BIN2CONST_WEB_ASSET
+/// #define BIN2CONST_WEB_ASSET
+/// #include <stddef.h>
+///
+/// struct web_asset {
+///     const char *mime;
+///     const char *encoding;
+///     const char *etag;
+///     size_t length;
+///     const unsigned char *data;
+///     size_t size;
+/// };
+/// #endif
+pub fn web_asset_type_to_c(tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "#ifndef BIN2CONST_WEB_ASSET\n#define BIN2CONST_WEB_ASSET\n#include <stddef.h>\n\n\
+         struct web_asset {{\n\
+         {indent}const char *mime;\n\
+         {indent}const char *encoding;\n\
+         {indent}const char *etag;\n\
+         {indent}size_t length;\n\
+         {indent}const unsigned char *data;\n\
+         {indent}size_t size;\n\
+         }};\n#endif\n"
+    )
+}
+
+/// Declares the web asset of the constant `name` for C/C++, for exemple:
+/// static const struct web_asset INDEX_ASSET = {
+///     "text/html", "gzip", "\"7212b13e379abac8\"", 43, INDEX, sizeof(INDEX)
+/// };
+pub fn web_asset_to_c(entry: &str, name: &str, asset: &WebAsset, tab_size: usize) -> String {
+    format!(
+        "static const struct web_asset {} = {{\n{}\"{}\", \"{}\", \"{}\", {}, {}, sizeof({})\n}};\n",
+        entry,
+        " ".repeat(tab_size),
+        escape_c(asset.mime, Escape::Minimal),
+        escape_c(asset.encoding, Escape::Minimal),
+        escape_c(&asset.etag, Escape::Minimal),
+        asset.length,
+        name,
+        name
+    )
+}
+
+/// Declares the Rust struct of the web assets, for exemple:
+/// struct WebAsset {
+///     mime: &'static str,
+///     encoding: &'static str,
+///     etag: &'static str,
+///     length: usize,
+///     data: &'static [u8],
+/// }
+pub fn web_asset_type_to_rust(tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "struct WebAsset {{\n\
+         {indent}mime: &'static str,\n\
+         {indent}encoding: &'static str,\n\
+         {indent}etag: &'static str,\n\
+         {indent}length: usize,\n\
+         {indent}data: &'static [u8],\n\
+         }}\n"
+    )
+}
+
+/// Declares the web asset of the constant `name` for Rust, for exemple:
+/// const INDEX_ASSET: WebAsset = WebAsset {
+///     mime: "text/html",
+///     encoding: "gzip",
+///     etag: "\"7212b13e379abac8\"",
+///     length: 43,
+///     data: &INDEX,
+/// };
+pub fn web_asset_to_rust(entry: &str, name: &str, asset: &WebAsset, tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "const {entry}: WebAsset = WebAsset {{\n\
+         {indent}mime: \"{}\",\n\
+         {indent}encoding: \"{}\",\n\
+         {indent}etag: \"{}\",\n\
+         {indent}length: {},\n\
+         {indent}data: &{name},\n\
+         }};\n",
+        escape_rust(asset.mime, Escape::Minimal),
+        escape_rust(asset.encoding, Escape::Minimal),
+        escape_rust(&asset.etag, Escape::Minimal),
+        asset.length,
+    )
+}
+
+/// Declares the Go struct of the web assets, for exemple:
+/// type WebAsset struct {
+///     Mime     string
+///     Encoding string
+///     ETag     string
+///     Length   int
+///     Data     []byte
+/// }
+pub fn web_asset_type_to_go(tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "type WebAsset struct {{\n\
+         {indent}Mime     string\n\
+         {indent}Encoding string\n\
+         {indent}ETag     string\n\
+         {indent}Length   int\n\
+         {indent}Data     []byte\n\
+         }}\n"
+    )
+}
+
+/// Declares the web asset of the constant `name` for Go, for exemple:
+/// var INDEX_ASSET = WebAsset{
+///     Mime:     "text/html",
+///     Encoding: "gzip",
+///     ETag:     "\"7212b13e379abac8\"",
+///     Length:   43,
+///     Data:     INDEX,
+/// }
+pub fn web_asset_to_go(entry: &str, name: &str, asset: &WebAsset, tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    format!(
+        "var {entry} = WebAsset{{\n\
+         {indent}Mime:     \"{}\",\n\
+         {indent}Encoding: \"{}\",\n\
+         {indent}ETag:     \"{}\",\n\
+         {indent}Length:   {},\n\
+         {indent}Data:     {name},\n\
+         }}\n",
+        escape_rust(asset.mime, Escape::Minimal),
+        escape_rust(asset.encoding, Escape::Minimal),
+        escape_rust(&asset.etag, Escape::Minimal),
+        asset.length,
+    )
+}
+
+/// Declares the web asset of the constant `name` as a Python dict or a Javascript object, for
+/// exemple for Python:
+/// INDEX_ASSET = {
+///     "mime": "text/html",
+///     "encoding": "gzip",
+///     "etag": "\"7212b13e379abac8\"",
+///     "length": 43,
+///     "data": INDEX,
+/// }
+pub fn web_asset_to_table(
+    entry: &str,
+    name: &str,
+    asset: &WebAsset,
+    tab_size: usize,
+    javascript: bool,
+) -> String {
+    let indent = " ".repeat(tab_size);
+    let text = |text: &str| format!("\"{}\"", escape_rust(text, Escape::Minimal));
+    let fields = [
+        ("mime", text(asset.mime)),
+        ("encoding", text(asset.encoding)),
+        ("etag", text(&asset.etag)),
+        ("length", asset.length.to_string()),
+        ("data", name.to_string()),
+    ];
+    let mut out = match javascript {
+        true => format!("const {} = {{\n", entry),
+        false => format!("{} = {{\n", entry),
+    };
+    for (key, value) in fields {
+        match javascript {
+            true => out.push_str(&format!("{}{}: {},\n", indent, key, value)),
+            false => out.push_str(&format!("{}\"{}\": {},\n", indent, key, value)),
+        }
+    }
+    out.push_str(if javascript { "};\n" } else { "}\n" });
+    out
+}
+
+/// Generates a Rust function finding constants by the path of their file, with a match the
+/// compiler turns into a search on the length then the bytes of the path.
+/// For exemple, with entries = [("icons/home.png", "HOME_PNG", 4)] and function = "find_asset",
+/// the function returns:
+/// /// Finds an embedded file by its path.
+/// fn find_asset(path: &str) -> Option<&'static [u8]> {
+///     match path {
+///         "icons/home.png" => Some(&HOME_PNG[..]),
+///         _ => None,
+///     }
+/// }
+pub fn lookup_to_rust(
+    entries: &[(&str, String, usize)],
+    function: &str,
+    tab_size: usize,
+) -> String {
+    let indent = " ".repeat(tab_size);
+    let mut out = format!(
+        "/// Finds an embedded file by its path.\n\
+         fn {}(path: &str) -> Option<&'static [u8]> {{\n{}match path {{\n",
+        function, indent
+    );
+    for (path, name, _) in entries {
+        out.push_str(&format!(
+            "{indent}{indent}\"{}\" => Some(&{}[..]),\n",
+            escape_rust(path, Escape::Minimal),
+            name
+        ));
+    }
+    out.push_str(&format!("{indent}{indent}_ => None,\n{indent}}}\n}}\n"));
+    out
+}
+
+/// Generates a C++ function finding constants by the path of their file, with a binary search
+/// in a constexpr table sorted by path.
+/// For exemple, with entries = [("icons/home.png", "HOME_PNG", 4)] and function = "find_asset",
+/// the function returns:
+/// #include <algorithm>
+/// #include <cstddef>
+/// #include <cstring>
+/// #include <iterator>
+///
+/// struct find_asset_entry {
+///     const char *path;
+///     const unsigned char *data;
+///     std::size_t size;
+/// };
+///
+/// constexpr find_asset_entry find_asset_entries[] = {
+///     { "icons/home.png", HOME_PNG, 4 },
+/// };
+///
+/// // Finds an embedded file by its path, returns nullptr if there is none.
+/// inline const find_asset_entry *find_asset(const char *path) {
+///     auto end = std::end(find_asset_entries);
+///     auto found = std::lower_bound(std::begin(find_asset_entries), end, path,
+///         [](const find_asset_entry &entry, const char *path) {
+///             return std::strcmp(entry.path, path) < 0;
+///         });
+///     return found != end && std::strcmp(found->path, path) == 0 ? found : nullptr;
+/// }
+pub fn lookup_to_cpp(entries: &[(&str, String, usize)], function: &str, tab_size: usize) -> String {
+    let indent = " ".repeat(tab_size);
+    let mut out = format!(
+        "#include <algorithm>\n#include <cstddef>\n#include <cstring>\n#include <iterator>\n\n\
+         struct {function}_entry {{\n\
+         {indent}const char *path;\n\
+         {indent}const unsigned char *data;\n\
+         {indent}std::size_t size;\n\
+         }};\n\n\
+         constexpr {function}_entry {function}_entries[] = {{\n"
+    );
+    for (path, name, size) in entries {
+        out.push_str(&format!(
+            "{indent}{{ \"{}\", {}, {} }},\n",
+            escape_c(path, Escape::Minimal),
+            name,
+            size
+        ));
+    }
+    out.push_str(&format!(
+        "}};\n\n\
+         // Finds an embedded file by its path, returns nullptr if there is none.\n\
+         inline const {function}_entry *{function}(const char *path) {{\n\
+         {indent}auto end = std::end({function}_entries);\n\
+         {indent}auto found = std::lower_bound(std::begin({function}_entries), end, path,\n\
+         {indent}{indent}[](const {function}_entry &entry, const char *path) {{\n\
+         {indent}{indent}{indent}return std::strcmp(entry.path, path) < 0;\n\
+         {indent}{indent}}});\n\
+         {indent}return found != end && std::strcmp(found->path, path) == 0 ? found : nullptr;\n\
+         }}\n"
+    ));
+    out
+}
+
+/// Generates a C accessor returning a constant and its length, so the code using it doesn't
+/// depend on how the bytes are declared.
+/// For exemple, with name = "TEST_TXT", function = "test_txt_data" and size = 4, the function
+/// returns:
+/// #include <stddef.h>
+/// #include <stdint.h>
+///
+/// static inline const uint8_t *test_txt_data(size_t *len)
+/// {
+///     *len = 4;
+///     return TEST_TXT;
+/// }
+pub fn accessor_to_c(name: &str, function: &str, size: usize, tab_size: usize) -> String {
+    // The size is given rather than a sizeof, as empty inputs are padded to one byte.
+    format!(
+        "#include <stddef.h>\n#include <stdint.h>\n\n\
+         static inline const uint8_t *{}(size_t *len)\n{{\n{indent}*len = {};\n{indent}return {};\n}}\n",
+        function,
+        size,
+        name,
+        indent = " ".repeat(tab_size)
+    )
+}
+
+/// Generates a Rust accessor returning a constant as a slice.
+/// For exemple, with name = "TEST_TXT" and function = "test_txt", the function returns:
+/// /// Returns the bytes of TEST_TXT.
+/// pub fn test_txt() -> &'static [u8] {
+///     &TEST_TXT[..]
+/// }
+pub fn accessor_to_rust(name: &str, function: &str, tab_size: usize) -> String {
+    format!(
+        "/// Returns the bytes of {}.\npub fn {}() -> &'static [u8] {{\n{}&{}[..]\n}}\n",
+        name,
+        function,
+        " ".repeat(tab_size),
+        name
+    )
+}
+
+/// Generates a C# property returning a constant as a read-only span, so it can't be modified.
+/// For exemple, with name = "TEST_TXT" and function = "TestTxt", the function returns:
+/// public static System.ReadOnlySpan<byte> TestTxt => TEST_TXT;
+pub fn accessor_to_csharp(name: &str, function: &str) -> String {
+    format!(
+        "public static System.ReadOnlySpan<byte> {} => {};\n",
+        function, name
+    )
+}
+
+/// Escapes a line of text for a C string literal. Non-ASCII characters are escaped as their
+/// UTF-8 bytes in octal, which unlike `\x` can't swallow the digits that follow.
+fn escape_c(line: &str, escape: Escape) -> String {
+    let mut out = String::new();
+    for c in line.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            // "??" starts trigraphs in older C and C++.
+            '?' if out.ends_with('?') => out.push_str("\\?"),
+            c if escape.keeps(c) => out.push(c),
+            c => {
+                for byte in c.to_string().bytes() {
+                    out.push_str(&format!("\\{:03o}", byte));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Escapes a line of text for a Rust string literal.
+fn escape_rust(line: &str, escape: Escape) -> String {
+    let mut out = String::new();
+    for c in line.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c if escape.keeps(c) => out.push(c),
+            c => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+        }
+    }
+    out
+}
+
+/// Escapes a text for a Python string. In a triple-quoted string newlines and tabs stay as is
+/// and quotes are only escaped when they could close the string.
+fn escape_python(text: &str, escape: Escape, triple_quoted: bool) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if !triple_quoted || matches!(chars.peek(), Some('"') | None) => {
+                out.push_str("\\\"")
+            }
+            '\\' => out.push_str("\\\\"),
+            '\n' | '\t' if triple_quoted => out.push(c),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if escape.keeps(c) => out.push(c),
+            c if (c as u32) < 0x100 => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c if (c as u32) < 0x10000 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push_str(&format!("\\U{:08x}", c as u32)),
+        }
+    }
+    out
+}
+
+/// Converts a text to a C string constant, one literal per line.
+/// For exemple, with text = "Hello\nWorld\n" and name = "TEST_TXT", the function returns:
+/// const char *TEST_TXT =
+///     "Hello\n"
+///     "World\n";
+pub fn text_to_c_const(text: &str, name: &str, tab_size: usize, escape: Escape) -> String {
+    if !text.contains('\n') {
+        return format!("const char *{} = \"{}\";\n", name, escape_c(text, escape));
+    }
+    let lines = text
+        .split_inclusive('\n')
+        .map(|line| format!("{}\"{}\"", " ".repeat(tab_size), escape_c(line, escape)))
+        .collect::<Vec<String>>();
+    format!("const char *{} =\n{};\n", name, lines.join("\n"))
+}
+
+/// Converts a text to a Rust string constant, one literal per line.
+/// For exemple, with text = "Hello\nWorld\n" and name = "TEST_TXT", the function returns:
+/// const TEST_TXT: &str = concat!(
+///     "Hello\n",
+///     "World\n",
+/// );
+pub fn text_to_rust_const(text: &str, name: &str, tab_size: usize, escape: Escape) -> String {
+    if !text.contains('\n') {
+        return format!(
+            "const {}: &str = \"{}\";\n",
+            name,
+            escape_rust(text, escape)
+        );
+    }
+    let lines = text
+        .split_inclusive('\n')
+        .map(|line| {
+            format!(
+                "{}\"{}\",\n",
+                " ".repeat(tab_size),
+                escape_rust(line, escape)
+            )
+        })
+        .collect::<String>();
+    format!("const {}: &str = concat!(\n{});\n", name, lines)
+}
+
+/// Converts a text to a Python triple-quoted string constant.
+/// For exemple, with text = "Hello\nWorld\n" and name = "TEST_TXT", the function returns:
+/// TEST_TXT = """\
+/// Hello
+/// World
+/// """
+pub fn text_to_python_const(text: &str, name: &str, _tab_size: usize, escape: Escape) -> String {
+    format!(
+        "{} = \"\"\"\\\n{}\"\"\"\n",
+        name,
+        escape_python(text, escape, true)
+    )
+}
+
+/// Converts lines of text to a C array of strings.
+/// For exemple, with lines = &["Hello", "World"] and name = "TEST_TXT", the function returns:
+/// const char *TEST_TXT[] = {
+///     "Hello",
+///     "World"
+/// };
+/// C arrays can't be empty, so no lines give a single null pointer.
+pub fn lines_to_c_const(lines: &[&str], name: &str, tab_size: usize, escape: Escape) -> String {
+    if lines.is_empty() {
+        return format!(
+            "/* The input is empty, padded to one line as C arrays can't be empty */\n\
+             const char *{}[1] = {{ 0 }};\n",
+            name
+        );
+    }
+    let elements = lines
+        .iter()
+        .map(|line| format!("\"{}\"", escape_c(line, escape)))
+        .collect::<Vec<String>>();
+    format!(
+        "const char *{}[] = {{\n{}\n}};\n",
+        name,
+        wrap_elements(&elements, ",", &" ".repeat(tab_size), 1, "\n")
+    )
+}
+
+/// Converts lines of text to a Rust array of strings.
+/// For exemple, with lines = &["Hello", "World"] and name = "TEST_TXT", the function returns:
+/// const TEST_TXT: [&str; 2] = [
+///     "Hello",
+///     "World"
+/// ];
+pub fn lines_to_rust_const(lines: &[&str], name: &str, tab_size: usize, escape: Escape) -> String {
+    if lines.is_empty() {
+        return format!("const {}: [&str; 0] = [];\n", name);
+    }
+    let elements = lines
+        .iter()
+        .map(|line| format!("\"{}\"", escape_rust(line, escape)))
+        .collect::<Vec<String>>();
+    format!(
+        "const {}: [&str; {}] = [\n{}\n];\n",
+        name,
+        lines.len(),
+        wrap_elements(&elements, ",", &" ".repeat(tab_size), 1, "\n")
+    )
+}
+
+/// Converts lines of text to a Python list of strings.
+/// For exemple, with lines = &["Hello", "World"] and name = "TEST_TXT", the function returns:
+/// TEST_TXT = [
+///     "Hello",
+///     "World"
+/// ]
+pub fn lines_to_python_const(
+    lines: &[&str],
+    name: &str,
+    tab_size: usize,
+    escape: Escape,
+) -> String {
+    if lines.is_empty() {
+        return format!("{} = []\n", name);
+    }
+    let elements = lines
+        .iter()
+        .map(|line| format!("\"{}\"", escape_python(line, escape, false)))
+        .collect::<Vec<String>>();
+    format!(
+        "{} = [\n{}\n]\n",
+        name,
+        wrap_elements(&elements, ",", &" ".repeat(tab_size), 1, "\n")
+    )
+}
@@ -1,101 +1,2089 @@
-use std::env::args;
+use std::env::args_os;
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+mod audio;
+mod decode;
+mod deflate;
+mod directory;
+#[cfg(feature = "disasm")]
+mod disasm;
+mod encoding;
+mod font;
+mod hash;
+mod ihex;
+mod image;
+mod language;
 mod library;
+mod selftest;
+mod srec;
+use audio::*;
+use decode::*;
+use deflate::*;
+use directory::*;
+use encoding::*;
+use font::*;
+use hash::*;
+use ihex::*;
+use image::*;
+use language::*;
 use library::*;
+use srec::*;
 
 const USAGE_DOC: &str = "\
-Usage: bin2const <input_file> <output_const_name> <conversion_type> [tab_size] [output_file]
-    <input_file>        The file to convert.
+Usage: bin2const <input_file> <output_const_name> <conversion_type> [tab_size] [output_file] [options]
+       bin2const <input_file> --name <name> --format <type> [--tab-size <size>] [--output <file>]
+    <input_file>        The file to convert, - to read stdin.
     <output_const_name> The name of the constant to generate. Has no effect if the conversion type
                         is bin or hex.
     <conversion_type>   The type of conversion to use. See below for the list of available types.
                         - raw: Prints the binary in a \"raw\" format.
                         - bin: Prints the binary in binary dissasembly.
                         - hex: Prints the binary in hexadecimal dissasembly.
+                        - octal: Prints the binary in octal disassembly, like od -b.
+                        - csv: Prints a row per byte with its offset, hex and decimal values and
+                          character, for spreadsheets.
+                        - tsv: Same as csv, separated by tabs, with tabs and newlines escaped.
+                        - html: Prints the hex dump as a styled HTML table, to embed in reports.
+                        - find: Searches the binary for the pattern given as <output_const_name>
+                          and prints the offsets of the matches with their hex dissasembly.
+                        - hash: Prints the size, MD5, SHA-1, SHA-256 and CRC32 of the binary.
+                        - base64: Prints the binary encoded in base64.
+                        - base32: Prints the binary encoded in base32.
+                        - ascii85: Prints the binary encoded in Ascii85, for PostScript and PDF.
+                        - z85: Prints the binary encoded in Z85, its size must be a multiple of 4.
+                        - hexstring: Prints the binary as bare hex digits, without offsets.
+                        - uuencode: Prints the binary uuencoded, with the constant name as the
+                          name of the file.
+                        - srec: Prints the binary as Motorola S-records, with the constant name
+                          in the header record.
+                        - decode-hex: Reads a dump of hex, hexdump -C or xxd back into bytes,
+                          written as is.
+                        - decode-array: Reads the array of the constant <output_const_name> of a
+                          C, C++, Rust, C# or Java source back into bytes, written as is.
+                        - disasm: Prints the disassembly of the binary, requires the disasm feature.
                         - c: Prints the binary as a C constant.
                         - cdef: Prints the binary as a C #define.
+                        - c-string: Prints the binary as a C string literal, with its length.
                         - rust: Prints the binary as a Rust constant.
+                        - rust-include: Prints a Rust static including the file with
+                          include_bytes!, relative to the output file.
+                        - rust-bytes: Prints the binary as a Rust byte string constant.
                         - csharp: Prints the binary as a C# constant.
                         - python: Prints the binary as a Python constant.
                         - javascript: Prints the binary as a Javascript constant.
-                        - typescript: Prints the binary as a Typescript constant.
+                        - typescript: Prints the binary as an exported Typescript constant.
                         - go: Prints the binary as a Go constant.
                         - java: Prints the binary as a Java constant.
+                        - ca65: Prints the binary as a 6502 label for ca65, with its size.
+                        - acme: Prints the binary as a 6502 label for ACME, with its size.
+                        - z80asm: Prints the binary as a Z80 label for z80asm, with its size.
+                        - zig: Prints the binary as a Zig constant.
+                        - swift: Prints the binary as a Swift [UInt8] constant.
+                        - swift-data: Prints the binary as a Swift Data constant.
+                        - kotlin: Prints the binary as a Kotlin ByteArray.
+                        - nim: Prints the binary as a Nim constant.
+                        - d: Prints the binary as a D constant.
+                        - pascal: Prints the binary as a Pascal/Delphi constant.
+                        - ada: Prints the binary as an Ada Stream_Element_Array constant.
+                        - nasm: Prints the binary as an x86 label for NASM, with its length.
+                        - gas: Prints the binary as a global label for the GNU assembler, with its
+                          size.
+                        - lua: Prints the binary as a Lua string.
+                        - lua-table: Prints the binary as a Lua table of numbers.
+                        - php: Prints the binary as a PHP string constant.
+                        - php-array: Prints the binary as a PHP array constant.
+                        - ruby: Prints the binary as a Ruby string packed from an array.
+                        - ruby-string: Prints the binary as a Ruby string literal.
+                        - perl: Prints the binary as a Perl variable packed from a list.
+                        - haskell: Prints the binary as a Haskell ByteString packed from a list.
+                        - ocaml: Prints the binary as OCaml bytes made from an escaped string.
+                        - elixir: Prints the binary as an Elixir module attribute.
+                        - erlang: Prints the binary as an Erlang macro.
+                        - scala: Prints the binary as a Scala Array[Byte].
+                        - dart: Prints the binary as a Dart Uint8List.
+                        - julia: Prints the binary as a Julia constant.
+                        - r: Prints the binary as an R raw vector.
+                        - matlab: Prints the binary as a MATLAB/Octave uint8 array, in decimal.
+                        - objc: Prints the binary as an Objective-C array, with a function
+                          returning it as an NSData.
+                        - verilog: Prints the binary as a memory file for $readmemh, with a byte
+                          per word.
+                        - vhdl: Prints the binary as a VHDL constant array of std_logic_vector.
+                        - arduino: Prints the binary as a C constant in the flash of AVR boards,
+                          with PROGMEM.
+                        - powershell: Prints the binary as a PowerShell byte array, continued with
+                          backticks.
+                        - bash: Prints the binary as a bash array.
+                        - sh: Prints the binary as a shell function printing it with printf.
+                        - sql: Prints the binary as an SQL statement inserting it in a blobs(name,
+                          data) table, for SQLite and MySQL.
+                        - postgres: Same as sql, with a bytea for PostgreSQL.
+                        - vbnet: Prints the binary as a VB.NET constant, continued with
+                          underscores.
+                        - gdscript: Prints the binary as a GDScript PackedByteArray constant, for
+                          Godot 4.
+                        - gdscript3: Prints the binary as a GDScript PoolByteArray variable, for
+                          Godot 3.
+                        - fsharp: Prints the binary as an F# byte array.
+                        - fortran: Prints the binary as a Fortran constant of signed bytes,
+                          continued with ampersands.
+                        - crystal: Prints the binary as a Crystal Bytes constant.
+                        - tcl: Prints the binary as a Tcl variable decoded from hex digits.
+                        - lisp: Prints the binary as a Common Lisp constant vector.
+                        - scheme: Prints the binary as a Scheme bytevector.
+                        - wat: Prints the binary as a data segment of the WebAssembly text format,
+                          at the --address.
+                        - groovy: Prints the binary as a Groovy byte array constant.
+                        - solidity: Prints the binary as a Solidity bytes constant made of hex
+                          literals.
+                        - yaml: Prints the binary as an entry of a YAML list, with its name and
+                          size.
+                        - toml: Prints the binary as a table of the constants array of a TOML file,
+                          with its name and size.
     [tab_size]          The size of a tabulation in the output file. Per default is 4.
     [output_file]       Optional output file, if not specified, the output will be printed to stdout.
+
+The arguments after the input file can be given as named flags instead, the others keeping their
+order: --name <output_const_name>, --format <conversion_type>, --tab-size <tab_size> and
+-o, --output <output_file>. The tab size can be left out before the output file.
+Without an input file, the named flags read the input from stdin.
+-h, --help prints this help and -V, --version the version of bin2const.
+
+Several inputs can be emitted into a single output with repeated --input/--name pairs:
+    bin2const --input <input_file> --name <output_const_name> [--input ... --name ...]
+              <conversion_type> [tab_size] [output_file]
+Without any --name, each constant is named after its file, logo_png for assets/logo.png.
+
+An input can be a directory, whose files are converted to constants named after their paths,
+<output_const_name>_icons_home_png for icons/home.png. The hidden files are skipped.
+An input can also be a glob pattern such as \"assets/*.png\", expanded by bin2const itself, whose
+files are named after their paths from the directory before the first wildcard.
+
+`bin2const selftest` runs every conversion type against built-in reference vectors, and checks
+the Rust, Python and Javascript outputs with rustfmt, python3 and node when they are installed.
+
+`bin2const verify <input_file> <source_file> [conversion_type]` decodes the array of a source
+file generated before, as the conversion type or the language of its extension, and fails with a
+summary of the difference if it doesn't hold the input anymore.
+
+Options:
+    --include <glob>    Only converts the files of the directories matching the glob, can be
+                        repeated. `*` matches anything but a `/`, `**` anything, and globs without
+                        a `/` are matched against the file names, like `*.png`.
+    --exclude <glob>    Skips the files and directories matching the glob, can be repeated.
+    --respect-gitignore Skips the files ignored by the .gitignore files of the directories.
+    --hidden            Also converts the hidden files of the directories.
+    --max-depth <depth> How deep the directories are walked, 1 being only their own files.
+    --lookup <function> (c, rust) Adds a function finding the constants by the path of their file,
+                        a match for Rust and a sorted constexpr table for C++.
+    --package <name>    (go, java) Starts the output with the package declaration, so it compiles
+                        as is. Java also needs --class.
+    --class <name>      (java) Wraps the constants in a public final class of this name.
+    --sort <order>      The order of the constants of several inputs, can be name, size, mtime or
+                        manifest-order (the order of the command line). Per default is name,
+                        as the constants are emitted, or manifest-order for --input/--name pairs.
+    --case <case>       How the constant name is cased, can be upper (TEST_TXT), lower (test_txt),
+                        camel (testTxt), pascal (TestTxt) or preserve. Per default is upper.
+                        (base32, hexstring) The case of the digits, upper or lower. Per default
+                        is upper for base32 and lower for hexstring.
+    --line-ending <eol> The line endings of the output, can be lf, crlf or native (crlf on Windows).
+                        Per default is lf.
+    --crlf              Same as --line-ending crlf.
+    --replace-in <file> Only replaces the values of the constants already declared in the file,
+                        keeping their declarations and the rest of the file.
+    --region            Only replaces the lines between the `bin2const:begin NAME` and
+                        `bin2const:end` comments of the existing output file, for each NAME given.
+    --check             Doesn't write the output file but exits with an error and a summary of the
+                        differences if it doesn't match what would be generated, for CI.
+    --emit-tests        Also generates a test checking the length and the SHA-256 of the constant
+                        (a #[test] for Rust, a static_assert on the size for C, a pytest for Python).
+    --readonly          (typescript) Types the constant as a Readonly<Uint8Array>.
+    --emit-accessor     Also generates an accessor for the constant, a `name_data(size_t *len)`
+                        function for C, a `name() -> &'static [u8]` function for Rust and a
+                        ReadOnlySpan<byte> property for C#.
+    --verify            Decodes the generated array back once written and fails if it differs from
+                        the input, byte for byte or by SHA-256.
+    --stats             Prints the input and output sizes, their ratio, the line count and the time
+                        taken for each input to stderr. --stats=json prints them as JSON lines.
+    --fsync             Flushes the output file to the disk before returning. The output file is
+                        always replaced atomically, it is never left half written.
+    --name-prefix <prefix>
+                        Prepended to the constant names before they are cased, such as ASSET_.
+    --name-suffix <suffix>
+                        Appended to the constant names before they are cased, such as _BIN.
+    --fix-names         Fixes constant names that are not valid identifiers or are reserved words
+                        in the target language instead of failing, with a warning.
+    --text              Embeds the input as a string constant instead of bytes, for shaders, SQL or
+                        config files. The input must be UTF-8. Supported for C, Rust and Python.
+    --lines             Embeds each line of the input as a string of an array, for word lists, help
+                        screens or ASCII art. The input must be UTF-8. Supported for C, Rust and Python.
+    --wrap-markdown     Wraps the output in a Markdown code block tagged with its language, to paste
+                        in docs and issues.
+    --base64            Embeds the data as a base64 string constant instead of bytes, to be decoded
+                        at run time.
+    --image             Also emits the width, height and bits per pixel of a PNG, BMP or GIF input as
+                        NAME_WIDTH, NAME_HEIGHT and NAME_BPP.
+    --image-format <format>
+                        (--image) Reads the input as png, bmp or gif instead of detecting it.
+    --pixels <format>   (--image) Embeds the decoded pixels instead of the file, as rgb565,
+                        rgb565be or 1bpp. NAME_BPP is then the one of the pixels.
+    --wav               Also emits the sample rate, channel count and bits per sample of a WAV input
+                        as NAME_SAMPLE_RATE, NAME_CHANNELS and NAME_BITS_PER_SAMPLE.
+    --pcm               Same as --wav but only embeds the samples, without the WAV header.
+    --espidf            (c) Embeds the file the ESP-IDF way: prints the CMake line adding it to
+                        the component and the declarations of the symbols it gets.
+                        --espidf=array prints a plain array with its size instead.
+    --webasset          Embeds the file gzipped for a web server, with its MIME type, encoding,
+                        ETag and uncompressed length, in one NAME_ASSET struct or table.
+    --font              Also emits the size and the family name of a TTF, OTF, TTC or BDF font input
+                        as NAME_SIZE and NAME_FAMILY, and aligns the data to 4 bytes.
+    --total-size <bytes>
+                        Pads the data to exactly this size, failing if it is bigger, for blobs
+                        filling a flash partition or an EEPROM.
+    --fill <byte>       (--total-size) The byte the data is padded with.
+                        (--input-format ihex, srec) The byte filling the gaps between the records.
+                        Per default is 0xff.
+    --align <bytes>     Aligns the data to this many bytes, for C/C++ and Rust. In Rust the
+                        constant is then a reference to the array.
+    --escape <escaping> (--text, --lines) Which characters are escaped, can be minimal (quotes, backslashes
+                        and control characters) or ascii (everything that isn't printable ASCII
+                        too). Per default is minimal.
+    --charset <charset> (bin, hex, find) How the text column is decoded, can be ascii, utf8, utf16le,
+                        utf16be or ebcdic. Per default is ascii.
+    --layout <file>     (hex) Annotates the dump with the fields described in the layout file, one
+                        field per line as `name offset size type`, see the readme for the types.
+    --xxd               (hex) Prints the dump exactly like xxd does per default, so it can be
+                        compared to the output of xxd.
+    --color             (html) Colors the bytes by class: null, printable ASCII or high bit set.
+    --string            (find) Searches the pattern as text instead of hex bytes.
+    --context <bytes>   (find) The number of bytes shown around each match. Per default is 16.
+    --arch <arch>       (disasm) The architecture to disassemble for. Per default is x86_64.
+                        Can be x86, x86_16, x86_64, arm, thumb, arm64, mips, mips64, riscv32, riscv64.
+    -s, --offset <bytes>
+                        (bin, hex, octal, csv, html, find, hash, disasm, srec and the encodings)
+                        Only uses the input from this offset, which can't be past its end.
+    -l, --length <bytes>
+                        (bin, hex, octal, csv, html, find, hash, disasm, srec and the encodings)
+                        Only uses this many bytes of the input.
+    --address <addr>    (disasm) The address the input is loaded at. Per default is the offset.
+                        (--linker-script) The address the section is placed at.
+                        (wat) The offset of the data segment in the memory. Per default is 0.
+    --wrap <columns>    (base64, base32, hexstring, ascii85, z85) The length of the lines, 0 not to
+                        split them. Per default is 76.
+    --url-safe          (base64, --base64) Encodes with the URL-safe alphabet, - and _ instead of
+                        + and /.
+    --frame             (ascii85) Writes the <~ and ~> delimiters around the encoded data.
+    --mode <mode>       (uuencode) The permissions of the file, in octal. Per default is 644.
+    --base-address <addr>
+                        (srec) The address of the first byte in the records. Per default is 0.
+    --address-width <bits>
+                        (srec) The size of the addresses, 16 (S1/S9 records), 24 (S2/S8) or 32
+                        (S3/S7). Per default the smallest holding the addresses.
+    --input-format <format>
+                        How the input file is read: raw bytes, the data of an ihex (Intel HEX) or
+                        srec (Motorola S-records) file, laid out by address, or text decoded from
+                        base64 or hexstring (bare hex digits). Per default is raw.
+    --max-size <bytes>  (--input-format ihex, srec, decode-hex) The most bytes the records or
+                        the squeezed lines of a dump can span, gaps included. Per default is 16 MiB.
+    --section <name>    (c, gas) Places the constant in this linker section, like .assets.
+    --linker-script <file>
+                        (c, gas) Also writes a GNU ld script fragment placing the --section at the
+                        --address to this file.
+
+Numbers can be given in decimal or in hexadecimal with a 0x prefix.
+
+Patterns are hex bytes such as \"de ad be ef\" where ? matches any nibble (\"de ?? b?\"),
+or text with --string where ? matches any byte.
+
+The errors are printed to stderr, and the exit code tells what went wrong:
+    0   Success, also when stdout is closed early, as by `| head`.
+    1   The conversion failed, or --check, --verify or verify found a difference.
+    2   The arguments are missing, invalid or can't be used together.
+    3   An input file can't be read.
+    4   An output file, or stdout, can't be written.
+    5   The conversion type or the --input-format is unknown.
 ";
 
+/// The exit code of a conversion that failed, or of a check that found a difference.
+const EXIT_FAILURE: i32 = 1;
+/// The exit code of missing, invalid or incompatible arguments.
+const EXIT_USAGE: i32 = 2;
+/// The exit code of an input file that can't be read.
+const EXIT_READ: i32 = 3;
+/// The exit code of an output file that can't be written.
+const EXIT_WRITE: i32 = 4;
+/// The exit code of an unknown conversion type or input format.
+const EXIT_UNKNOWN_FORMAT: i32 = 5;
+
+/// An error ending bin2const, with the exit code telling its kind.
+struct Failure {
+    code: i32,
+    message: String,
+}
+
+impl Failure {
+    fn new(code: i32, message: impl Into<String>) -> Failure {
+        Failure {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// The errors of the conversions themselves.
+impl From<String> for Failure {
+    fn from(message: String) -> Failure {
+        Failure::new(EXIT_FAILURE, message)
+    }
+}
+
+/// Prints an error to stderr and exits with its code.
+fn fail(code: i32, message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(code)
+}
+
+/// Writes the parts to stdout one after the other, exiting quietly when the reader is gone, as
+/// when the output is piped into `head`.
+fn write_stdout(parts: &[&[u8]]) {
+    let mut stdout = std::io::stdout().lock();
+    let written = parts
+        .iter()
+        .try_for_each(|part| stdout.write_all(part))
+        .and_then(|_| stdout.flush());
+    match written {
+        Ok(_) => (),
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+        Err(e) => fail(EXIT_WRITE, format!("Error while writing to stdout: {}", e)),
+    }
+}
+
+/// Options given as `--name value` or `--name` anywhere on the command line.
+/// Values are kept as given by the OS, so paths that are not valid UTF-8 survive.
+struct Flags {
+    values: Vec<(String, Option<OsString>)>,
+}
+
+/// The flags that expect a value.
+const VALUE_FLAGS: &[&str] = &[
+    "--case",
+    "--charset",
+    "--layout",
+    "--line-ending",
+    "--context",
+    "--arch",
+    "--offset",
+    "--length",
+    "--address",
+    "--wrap",
+    "--mode",
+    "--base-address",
+    "--address-width",
+    "--input-format",
+    "--escape",
+    "--image-format",
+    "--pixels",
+    "--align",
+    "--input",
+    "--name",
+    "--format",
+    "--tab-size",
+    "--output",
+    "--name-prefix",
+    "--name-suffix",
+    "--replace-in",
+    "--include",
+    "--exclude",
+    "--max-depth",
+    "--sort",
+    "--section",
+    "--linker-script",
+    "--lookup",
+    "--total-size",
+    "--fill",
+    "--max-size",
+    "--package",
+    "--class",
+];
+
+/// The flags that don't expect a value, though some take an optional one as `--flag=value`.
+const SWITCH_FLAGS: &[&str] = &[
+    "--help",
+    "--version",
+    "--xxd",
+    "--color",
+    "--string",
+    "--url-safe",
+    "--frame",
+    "--text",
+    "--lines",
+    "--fix-names",
+    "--image",
+    "--wav",
+    "--pcm",
+    "--font",
+    "--webasset",
+    "--espidf",
+    "--readonly",
+    "--base64",
+    "--emit-tests",
+    "--emit-accessor",
+    "--crlf",
+    "--hidden",
+    "--respect-gitignore",
+    "--stats",
+    "--check",
+    "--verify",
+    "--region",
+    "--wrap-markdown",
+    "--fsync",
+];
+
+/// The short forms of some flags.
+const SHORT_FLAGS: &[(&str, &str)] = &[
+    ("-s", "--offset"),
+    ("-l", "--length"),
+    ("-o", "--output"),
+    ("-h", "--help"),
+    ("-V", "--version"),
+];
+
+impl Flags {
+    /// Splits the command line into the positional arguments and the flags.
+    fn parse(args: Vec<OsString>) -> Result<(Vec<OsString>, Flags), String> {
+        let mut positional = Vec::new();
+        let mut values = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Flags are always valid UTF-8, anything else is a positional argument such as a path.
+            let arg = match arg.to_str() {
+                Some(flag) if flag.starts_with('-') && flag != "-" => flag.to_string(),
+                _ => {
+                    positional.push(arg);
+                    continue;
+                }
+            };
+            let arg = match SHORT_FLAGS.iter().find(|(short, _)| *short == arg) {
+                Some((_, long)) => long.to_string(),
+                None => arg,
+            };
+            let known = arg.split_once('=').map_or(arg.as_str(), |(name, _)| name);
+            if !VALUE_FLAGS.contains(&known) && !SWITCH_FLAGS.contains(&known) {
+                return Err(format!("Unknown option: {}, see --help", known));
+            }
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.into())),
+                None if VALUE_FLAGS.contains(&arg.as_str()) => match args.next() {
+                    Some(value) => (arg, Some(value)),
+                    None => return Err(format!("Missing value for {}", arg)),
+                },
+                None => (arg, None),
+            };
+            values.push((name, value));
+        }
+        Ok((positional, Flags { values }))
+    }
+
+    /// Whether the flag was given.
+    fn has(&self, name: &str) -> bool {
+        self.values.iter().any(|(n, _)| n == name)
+    }
+
+    /// The value of the last occurence of the flag, as given by the OS.
+    fn os_value(&self, name: &str) -> Option<&std::ffi::OsStr> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// The values of every occurence of the flag in order, as given by the OS.
+    fn os_values(&self, name: &str) -> Vec<&std::ffi::OsStr> {
+        self.values
+            .iter()
+            .filter(|(n, _)| n == name)
+            .filter_map(|(_, v)| v.as_deref())
+            .collect()
+    }
+
+    /// The values of every occurence of the flag in order, lossily converted to UTF-8.
+    fn values(&self, name: &str) -> Vec<std::borrow::Cow<'_, str>> {
+        self.os_values(name)
+            .into_iter()
+            .map(|v| v.to_string_lossy())
+            .collect()
+    }
+
+    /// The value of the last occurence of the flag, when it is valid UTF-8.
+    fn value(&self, name: &str) -> Option<&str> {
+        self.os_value(name).and_then(|v| v.to_str())
+    }
+
+    /// The value of the last occurence of the flag as a path.
+    fn path(&self, name: &str) -> Option<&Path> {
+        self.os_value(name).map(Path::new)
+    }
+
+    /// The start and end of the part of a binary of `len` bytes selected by `--offset`
+    /// and `--length`, the whole binary per default. An offset past the end is an error.
+    fn range(&self, len: usize) -> Result<(usize, usize), String> {
+        let offset = self.number("--offset", 0)?;
+        if offset > len {
+            return Err(format!(
+                "--offset {} is past the end of the input, which is {} bytes long",
+                offset, len
+            ));
+        }
+        let length = self.number("--length", usize::MAX)?;
+        Ok((offset, offset.saturating_add(length).min(len)))
+    }
+
+    /// The part of the binary selected by `--offset` and `--length`, the whole binary per default.
+    fn slice<'a>(&self, binary: &'a [u8]) -> Result<&'a [u8], String> {
+        let (start, end) = self.range(binary.len())?;
+        Ok(&binary[start..end])
+    }
+
+    /// The value of the flag parsed as a number, or `default` if not given.
+    fn number(&self, name: &str, default: usize) -> Result<usize, String> {
+        match self.value(name) {
+            Some(value) => match value.strip_prefix("0x") {
+                Some(hex) => usize::from_str_radix(hex, 16),
+                None => value.parse::<usize>(),
+            }
+            .map_err(|_| format!("Invalid value for {}: {}", name, value)),
+            None => Ok(default),
+        }
+    }
+}
+
+/// A constant emitted next to the data.
+enum Parameter {
+    Integer(usize),
+    Text(String),
+}
+
+/// The data to embed and what goes with it, once a preset understood the input.
+struct Preset {
+    data: Vec<u8>,
+    /// The constants emitted before the data, as (suffix of the name, value) pairs.
+    parameters: Vec<(&'static str, Parameter)>,
+    /// The alignment the data needs, in bytes.
+    alignment: Option<usize>,
+    /// What --webasset emits with the data, in one struct or table where the language has them.
+    web_asset: Option<WebAsset>,
+}
+
+/// Turns the input into the data to embed and the parameters emitted next to it, for the
+/// presets that understand the input format.
+fn apply_preset(input_file: &Path, binary: &[u8], flags: &Flags) -> Result<Preset, String> {
+    if flags.has("--webasset") {
+        // A strong ETag only has to change with the content, 64 bits of its hash are plenty.
+        let etag = format!("\"{}\"", to_hex_string(&sha256(binary)[..8]));
+        return Ok(Preset {
+            data: gzip(binary),
+            parameters: Vec::new(),
+            alignment: None,
+            web_asset: Some(WebAsset {
+                mime: mime_type(input_file),
+                encoding: "gzip",
+                etag,
+                length: binary.len(),
+            }),
+        });
+    }
+    if flags.has("--wav") || flags.has("--pcm") {
+        let info = parse_wav(binary)?;
+        let data = if flags.has("--pcm") {
+            binary[info.data].to_vec()
+        } else {
+            binary.to_vec()
+        };
+        return Ok(Preset {
+            data,
+            parameters: vec![
+                ("sample_rate", Parameter::Integer(info.sample_rate)),
+                ("channels", Parameter::Integer(info.channels)),
+                ("bits_per_sample", Parameter::Integer(info.bits_per_sample)),
+            ],
+            alignment: None,
+            web_asset: None,
+        });
+    }
+    if flags.has("--font") {
+        if !is_font(binary) {
+            return Err("The input isn't a TTF, OTF, TTC or BDF font".to_string());
+        }
+        let mut parameters = vec![("size", Parameter::Integer(binary.len()))];
+        if let Some(family) = font_family(binary)? {
+            parameters.push(("family", Parameter::Text(family)));
+        }
+        // Font renderers read the tables as 32 bits integers.
+        return Ok(Preset {
+            data: binary.to_vec(),
+            parameters,
+            alignment: Some(4),
+            web_asset: None,
+        });
+    }
+    if !["--image", "--image-format", "--pixels"]
+        .iter()
+        .any(|f| flags.has(f))
+    {
+        return Ok(Preset {
+            data: binary.to_vec(),
+            parameters: Vec::new(),
+            alignment: None,
+            web_asset: None,
+        });
+    }
+    let format = match flags.value("--image-format") {
+        Some(name) => {
+            ImageFormat::from_name(name).ok_or(format!("Unknown image format: {}", name))?
+        }
+        None => ImageFormat::detect(binary)
+            .ok_or("The input isn't a PNG, BMP or GIF image, use --image-format to force one")?,
+    };
+    let info = image_info(binary, format)?;
+    let pixels = match flags.value("--pixels") {
+        Some(name) => {
+            Some(PixelFormat::from_name(name).ok_or(format!("Unknown pixel format: {}", name))?)
+        }
+        None => None,
+    };
+    let (data, bpp) = match pixels {
+        Some(pixels) => (
+            convert_pixels(&decode_image(binary, format)?, info.width, pixels),
+            pixels.bpp(),
+        ),
+        None => (binary.to_vec(), info.bpp),
+    };
+    Ok(Preset {
+        data,
+        parameters: vec![
+            ("width", Parameter::Integer(info.width)),
+            ("height", Parameter::Integer(info.height)),
+            ("bpp", Parameter::Integer(bpp)),
+        ],
+        alignment: None,
+        web_asset: None,
+    })
+}
+
+/// A file to convert.
+struct Input {
+    file: PathBuf,
+    /// The name of its constant.
+    name: String,
+    /// The path --lookup finds it by: the path given, or its path in the directory given.
+    key: String,
+}
+
+/// What converting an input gave.
+struct Conversion {
+    out: String,
+    /// The size of the input file, for --stats.
+    input_size: usize,
+    /// The data of the constants, which --verify decodes back. None for the dumps, the text, the
+    /// base64 strings and the included files.
+    embedded: Option<Vec<u8>>,
+    /// The size of the data of the constant, which --lookup needs. None for the dumps, the text
+    /// and the base64 strings.
+    data_size: Option<usize>,
+    /// The names of the constants it declares, which --replace-in looks for, the constant of the
+    /// data last. Empty for the dumps.
+    names: Vec<String>,
+}
+
+/// Converts an input file with the given constant name and conversion type.
+fn convert(
+    input_file: &Path,
+    output_file: Option<&Path>,
+    const_name: &str,
+    conversion_type: &str,
+    tab_size: usize,
+    flags: &Flags,
+) -> Result<Conversion, Failure> {
+    let binary = input_to_binary(input_file).map_err(|e| {
+        let message = format!("Error while reading {}: {}", input_file.display(), e);
+        Failure::new(EXIT_READ, message)
+    })?;
+    let binary = decode_input(binary, flags)?;
+
+    let charset = match flags.value("--charset") {
+        Some(name) => Charset::from_name(name).ok_or(Failure::new(
+            EXIT_USAGE,
+            format!("Unknown charset: {}", name),
+        ))?,
+        None => Charset::Ascii,
+    };
+
+    // The dumps keep the offsets of the whole file, so they get the binary up to the end of
+    // the range and the offset to start from instead of a slice.
+    let (start, end) = flags
+        .range(binary.len())
+        .map_err(|e| Failure::new(EXIT_USAGE, e))?;
+    let dumped = &binary[..end];
+
+    let out = match conversion_type.to_ascii_lowercase().trim() {
+        "raw" => format!("{:?}", binary),
+        "bin" | "binary" => binary_to_binary(dumped, start, charset),
+        "hex" | "hexadecimal" | "hexa" | "hexa-decimal" | "hexa_decimal" => {
+            match flags.path("--layout") {
+                _ if flags.has("--xxd") && flags.has("--charset") => {
+                    return Err(Failure::new(
+                        EXIT_USAGE,
+                        "xxd only shows ASCII, --charset can't be used with --xxd",
+                    ))
+                }
+                Some(_) if flags.has("--xxd") => {
+                    return Err(Failure::new(
+                        EXIT_USAGE,
+                        "--layout can't be used with --xxd",
+                    ))
+                }
+                Some(layout_file) => {
+                    let layout = std::fs::read_to_string(long_path(layout_file))
+                        .map_err(|e| format!("Error while reading layout file: {}", e))?;
+                    let fields =
+                        parse_layout(&layout).map_err(|e| format!("Invalid layout file: {}", e))?;
+                    binary_to_layout_dump(dumped, start, &fields, charset)
+                }
+                None if flags.has("--xxd") => binary_to_xxd(dumped, start),
+                None => binary_to_hex(dumped, start, charset),
+            }
+        }
+        "octal" | "oct" | "od" => binary_to_octal(dumped, start),
+        "csv" => binary_to_table(dumped, start, ','),
+        "tsv" => binary_to_table(dumped, start, '\t'),
+        "html" | "htm" => binary_to_html(dumped, start, flags.has("--color")),
+        "find" | "search" => {
+            let pattern = parse_pattern(const_name, flags.has("--string"))
+                .map_err(|e| format!("Invalid pattern: {}", e))?;
+            let context = flags.number("--context", 16)?;
+            binary_to_find(dumped, start, &pattern, context, charset)
+        }
+        "hash" | "hashes" | "checksum" | "checksums" => {
+            binary_to_hash_report(flags.slice(&binary)?)
+        }
+        "base64" | "b64" => wrap_text(
+            &to_base64(flags.slice(&binary)?, flags.has("--url-safe")),
+            flags.number("--wrap", 76)?,
+        ),
+        "base32" | "b32" => {
+            let encoded = to_base32(flags.slice(&binary)?);
+            let encoded = match encoding_uppercase(flags, true)? {
+                true => encoded,
+                false => encoded.to_ascii_lowercase(),
+            };
+            wrap_text(&encoded, flags.number("--wrap", 76)?)
+        }
+        "hexstring" | "hex-string" | "plainhex" | "plain-hex" => {
+            let encoded = to_hex_string(flags.slice(&binary)?);
+            let encoded = match encoding_uppercase(flags, false)? {
+                true => encoded.to_ascii_uppercase(),
+                false => encoded,
+            };
+            wrap_text(&encoded, flags.number("--wrap", 76)?)
+        }
+        "ascii85" | "a85" | "base85" => {
+            let encoded = wrap_text(
+                &to_ascii85(flags.slice(&binary)?),
+                flags.number("--wrap", 76)?,
+            );
+            match flags.has("--frame") {
+                // The delimiters go around the lines, ~> can't be split.
+                true => format!("<~{}~>\n", encoded.trim_end()),
+                false => encoded,
+            }
+        }
+        "z85" => wrap_text(&to_z85(flags.slice(&binary)?)?, flags.number("--wrap", 76)?),
+        "uuencode" | "uu" | "uue" => {
+            let mode = match flags.value("--mode") {
+                Some(mode) => u32::from_str_radix(mode, 8)
+                    .ok()
+                    .filter(|mode| *mode <= 0o7777)
+                    .ok_or(format!("Invalid permissions for --mode: {}", mode))?,
+                None => 0o644,
+            };
+            to_uuencode(flags.slice(&binary)?, const_name, mode)
+        }
+        "srec" | "s-record" | "s-records" | "srecord" | "mot" => {
+            let slice = flags.slice(&binary)?;
+            let base_address = flags.number("--base-address", 0)?;
+            let end = base_address.saturating_add(slice.len());
+            let width = match flags.value("--address-width") {
+                Some(name) => SrecWidth::from_name(name).ok_or(format!(
+                    "Unknown address width: {}, expected 16, 24 or 32",
+                    name
+                ))?,
+                None => SrecWidth::fitting(base_address, end),
+            };
+            let last = end.saturating_sub(1).max(base_address);
+            if last > width.max_address() {
+                return Err(Failure::from(format!(
+                    "The data ends at 0x{:x}, past the highest address of the records, 0x{:x}",
+                    last,
+                    width.max_address()
+                )));
+            }
+            binary_to_srec(slice, const_name, base_address, width)
+        }
+        "disasm" | "disassembly" | "disassemble" => {
+            #[cfg(feature = "disasm")]
+            {
+                let slice = flags.slice(&binary)?;
+                let address = flags.number("--address", flags.number("--offset", 0)?)?;
+                disasm::binary_to_disasm(
+                    slice,
+                    flags.value("--arch").unwrap_or("x86_64"),
+                    address as u64,
+                )
+                .map_err(|e| format!("Error while disassembling: {}", e))?
+            }
+            #[cfg(not(feature = "disasm"))]
+            {
+                return Err(Failure::from("bin2const was compiled without the disasm feature, rebuild it with `cargo build --features disasm`".to_string()));
+            }
+        }
+        _ => match Language::from_name(conversion_type) {
+            Some(language) => {
+                return convert_to_language(
+                    input_file,
+                    output_file,
+                    &binary,
+                    language,
+                    const_name,
+                    tab_size,
+                    flags,
+                )
+                .map_err(Failure::from)
+            }
+            None => {
+                return Err(Failure::new(
+                    EXIT_UNKNOWN_FORMAT,
+                    format!("Unknown conversion type: {}", conversion_type),
+                ))
+            }
+        },
+    };
+    Ok(Conversion {
+        out,
+        input_size: binary.len(),
+        embedded: None,
+        data_size: None,
+        names: Vec::new(),
+    })
+}
+
+/// Whether --input-format asks for the input to be decoded rather than taken as raw bytes.
+fn decodes_input(flags: &Flags) -> bool {
+    !matches!(flags.value("--input-format"), None | Some("raw"))
+}
+
+/// The byte given by --fill, 0xff per default like erased flash.
+fn fill_byte(flags: &Flags) -> Result<u8, String> {
+    match flags.number("--fill", 0xff)? {
+        fill if fill <= 0xff => Ok(fill as u8),
+        fill => Err(format!("--fill must be a byte, not {}", fill)),
+    }
+}
+
+/// The most bytes the records of --input-format and the squeezed lines of a hex dump can span.
+fn max_size(flags: &Flags) -> Result<usize, Failure> {
+    // The records are laid out in memory, 16 MiB per default is more than most flash memories.
+    flags
+        .number("--max-size", 16 << 20)
+        .map_err(|e| Failure::new(EXIT_USAGE, e))
+}
+
+/// Decodes the bytes of an input file as the format given by --input-format, raw per default.
+fn decode_input(binary: Vec<u8>, flags: &Flags) -> Result<Vec<u8>, Failure> {
+    let fill = fill_byte(flags).map_err(|e| Failure::new(EXIT_USAGE, e))?;
+    let max_size = max_size(flags)?;
+    let format = flags.value("--input-format").unwrap_or("raw");
+    if format == "raw" {
+        return Ok(binary);
+    }
+    // Only the text formats read the input as text, the raw bytes are never converted.
+    let text = || String::from_utf8_lossy(&binary);
+    let decoded = match format {
+        "ihex" | "intel-hex" | "intelhex" => ihex_to_binary(&text(), fill, max_size)
+            .map_err(|e| format!("Invalid Intel HEX input: {}", e)),
+        "srec" | "s-record" | "s-records" | "mot" => srec_to_binary(&text(), fill, max_size)
+            .map_err(|e| format!("Invalid S-record input: {}", e)),
+        "base64" | "b64" => {
+            from_base64(&text()).map_err(|e| format!("Invalid base64 input: {}", e))
+        }
+        "hexstring" | "hex-string" | "plainhex" | "plain-hex" => {
+            decode_hex_digits(&text()).map_err(|e| format!("Invalid hex string input: {}", e))
+        }
+        other => {
+            return Err(Failure::new(
+                EXIT_UNKNOWN_FORMAT,
+                format!(
+                    "Unknown input format: {}, expected raw, ihex, srec, base64 or hexstring",
+                    other
+                ),
+            ))
+        }
+    };
+    Ok(decoded?)
+}
+
+/// Whether an encoding is written in uppercase, as chosen with `--case upper` or `--case lower`.
+fn encoding_uppercase(flags: &Flags, default: bool) -> Result<bool, String> {
+    match flags.value("--case") {
+        Some(name) => match NameCase::from_name(name) {
+            Some(NameCase::Upper) => Ok(true),
+            Some(NameCase::Lower) => Ok(false),
+            _ => Err(format!(
+                "Unknown case for an encoding: {}, expected upper or lower",
+                name
+            )),
+        },
+        None => Ok(default),
+    }
+}
+
+/// The language tag of the Markdown code block --wrap-markdown puts the output of a conversion
+/// type in, text for the dumps and encodings.
+fn markdown_tag(conversion_type: &str) -> &'static str {
+    match conversion_type.to_ascii_lowercase().trim() {
+        "csv" => "csv",
+        "tsv" => "tsv",
+        "html" | "htm" => "html",
+        "disasm" | "disassembly" | "disassemble" => "asm",
+        name => Language::from_name(name).map_or("text", Language::markdown_tag),
+    }
+}
+
+/// Reads the text of an input back into the bytes it was generated from, given the constant name
+/// and the most bytes it can decode to.
+type Decoder = fn(&str, &str, usize) -> Result<Vec<u8>, String>;
+
+/// The decoder of the decode conversion types, None for the other types.
+fn decoder(conversion_type: &str) -> Option<Decoder> {
+    match conversion_type.to_ascii_lowercase().trim() {
+        "decode-hex" | "decode_hex" | "unhex" => {
+            Some(|text, _, max_size| decode_hexdump(text, max_size))
+        }
+        "decode-array" | "decode_array" | "unarray" => {
+            Some(|text, name, _| decode_initializer(text, name))
+        }
+        _ => None,
+    }
+}
+
+/// The case policy of the constant names, given by --case or the one of the language.
+fn name_case(language: Language, flags: &Flags) -> Result<NameCase, String> {
+    match flags.value("--case") {
+        Some(name) => NameCase::from_name(name).ok_or(format!("Unknown case: {}", name)),
+        None => Ok(language.default_case()),
+    }
+}
+
+/// The identifier a constant is emitted as: its name with the --name-prefix and --name-suffix,
+/// in the case policy, and fixed with --fix-names when the language doesn't allow it, in which
+/// case the warning explaining why is returned too.
+fn constant_identifier(
+    language: Language,
+    const_name: &str,
+    flags: &Flags,
+) -> Result<(String, Option<String>), String> {
+    let name = name_case(language, flags)?.apply(&format!(
+        "{}{}{}",
+        flags.value("--name-prefix").unwrap_or(""),
+        const_name,
+        flags.value("--name-suffix").unwrap_or("")
+    ));
+    match language.validate_name(&name) {
+        Ok(()) => Ok((name, None)),
+        Err(e) if flags.has("--fix-names") => {
+            let fixed = language.fix_name(&name);
+            let warning = format!("{}, using \"{}\" instead", e, fixed);
+            Ok((fixed, Some(warning)))
+        }
+        Err(e) => Err(format!(
+            "Invalid constant name: {}, use --fix-names to fix it automatically",
+            e
+        )),
+    }
+}
+
+/// Converts an input to a constant of a language.
+#[allow(clippy::too_many_arguments)]
+fn convert_to_language(
+    input_file: &Path,
+    output_file: Option<&Path>,
+    binary: &[u8],
+    language: Language,
+    const_name: &str,
+    tab_size: usize,
+    flags: &Flags,
+) -> Result<Conversion, String> {
+    let case = name_case(language, flags)?;
+    let (name, warning) = constant_identifier(language, const_name, flags)?;
+    if let Some(warning) = warning {
+        eprintln!("Warning: {}", warning);
+    }
+
+    // ESP-IDF embeds the file itself with CMake, or takes a plain array.
+    let espidf = match flags.has("--espidf") {
+        true if language != Language::C => return Err("--espidf only works with c".to_string()),
+        true => match flags.value("--espidf").unwrap_or("cmake") {
+            "cmake" if flags.has("--emit-accessor") => {
+                return Err("--emit-accessor needs --espidf=array".to_string())
+            }
+            "cmake" if decodes_input(flags) => {
+                return Err("--input-format needs --espidf=array".to_string())
+            }
+            "cmake" if is_stdin(input_file) => {
+                return Err("stdin can't be embedded by CMake, it needs --espidf=array".to_string())
+            }
+            "cmake" => {
+                return Ok(Conversion {
+                    out: espidf_binary_data(
+                        &input_file.to_string_lossy().replace('\\', "/"),
+                        &case.apply(&format!("{}_start", name)),
+                        &case.apply(&format!("{}_end", name)),
+                        &case.apply(&format!("{}_size", name)),
+                    ),
+                    input_size: binary.len(),
+                    embedded: None,
+                    data_size: None,
+                    names: Vec::new(),
+                })
+            }
+            "array" => true,
+            other => {
+                return Err(format!(
+                    "Unknown --espidf mode: {}, expected cmake or array",
+                    other
+                ))
+            }
+        },
+        false => false,
+    };
+
+    if let Some(text_mode) = ["--text", "--lines"].into_iter().find(|f| flags.has(f)) {
+        for incompatible in ["--emit-tests", "--emit-accessor"] {
+            if flags.has(incompatible) {
+                return Err(format!("{} can't be used with {}", incompatible, text_mode));
+            }
+        }
+        let escape = match flags.value("--escape") {
+            Some(name) => Escape::from_name(name).ok_or(format!("Unknown escaping: {}", name))?,
+            None => Escape::Minimal,
+        };
+        let text = std::str::from_utf8(binary)
+            .map_err(|e| format!("The input isn't valid UTF-8 text: {}", e))?;
+        let out = if text_mode == "--lines" {
+            let lines = text.lines().collect::<Vec<&str>>();
+            language.convert_lines(&lines, &name, tab_size, escape)
+        } else {
+            language.convert_text(text, &name, tab_size, escape)
+        };
+        return match out {
+            Some(out) => Ok(Conversion {
+                out,
+                input_size: binary.len(),
+                embedded: None,
+                data_size: None,
+                names: vec![name],
+            }),
+            None => Err(format!(
+                "{} isn't supported for {}",
+                text_mode,
+                language.display_name()
+            )),
+        };
+    }
+
+    let mut preset = apply_preset(input_file, binary, flags)?;
+    if espidf {
+        preset
+            .parameters
+            .push(("size", Parameter::Integer(preset.data.len())));
+    }
+    // The web asset gathers the data and what serving it needs in one entry, except for the
+    // languages without structs or tables and for base64 strings, which get separate constants.
+    let web_asset_entry = case.apply(&format!("{}_asset", name));
+    let web_asset = match preset.web_asset.take() {
+        Some(asset) => match language.web_asset(&web_asset_entry, &name, &asset, tab_size) {
+            Some(entry) if !flags.has("--base64") => Some(entry),
+            _ => {
+                preset.parameters.extend([
+                    ("mime", Parameter::Text(asset.mime.to_string())),
+                    ("encoding", Parameter::Text(asset.encoding.to_string())),
+                    ("etag", Parameter::Text(asset.etag)),
+                    ("length", Parameter::Integer(asset.length)),
+                ]);
+                None
+            }
+        },
+        None => None,
+    };
+    let alignment = match flags.value("--align") {
+        Some(_) => match flags.number("--align", 1)? {
+            alignment if alignment.is_power_of_two() => Some(alignment),
+            alignment => {
+                return Err(format!(
+                    "The alignment must be a power of two, not {}",
+                    alignment
+                ))
+            }
+        },
+        None => preset.alignment,
+    };
+    let mut data = preset.data;
+    if flags.has("--total-size") {
+        let total_size = flags.number("--total-size", 0)?;
+        let fill = fill_byte(flags)?;
+        if data.len() > total_size {
+            return Err(format!(
+                "The data is {} bytes, more than the --total-size of {} bytes",
+                data.len(),
+                total_size
+            ));
+        }
+        data.resize(total_size, fill);
+    }
+
+    let mut out = String::new();
+    let mut names = Vec::new();
+    for (suffix, value) in &preset.parameters {
+        let parameter = case.apply(&format!("{}_{}", name, suffix));
+        out.push_str(&match value {
+            Parameter::Integer(value) => language.integer_const(&parameter, *value),
+            Parameter::Text(value) => language.string_const(&parameter, value),
+        });
+        names.push(parameter);
+    }
+    if !preset.parameters.is_empty() {
+        out.push('\n');
+    }
+    if flags.has("--base64") {
+        for incompatible in [
+            "--align",
+            "--section",
+            "--readonly",
+            "--emit-tests",
+            "--emit-accessor",
+        ] {
+            if flags.has(incompatible) {
+                return Err(format!("{} can't be used with --base64", incompatible));
+            }
+        }
+        out.push_str(&language.convert_base64(&data, &name, flags.has("--url-safe")));
+        names.push(name);
+        return Ok(Conversion {
+            out,
+            input_size: binary.len(),
+            embedded: None,
+            data_size: None,
+            names,
+        });
+    }
+    let aligned = alignment.and_then(|a| language.convert_aligned(&data, &name, tab_size, a));
+    let array = match aligned {
+        _ if language == Language::TypeScript => {
+            binary_to_typescript_const(&data, &name, tab_size, flags.has("--readonly"))
+        }
+        _ if flags.has("--readonly") => {
+            return Err(format!(
+                "--readonly isn't supported for {}",
+                language.display_name()
+            ))
+        }
+        _ if language == Language::Wat => {
+            binary_to_wat_data(&data, &name, tab_size, flags.number("--address", 0)?)
+        }
+        // include_bytes! is relative to the source file including it.
+        _ if language == Language::RustInclude && is_stdin(input_file) => {
+            return Err("rust-include includes a file, it can't read stdin".to_string())
+        }
+        _ if language == Language::RustInclude => {
+            if data != binary || decodes_input(flags) {
+                return Err(
+                    "rust-include embeds the file as is, it can't be used with options changing \
+                     the data"
+                        .to_string(),
+                );
+            }
+            let directory = match output_file.and_then(Path::parent) {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            binary_to_rust_include(&relative_path(input_file, directory), &name, alignment)
+        }
+        Some(array) => array,
+        // Only forced by --align, presets align when the language can.
+        None if flags.has("--align") => {
+            return Err(format!(
+                "--align isn't supported for {}",
+                language.display_name()
+            ))
+        }
+        None => language.convert(&data, &name, tab_size),
+    };
+    // The imports come before the parameters, Haskell doesn't allow them after a declaration.
+    let array = match array.split_once("\n\n") {
+        Some((imports, rest)) if imports.lines().all(|line| line.starts_with("import ")) => {
+            out.insert_str(0, &format!("{}\n\n", imports));
+            rest.to_string()
+        }
+        _ => array,
+    };
+    match flags.value("--section") {
+        Some(section) => match language.in_section(&array, &name, section, tab_size) {
+            Some(array) => out.push_str(&array),
+            None => {
+                return Err(format!(
+                    "--section isn't supported for {}",
+                    language.display_name()
+                ))
+            }
+        },
+        None => out.push_str(&array),
+    }
+    if let Some(web_asset) = &web_asset {
+        out.push('\n');
+        out.push_str(web_asset);
+        names.push(web_asset_entry);
+    }
+    if flags.has("--emit-accessor") {
+        match language.accessor(&name, data.len(), tab_size) {
+            // The accessor and the constant share a namespace in Rust and C#.
+            _ if language.accessor_name(&name).as_ref() == Some(&name) => {
+                return Err(format!(
+                    "The accessor of {} would have the same name, choose another --case",
+                    name
+                ))
+            }
+            Some(accessor) => {
+                out.push('\n');
+                out.push_str(&accessor);
+            }
+            None => {
+                return Err(format!(
+                    "--emit-accessor isn't supported for {}",
+                    language.display_name()
+                ))
+            }
+        }
+    }
+    if flags.has("--emit-tests") {
+        match language.test(&data, &name, tab_size) {
+            Some(test) => {
+                out.push('\n');
+                out.push_str(&test);
+            }
+            None => {
+                return Err(format!(
+                    "--emit-tests isn't supported for {}",
+                    language.display_name()
+                ))
+            }
+        }
+    }
+    names.extend(language.declared_names(&name));
+    Ok(Conversion {
+        out,
+        input_size: binary.len(),
+        data_size: Some(data.len()),
+        // An included file has no bytes in the output to decode back.
+        embedded: (language != Language::RustInclude).then_some(data),
+        names,
+    })
+}
+
+/// Describes the cost of a conversion, as a line of text or of JSON.
+/// For exemple, for a 3 bytes file converted in 1.5ms the function returns:
+/// abc.bin: 3 bytes -> 52 bytes (17.33x), 3 lines, 1.500ms
+fn conversion_stats(
+    input_file: &Path,
+    conversion: &Conversion,
+    elapsed: std::time::Duration,
+    json: bool,
+) -> String {
+    let output_size = conversion.out.len();
+    let ratio = output_size as f64 / conversion.input_size.max(1) as f64;
+    let lines = conversion.out.lines().count();
+    let milliseconds = elapsed.as_secs_f64() * 1000.0;
+    if json {
+        let input = input_file
+            .to_string_lossy()
+            .chars()
+            .map(|c| match c {
+                '"' | '\\' => format!("\\{}", c),
+                c if c.is_control() => format!("\\u{:04x}", c as u32),
+                c => c.to_string(),
+            })
+            .collect::<String>();
+        format!(
+            "{{\"input\": \"{}\", \"input_size\": {}, \"output_size\": {}, \
+             \"ratio\": {:.2}, \"lines\": {}, \"elapsed_ms\": {:.3}}}",
+            input, conversion.input_size, output_size, ratio, lines, milliseconds
+        )
+    } else {
+        format!(
+            "{}: {} bytes -> {} bytes ({:.2}x), {} lines, {:.3}ms",
+            input_file.display(),
+            conversion.input_size,
+            output_size,
+            ratio,
+            lines,
+            milliseconds
+        )
+    }
+}
+
 fn main() {
-    let args = args().collect::<Vec<String>>();
+    let (args, flags) = match Flags::parse(args_os().collect()) {
+        Ok(parsed) => parsed,
+        Err(e) => fail(EXIT_USAGE, e),
+    };
 
-    if args.len() < 4 {
-        println!("{}", USAGE_DOC);
+    if flags.has("--help") {
+        write_stdout(&[USAGE_DOC.as_bytes(), b"\n"]);
+        return;
+    }
+    if flags.has("--version") {
+        write_stdout(&[format!("bin2const {}\n", env!("CARGO_PKG_VERSION")).as_bytes()]);
         return;
     }
 
-    let input_file = args[1].clone();
-    let output_const_name = args[2].clone();
-    let conversion_type = args[3].clone();
-    let tab_size = if args.len() > 4 {
-        args[4].parse::<usize>().unwrap_or(4)
-    } else {
-        4
+    if args.len() == 2 && args[1] == "selftest" {
+        std::process::exit(if selftest::selftest() { 0 } else { 1 });
+    }
+    let command = match args.get(1) {
+        Some(command) if command == "verify" => verify_command(&args[2..], &flags),
+        _ => convert_command(&args, &flags),
     };
-    let output_file = if args.len() > 5 {
-        Some(args[5].clone())
+    if let Err(failure) = command {
+        fail(failure.code, failure.message);
+    }
+}
+
+/// What to convert and how, from the positional arguments and the named flags.
+struct Arguments {
+    inputs: Vec<Input>,
+    conversion_type: String,
+    tab_size: usize,
+    /// The output file, or the file updated in place by --replace-in, None for stdout.
+    output_file: Option<PathBuf>,
+}
+
+/// Runs `bin2const <input_file> <output_const_name> <conversion_type> [tab_size] [output_file]`,
+/// converting every input and writing their constants together.
+fn convert_command(args: &[OsString], flags: &Flags) -> Result<(), Failure> {
+    let arguments = parse_arguments(args, flags)?;
+    let output_file = arguments.output_file.as_deref();
+    if let Some(decoder) = decoder(&arguments.conversion_type) {
+        return decode_inputs(&arguments.inputs, decoder, output_file, flags);
+    }
+
+    let line_ending = line_ending(flags)?;
+    let mut rendered = render_output(&arguments, flags)?;
+    let line_ending = update_output(&mut rendered, output_file, line_ending, flags)?;
+    let out = if line_ending == "\n" {
+        rendered.out
     } else {
-        None
+        rendered.out.replace('\n', line_ending)
     };
 
-    let binary = match file_to_binary(&input_file) {
-        Ok(binary) => binary,
-        Err(e) => {
-            println!("Error while reading file: {}", e);
-            return;
+    // The options are checked before anything is written.
+    if flags.has("--verify") && rendered.embedded.is_none() {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "--verify only works with the constant conversion types",
+        ));
+    }
+    if flags.has("--check") {
+        return check_output(&out, output_file);
+    }
+    if let Some(script_file) = flags.path("--linker-script") {
+        let data = rendered.embedded.as_deref();
+        write_linker_script(script_file, data, line_ending, arguments.tab_size, flags)?;
+    }
+    // xxd doesn't end its dump with an empty line.
+    let end = if flags.has("--xxd") { "" } else { line_ending };
+    write_output(out.as_bytes(), end, output_file, flags)?;
+
+    if let (true, Some(binary)) = (flags.has("--verify"), &rendered.embedded) {
+        // Reads back what actually landed on the disk rather than what was meant to be written.
+        let generated = match output_file {
+            Some(output_file) => match file_to_binary(output_file) {
+                Ok(generated) => String::from_utf8_lossy(&generated).to_string(),
+                Err(e) => {
+                    let message =
+                        format!("Error while reading back {}: {}", output_file.display(), e);
+                    return Err(Failure::new(EXIT_READ, message));
+                }
+            },
+            None => out,
+        };
+        let language = Language::from_name(&arguments.conversion_type);
+        verify_generated(binary, &generated, language)?;
+    }
+    Ok(())
+}
+
+/// Reads the positional arguments, <input_file> <output_const_name> <conversion_type>
+/// [tab_size] [output_file], the named flags taking the place of theirs and the others keeping
+/// their order, then collects and sorts the inputs.
+fn parse_arguments(args: &[OsString], flags: &Flags) -> Result<Arguments, Failure> {
+    let mut positional = args[1..].iter();
+    // Without an input file, the input is read from stdin, like with `-`.
+    let input_files = match flags.os_values("--input") {
+        files if files.is_empty() => match positional.next() {
+            Some(file) => vec![file.as_os_str()],
+            None if flags.has("--name") => vec![std::ffi::OsStr::new("-")],
+            None => Vec::new(),
+        },
+        files => files,
+    };
+    let const_names = match flags.values("--name") {
+        // The repeated --input without names are named after their files.
+        names if names.is_empty() && flags.has("--input") => input_files
+            .iter()
+            .map(|file| match name_from_file(Path::new(file)) {
+                // The files matched by a glob are named after their paths only.
+                _ if is_glob(Path::new(file)) => Ok(std::borrow::Cow::Borrowed("")),
+                Some(name) => Ok(std::borrow::Cow::Owned(name)),
+                None => Err(Failure::new(
+                    EXIT_USAGE,
+                    "stdin has no file name, - needs a --name",
+                )),
+            })
+            .collect::<Result<_, Failure>>()?,
+        names if names.is_empty() => positional
+            .next()
+            .map(|name| name.to_string_lossy())
+            .into_iter()
+            .collect(),
+        names => names,
+    };
+    let conversion_type = match flags.value("--format") {
+        Some(format) => Some(format.to_string()),
+        None => positional.next().map(|t| t.to_string_lossy().to_string()),
+    };
+    let Some(conversion_type) = conversion_type.filter(|_| !input_files.is_empty()) else {
+        return Err(Failure::new(EXIT_USAGE, USAGE_DOC));
+    };
+    if input_files.len() != const_names.len() {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "Each --input needs a --name, or none to be named after its file",
+        ));
+    }
+    // The positional tab size can be left out before the output file.
+    let tab_size = match flags.number("--tab-size", 4) {
+        Ok(tab_size) if flags.has("--tab-size") => tab_size,
+        Ok(_) => match positional
+            .as_slice()
+            .first()
+            .and_then(|t| t.to_str()?.parse().ok())
+        {
+            Some(tab_size) => {
+                positional.next();
+                tab_size
+            }
+            None => 4,
+        },
+        Err(e) => return Err(Failure::new(EXIT_USAGE, e)),
+    };
+    let output_file = match flags.path("--output") {
+        Some(output_file) => Some(output_file.to_path_buf()),
+        None => positional.next().map(PathBuf::from),
+    };
+    if let Some(unexpected) = positional.next() {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            format!("Unexpected argument: {}", unexpected.to_string_lossy()),
+        ));
+    }
+    let output_file = match flags.path("--replace-in") {
+        Some(_) if output_file.is_some() => {
+            return Err(Failure::new(
+                EXIT_USAGE,
+                "--replace-in updates its file in place, it doesn't need an output file",
+            ))
         }
+        Some(file) => Some(file.to_path_buf()),
+        None => output_file,
     };
+    let inputs = input_files
+        .iter()
+        .zip(&const_names)
+        .map(|(file, name)| Input {
+            file: PathBuf::from(file),
+            name: name.to_string(),
+            key: file.to_string_lossy().replace('\\', "/"),
+        })
+        .collect::<Vec<Input>>();
+    if inputs.iter().filter(|input| is_stdin(&input.file)).count() > 1 {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "stdin can only be read once, - can't be given twice",
+        ));
+    }
 
-    let out = match conversion_type.to_ascii_lowercase().trim() {
-        "raw" => format!("{:?}", binary),
-        "bin" | "binary" => binary_to_binary(&binary),
-        "hex" | "hexadecimal" | "hexa" | "hexa-decimal" | "hexa_decimal" => binary_to_hex(&binary),
-        "c" | "cpp" | "c++" | "cxx" | "h" | "hpp" | "h++" | "hxx" => {
-            binary_to_c_const(&binary, &output_const_name, tab_size)
+    let mut inputs = collect_inputs(inputs, flags)?;
+    sort_inputs(&mut inputs, &conversion_type, flags)?;
+    Ok(Arguments {
+        inputs,
+        conversion_type,
+        tab_size,
+        output_file,
+    })
+}
+
+/// The filter of the directories and glob patterns given as inputs, from --include, --exclude,
+/// --hidden, --respect-gitignore and --max-depth.
+fn directory_filter(flags: &Flags) -> Result<DirectoryFilter, Failure> {
+    let globs = |name: &str| {
+        flags
+            .values(name)
+            .iter()
+            .map(|g| g.to_string())
+            .collect::<Vec<String>>()
+    };
+    Ok(DirectoryFilter {
+        include: globs("--include"),
+        exclude: globs("--exclude"),
+        hidden: flags.has("--hidden"),
+        gitignore: flags.has("--respect-gitignore"),
+        max_depth: match flags.number("--max-depth", 0) {
+            Ok(0) if flags.has("--max-depth") => {
+                return Err(Failure::new(EXIT_USAGE, "--max-depth must be at least 1"))
+            }
+            Ok(0) => None,
+            Ok(max_depth) => Some(max_depth),
+            Err(e) => return Err(Failure::new(EXIT_USAGE, e)),
+        },
+    })
+}
+
+/// Replaces the directories and the glob patterns given as inputs by their files, named after
+/// their paths, and keeps the other inputs as they are.
+fn collect_inputs(inputs: Vec<Input>, flags: &Flags) -> Result<Vec<Input>, Failure> {
+    let filter = directory_filter(flags)?;
+    let mut files = Vec::new();
+    for input in inputs {
+        let glob = is_glob(&input.file);
+        let listed = if glob {
+            expand_glob(&input.file.to_string_lossy(), &filter)
+        } else if long_path(&input.file).is_dir() {
+            list_directory(&input.file, &filter).map(|listed| {
+                listed
+                    .into_iter()
+                    .map(|(file, path)| (input.file.join(file), path))
+                    .collect()
+            })
+        } else {
+            files.push(input);
+            continue;
+        };
+        let listed = listed.map_err(|e| {
+            let message = format!("Error while reading {}: {}", input.file.display(), e);
+            Failure::new(EXIT_READ, message)
+        })?;
+        if glob && listed.is_empty() {
+            let message = format!("{} matches no file", input.file.display());
+            return Err(Failure::new(EXIT_READ, message));
+        }
+        files.extend(listed.into_iter().map(|(file, path)| {
+            let path_name = path.replace(|c: char| !c.is_alphanumeric(), "_");
+            Input {
+                file,
+                name: match input.name.is_empty() {
+                    true => path_name,
+                    false => format!("{}_{}", input.name, path_name),
+                },
+                key: path,
+            }
+        }));
+    }
+    Ok(files)
+}
+
+/// Checks that no two inputs declare the same constant, and sorts them as --sort asks.
+fn sort_inputs(inputs: &mut [Input], conversion_type: &str, flags: &Flags) -> Result<(), Failure> {
+    // The names are compared as they are emitted, after the case policy and --fix-names, so
+    // key and KEY, or my-key and my_key, can't declare the same constant twice. The invalid
+    // names are reported by their conversion.
+    let language = Language::from_name(conversion_type);
+    let identifier = |input: &Input| match language {
+        Some(language) => constant_identifier(language, &input.name, flags)
+            .map_or(input.name.clone(), |(identifier, _)| identifier),
+        None => input.name.clone(),
+    };
+    let identifiers = inputs.iter().map(identifier).collect::<Vec<String>>();
+    for (i, input) in inputs.iter().enumerate() {
+        let Some(previous) = (0..i).find(|&previous| identifiers[previous] == identifiers[i])
+        else {
+            continue;
+        };
+        let message = match &inputs[previous].name {
+            name if *name == input.name => {
+                format!("The constant name {} is given twice", input.name)
+            }
+            name => format!(
+                "The constant names {} and {} are both emitted as {}",
+                name, input.name, identifiers[i]
+            ),
+        };
+        return Err(Failure::new(EXIT_USAGE, message));
+    }
+
+    // The names are compared byte by byte as they are emitted, so the order doesn't depend on
+    // the locale, and ties keep their order, so regenerated files only change where their inputs
+    // did. The explicit --input/--name pairs keep their order per default.
+    let metadata = |input: &Input| std::fs::metadata(long_path(&input.file)).ok();
+    let sort = match flags.has("--input") && flags.has("--name") {
+        true => "manifest-order",
+        false => "name",
+    };
+    match flags.value("--sort").unwrap_or(sort) {
+        "name" => inputs.sort_by_cached_key(identifier),
+        "size" => {
+            inputs.sort_by_cached_key(|input| (metadata(input).map(|m| m.len()), identifier(input)))
+        }
+        "mtime" => inputs.sort_by_cached_key(|input| {
+            (
+                metadata(input).and_then(|m| m.modified().ok()),
+                identifier(input),
+            )
+        }),
+        "manifest-order" => (),
+        other => {
+            return Err(Failure::new(
+                EXIT_USAGE,
+                format!(
+                    "Unknown sort: {}, expected name, size, mtime or manifest-order",
+                    other
+                ),
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Runs the decode conversion types, which turn text back into the bytes it was generated from,
+/// written as is.
+fn decode_inputs(
+    inputs: &[Input],
+    decoder: Decoder,
+    output_file: Option<&Path>,
+    flags: &Flags,
+) -> Result<(), Failure> {
+    let max_size = max_size(flags)?;
+    let mut decoded = Vec::new();
+    for input in inputs {
+        let text = input_to_binary(&input.file).map_err(|e| {
+            let message = format!("Error while reading {}: {}", input.file.display(), e);
+            Failure::new(EXIT_READ, message)
+        })?;
+        let bytes = decoder(&String::from_utf8_lossy(&text), &input.name, max_size)
+            .map_err(|e| format!("{} can't be decoded: {}", input.file.display(), e))?;
+        decoded.extend(bytes);
+    }
+    write_output(&decoded, "", output_file, flags)
+}
+
+/// The line ending of the output, from --line-ending or --crlf.
+fn line_ending(flags: &Flags) -> Result<&'static str, Failure> {
+    match flags.value("--line-ending") {
+        _ if flags.has("--crlf") => Ok("\r\n"),
+        None | Some("lf") => Ok("\n"),
+        Some("crlf") => Ok("\r\n"),
+        Some("native") if cfg!(windows) => Ok("\r\n"),
+        Some("native") => Ok("\n"),
+        Some(other) => Err(Failure::new(
+            EXIT_USAGE,
+            format!(
+                "Unknown line ending: {}, expected lf, crlf or native",
+                other
+            ),
+        )),
+    }
+}
+
+/// The output of the inputs converted together.
+struct Rendered {
+    out: String,
+    /// The constant name, the declared names and the output of every input, which --region and
+    /// --replace-in put in place.
+    generated: Vec<(String, Vec<String>, String)>,
+    /// The data of all the constants, None when an input has none, see Conversion.
+    embedded: Option<Vec<u8>>,
+}
+
+/// Converts every input in order and emits their constants together, followed by the --lookup
+/// function, in the --class or --package, and fenced by --wrap-markdown.
+fn render_output(arguments: &Arguments, flags: &Flags) -> Result<Rendered, Failure> {
+    let Arguments {
+        inputs,
+        conversion_type,
+        tab_size,
+        output_file,
+    } = arguments;
+    let usage = |message: String| Failure::new(EXIT_USAGE, message);
+    let mut out = String::new();
+    let mut generated = Vec::new();
+    let mut embedded = Some(Vec::new());
+    let mut lookup = Vec::new();
+    for input in inputs {
+        let started = std::time::Instant::now();
+        let conversion = convert(
+            &input.file,
+            output_file.as_deref(),
+            &input.name,
+            conversion_type,
+            *tab_size,
+            flags,
+        )?;
+        if flags.has("--stats") {
+            let json = flags.value("--stats") == Some("json");
+            let stats = conversion_stats(&input.file, &conversion, started.elapsed(), json);
+            eprintln!("{}", stats);
         }
-        "cdef" | "c-def" | "c_def" | "def" | "define" | "cppdef" => {
-            binary_to_c_define(&binary, &output_const_name, tab_size)
+        if !out.is_empty() {
+            out.push('\n');
         }
-        "rust" | "rs" | "rustlang" | "rust-lang" => {
-            binary_to_rust_const(&binary, &output_const_name, tab_size)
+        out.push_str(&conversion.out);
+        if let (Some(size), Some(name)) = (conversion.data_size, conversion.names.last()) {
+            lookup.push((input.key.as_str(), name.clone(), size));
         }
-        "csharp" | "cs" | "c#" | "c-sharp" | "c_sharp" => {
-            binary_to_csharp_const(&binary, &output_const_name, tab_size)
+        generated.push((input.name.clone(), conversion.names, conversion.out));
+        embedded = match (embedded, conversion.embedded) {
+            (Some(mut all), Some(data)) => {
+                all.extend_from_slice(&data);
+                Some(all)
+            }
+            _ => None,
+        };
+    }
+
+    // --lookup adds a function finding the constants by the path of their file.
+    let language = Language::from_name(conversion_type);
+    if let Some(function) = flags.value("--lookup") {
+        if lookup.len() != inputs.len() {
+            return Err(usage(
+                "--lookup only works with the constant conversion types".to_string(),
+            ));
         }
-        "python" | "py" | "python3" | "py3" | "python_3" => {
-            binary_to_python_const(&binary, &output_const_name, tab_size)
+        lookup.sort_by(|a, b| a.0.cmp(b.0));
+        if let Some(twice) = lookup.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(usage(format!("The path {} is given twice", twice[0].0)));
         }
-        "javascript" | "js" | "typescript" | "ts" => {
-            binary_to_javascript_const(&binary, &output_const_name, tab_size)
+        match language.and_then(|language| language.lookup(&lookup, function, *tab_size)) {
+            Some(function) => {
+                out.push('\n');
+                out.push_str(&function);
+            }
+            None => return Err(usage("--lookup only works with c and rust".to_string())),
         }
-        "go" | "golang" | "go-lang" | "go_lang" => {
-            binary_to_go_const(&binary, &output_const_name, tab_size)
+    }
+
+    // The type of the --webasset entries is declared once, before them.
+    if flags.has("--webasset") && !flags.has("--base64") {
+        if let Some(declaration) = language.and_then(|language| language.web_asset_type(*tab_size))
+        {
+            out = format!("{}\n{}", declaration, out);
         }
-        "java" | "jvm" | "jre" | "jre8" | "jre-8" | "jre_8" | "jre11" | "jre-11" | "jre_11" => {
-            binary_to_java_const(&binary, &output_const_name, tab_size)
+    }
+
+    // --class and --package make the output a whole source file.
+    if let Some(class) = flags.value("--class") {
+        match language {
+            Some(language) => out = language.in_class(&out, class, *tab_size).map_err(usage)?,
+            None => return Err(usage("--class only works with java".to_string())),
         }
-        _ => {
-            println!("Unknown conversion type: {}", conversion_type);
-            return;
+    }
+    if let Some(package) = flags.value("--package") {
+        match language {
+            Some(language) => out = language.in_package(&out, package).map_err(usage)?,
+            None => return Err(usage("--package only works with go and java".to_string())),
+        }
+    }
+
+    // --wrap-markdown puts the output in a fenced code block, to paste in docs and issues.
+    if flags.has("--wrap-markdown") {
+        for incompatible in ["--region", "--replace-in", "--verify"] {
+            if flags.has(incompatible) {
+                let message = format!("{} can't be used with --wrap-markdown", incompatible);
+                return Err(usage(message));
+            }
+        }
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        // The fence must be longer than any run of backquotes in the output, such as text
+        // embedded with --text.
+        let mut fence = "```".to_string();
+        while out.contains(&fence) {
+            fence.push('`');
+        }
+        let tag = markdown_tag(conversion_type);
+        out = format!("{}{}\n{}{}\n", fence, tag, out, fence);
+    }
+
+    Ok(Rendered {
+        out,
+        generated,
+        embedded,
+    })
+}
+
+/// Puts the output in place in the existing output file for --region and --replace-in, rather
+/// than replacing the file. Returns the line ending to write with, the one of the file unless
+/// another is asked for.
+fn update_output(
+    rendered: &mut Rendered,
+    output_file: Option<&Path>,
+    line_ending: &'static str,
+    flags: &Flags,
+) -> Result<&'static str, Failure> {
+    let update = match (flags.has("--region"), flags.path("--replace-in")) {
+        (true, Some(_)) => {
+            return Err(Failure::new(
+                EXIT_USAGE,
+                "--region can't be used with --replace-in",
+            ))
+        }
+        (true, None) => "--region",
+        (false, Some(_)) => "--replace-in",
+        (false, None) => return Ok(line_ending),
+    };
+    // The tests of a constant are only generated with it, so only --region can update them,
+    // but the package is declared once at the top of the file.
+    for incompatible in [
+        "--verify",
+        "--emit-tests",
+        "--emit-accessor",
+        "--package",
+        "--class",
+    ] {
+        let always = matches!(incompatible, "--verify" | "--package" | "--class");
+        if flags.has(incompatible) && (always || update == "--replace-in") {
+            return Err(Failure::new(
+                EXIT_USAGE,
+                format!("{} can't be used with {}", incompatible, update),
+            ));
+        }
+    }
+    let Some(output_file) = output_file else {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "--region needs an output file to update",
+        ));
+    };
+    let existing = match file_to_binary(output_file) {
+        Ok(existing) => String::from_utf8_lossy(&existing).to_string(),
+        Err(e) => {
+            let message = format!("Error while reading {}: {}", output_file.display(), e);
+            return Err(Failure::new(EXIT_READ, message));
+        }
+    };
+    // Keeps the line endings of the file unless others are asked for.
+    let line_ending =
+        match !flags.has("--line-ending") && !flags.has("--crlf") && existing.contains("\r\n") {
+            true => "\r\n",
+            false => line_ending,
+        };
+    let mut out = existing.replace("\r\n", "\n");
+    for (const_name, names, generated) in &rendered.generated {
+        let updated = if update == "--region" {
+            replace_region(&out, const_name, generated)
+        } else if names.is_empty() {
+            Err("--replace-in only works with the constant conversion types".to_string())
+        } else {
+            names
+                .iter()
+                .try_fold(out, |out, name| replace_constant(&out, name, generated))
+        };
+        out = updated.map_err(|e| format!("Can't update {}: {}", output_file.display(), e))?;
+    }
+    rendered.out = out;
+    Ok(line_ending)
+}
+
+/// Compares the output with the existing output file for --check, failing with a summary of
+/// the difference when it is out of date.
+fn check_output(out: &str, output_file: Option<&Path>) -> Result<(), Failure> {
+    let Some(output_file) = output_file else {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "--check needs an output file to compare to",
+        ));
+    };
+    let existing = match file_to_binary(output_file) {
+        Ok(existing) => String::from_utf8_lossy(&existing).to_string(),
+        Err(e) => {
+            let message = format!("{} can't be checked: {}", output_file.display(), e);
+            return Err(Failure::new(EXIT_READ, message));
         }
     };
+    match diff_summary(&existing, out) {
+        Some(summary) => Err(Failure::new(
+            EXIT_FAILURE,
+            format!(
+                "{} is out of date:\n{}",
+                output_file.display(),
+                summary.trim_end()
+            ),
+        )),
+        None => {
+            eprintln!("{} is up to date", output_file.display());
+            Ok(())
+        }
+    }
+}
 
+/// Writes the --linker-script placing the data of the constants in its --section at its
+/// --address.
+fn write_linker_script(
+    script_file: &Path,
+    data: Option<&[u8]>,
+    line_ending: &str,
+    tab_size: usize,
+    flags: &Flags,
+) -> Result<(), Failure> {
+    let (Some(section), Some(_)) = (flags.value("--section"), flags.value("--address")) else {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "--linker-script needs the --section and --address to place the data at",
+        ));
+    };
+    let Some(data) = data else {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "--linker-script only works with the constant conversion types",
+        ));
+    };
+    let address = flags
+        .number("--address", 0)
+        .map_err(|e| Failure::new(EXIT_USAGE, e))?;
+    let script = linker_script(section, address, data.len(), tab_size).replace('\n', line_ending);
+    write_output(script.as_bytes(), "", Some(script_file), flags)
+}
+
+/// Writes the output to the output file, or to stdout followed by `end`.
+fn write_output(
+    out: &[u8],
+    end: &str,
+    output_file: Option<&Path>,
+    flags: &Flags,
+) -> Result<(), Failure> {
     match output_file {
-        Some(output_file) => match std::fs::write(output_file, out) {
-            Ok(_) => (),
-            Err(e) => {
-                println!("Error while writing to file: {}", e);
-                return;
-            }
-        },
-        None => println!("{}", out),
+        Some(output_file) => write_atomic(output_file, out, flags.has("--fsync")).map_err(|e| {
+            let message = format!("Error while writing to {}: {}", output_file.display(), e);
+            Failure::new(EXIT_WRITE, message)
+        }),
+        None => {
+            write_stdout(&[out, end.as_bytes()]);
+            Ok(())
+        }
+    }
+}
+
+/// Decodes the array of a generated source file, as its language or any hex array, and compares
+/// it byte for byte and by SHA-256 with the input, printing a summary to stderr.
+fn verify_generated(
+    binary: &[u8],
+    generated: &str,
+    language: Option<Language>,
+) -> Result<(), String> {
+    let decoded = match language {
+        Some(language) => language.decode(generated),
+        None => decode_array(generated),
+    }
+    .map_err(|e| {
+        format!(
+            "Verification failed, the generated array can't be decoded: {}",
+            e
+        )
+    })?;
+    if decoded.is_empty() && !binary.is_empty() {
+        return Err(
+            "Verification failed, no byte array found, pass the conversion type".to_string(),
+        );
+    }
+    let (expected_hash, decoded_hash) = (sha256(binary), sha256(&decoded));
+    if decoded != binary || decoded_hash != expected_hash {
+        let first_difference = decoded
+            .iter()
+            .zip(binary)
+            .position(|(a, b)| a != b)
+            .unwrap_or(decoded.len().min(binary.len()));
+        return Err(format!(
+            "Verification failed, the generated array differs from the input from offset {}\n\
+             input:     {} bytes, sha256 {}\n\
+             generated: {} bytes, sha256 {}",
+            first_difference,
+            binary.len(),
+            to_hex_string(&expected_hash),
+            decoded.len(),
+            to_hex_string(&decoded_hash)
+        ));
     }
+    eprintln!(
+        "Verified {} bytes, sha256 {}",
+        decoded.len(),
+        to_hex_string(&decoded_hash)
+    );
+    Ok(())
+}
+
+/// Runs `bin2const verify <input_file> <source_file> [conversion_type]`, checking that a
+/// previously generated source file still holds the input.
+fn verify_command(args: &[OsString], flags: &Flags) -> Result<(), Failure> {
+    let (input_file, source_file) = match args {
+        [input_file, source_file] | [input_file, source_file, _] => {
+            (Path::new(input_file), Path::new(source_file))
+        }
+        _ => {
+            return Err(Failure::new(
+                EXIT_USAGE,
+                "Usage: bin2const verify <input_file> <source_file> [conversion_type]",
+            ))
+        }
+    };
+    let conversion_type = args
+        .get(2)
+        .map_or(String::new(), |t| t.to_string_lossy().to_string());
+    if !conversion_type.is_empty() && Language::from_name(&conversion_type).is_none() {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            format!(
+                "verify only works with the constant conversion types, not {}",
+                conversion_type
+            ),
+        ));
+    }
+    let read = |file: &Path| {
+        input_to_binary(file).map_err(|e| {
+            let message = format!("Error while reading {}: {}", file.display(), e);
+            Failure::new(EXIT_READ, message)
+        })
+    };
+    // Without a conversion type, the language is guessed from the extension of the source file.
+    let language = match conversion_type.is_empty() {
+        true => source_file
+            .extension()
+            .and_then(|extension| Language::from_extension(&extension.to_string_lossy())),
+        false => Language::from_name(&conversion_type),
+    };
+    let binary = decode_input(read(input_file)?, flags)?;
+    let generated = read(source_file)?;
+    Ok(verify_generated(
+        &binary,
+        &String::from_utf8_lossy(&generated),
+        language,
+    )?)
 }
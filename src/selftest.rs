@@ -0,0 +1,315 @@
+use crate::library::diff_summary;
+use crate::{convert, Flags};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// The bytes 0x00 to 0x13 then 0x7f, 0x80 and 0xff, the input of tests/golden.rs.
+const GOLDEN_INPUT: &[u8] = &[
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    0x10, 0x11, 0x12, 0x13, 0x7f, 0x80, 0xff,
+];
+
+/// A reference conversion: its input, the arguments given after the input file, and the
+/// expected output, which is the golden file of the tests.
+struct Vector {
+    golden: &'static str,
+    input: &'static [u8],
+    args: &'static [&'static str],
+    expected: &'static str,
+}
+
+macro_rules! vector {
+    ($golden:literal, $input:expr, $args:expr) => {
+        Vector {
+            golden: $golden,
+            input: $input,
+            args: $args,
+            expected: include_str!(concat!("../tests/golden/", $golden)),
+        }
+    };
+}
+
+/// Every emitter, on the golden input then on an empty one.
+const VECTORS: &[Vector] = &[
+    vector!("c.txt", GOLDEN_INPUT, &["test_txt", "c"]),
+    vector!("cdef.txt", GOLDEN_INPUT, &["test_txt", "cdef"]),
+    vector!("rust.txt", GOLDEN_INPUT, &["test_txt", "rust"]),
+    vector!("csharp.txt", GOLDEN_INPUT, &["test_txt", "csharp"]),
+    vector!("python.txt", GOLDEN_INPUT, &["test_txt", "python"]),
+    vector!("javascript.txt", GOLDEN_INPUT, &["test_txt", "javascript"]),
+    vector!("go.txt", GOLDEN_INPUT, &["test_txt", "go"]),
+    vector!("java.txt", GOLDEN_INPUT, &["test_txt", "java"]),
+    vector!("ca65.txt", GOLDEN_INPUT, &["test_txt", "ca65"]),
+    vector!("acme.txt", GOLDEN_INPUT, &["test_txt", "acme"]),
+    vector!("z80asm.txt", GOLDEN_INPUT, &["test_txt", "z80asm"]),
+    vector!("zig.txt", GOLDEN_INPUT, &["test_txt", "zig"]),
+    vector!("swift.txt", GOLDEN_INPUT, &["test_txt", "swift"]),
+    vector!("swift_data.txt", GOLDEN_INPUT, &["test_txt", "swift_data"]),
+    vector!("kotlin.txt", GOLDEN_INPUT, &["test_txt", "kotlin"]),
+    vector!("nim.txt", GOLDEN_INPUT, &["test_txt", "nim"]),
+    vector!("d.txt", GOLDEN_INPUT, &["test_txt", "d"]),
+    vector!("pascal.txt", GOLDEN_INPUT, &["test_txt", "pascal"]),
+    vector!("ada.txt", GOLDEN_INPUT, &["test_txt", "ada"]),
+    vector!("nasm.txt", GOLDEN_INPUT, &["test_txt", "nasm"]),
+    vector!("gas.txt", GOLDEN_INPUT, &["test_txt", "gas"]),
+    vector!("lua.txt", GOLDEN_INPUT, &["test_txt", "lua"]),
+    vector!("lua_table.txt", GOLDEN_INPUT, &["test_txt", "lua_table"]),
+    vector!("php.txt", GOLDEN_INPUT, &["test_txt", "php"]),
+    vector!("php_array.txt", GOLDEN_INPUT, &["test_txt", "php_array"]),
+    vector!("ruby.txt", GOLDEN_INPUT, &["test_txt", "ruby"]),
+    vector!(
+        "ruby_string.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "ruby_string"]
+    ),
+    vector!("perl.txt", GOLDEN_INPUT, &["test_txt", "perl"]),
+    vector!("haskell.txt", GOLDEN_INPUT, &["test_txt", "haskell"]),
+    vector!("ocaml.txt", GOLDEN_INPUT, &["test_txt", "ocaml"]),
+    vector!("elixir.txt", GOLDEN_INPUT, &["test_txt", "elixir"]),
+    vector!("erlang.txt", GOLDEN_INPUT, &["test_txt", "erlang"]),
+    vector!("scala.txt", GOLDEN_INPUT, &["test_txt", "scala"]),
+    vector!("dart.txt", GOLDEN_INPUT, &["test_txt", "dart"]),
+    vector!("julia.txt", GOLDEN_INPUT, &["test_txt", "julia"]),
+    vector!("r.txt", GOLDEN_INPUT, &["test_txt", "r"]),
+    vector!("matlab.txt", GOLDEN_INPUT, &["test_txt", "matlab"]),
+    vector!("objc.txt", GOLDEN_INPUT, &["test_txt", "objc"]),
+    vector!("verilog.txt", GOLDEN_INPUT, &["test_txt", "verilog"]),
+    vector!("vhdl.txt", GOLDEN_INPUT, &["test_txt", "vhdl"]),
+    vector!("arduino.txt", GOLDEN_INPUT, &["test_txt", "arduino"]),
+    vector!("powershell.txt", GOLDEN_INPUT, &["test_txt", "powershell"]),
+    vector!("bash.txt", GOLDEN_INPUT, &["test_txt", "bash"]),
+    vector!("sh.txt", GOLDEN_INPUT, &["test_txt", "sh"]),
+    vector!("sql.txt", GOLDEN_INPUT, &["test_txt", "sql"]),
+    vector!("postgres.txt", GOLDEN_INPUT, &["test_txt", "postgres"]),
+    vector!("vbnet.txt", GOLDEN_INPUT, &["test_txt", "vbnet"]),
+    vector!("gdscript.txt", GOLDEN_INPUT, &["test_txt", "gdscript"]),
+    vector!("gdscript3.txt", GOLDEN_INPUT, &["test_txt", "gdscript3"]),
+    vector!("fsharp.txt", GOLDEN_INPUT, &["test_txt", "fsharp"]),
+    vector!("fortran.txt", GOLDEN_INPUT, &["test_txt", "fortran"]),
+    vector!("crystal.txt", GOLDEN_INPUT, &["test_txt", "crystal"]),
+    vector!("tcl.txt", GOLDEN_INPUT, &["test_txt", "tcl"]),
+    vector!("lisp.txt", GOLDEN_INPUT, &["test_txt", "lisp"]),
+    vector!("scheme.txt", GOLDEN_INPUT, &["test_txt", "scheme"]),
+    vector!("wat.txt", GOLDEN_INPUT, &["test_txt", "wat"]),
+    vector!("typescript.txt", GOLDEN_INPUT, &["test_txt", "typescript"]),
+    vector!("groovy.txt", GOLDEN_INPUT, &["test_txt", "groovy"]),
+    vector!("solidity.txt", GOLDEN_INPUT, &["test_txt", "solidity"]),
+    vector!("c_string.txt", GOLDEN_INPUT, &["test_txt", "c_string"]),
+    vector!("rust_bytes.txt", GOLDEN_INPUT, &["test_txt", "rust_bytes"]),
+    vector!("yaml.txt", GOLDEN_INPUT, &["test_txt", "yaml"]),
+    vector!("toml.txt", GOLDEN_INPUT, &["test_txt", "toml"]),
+    vector!(
+        "base64_yaml.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "yaml", "4", "--base64"]
+    ),
+    vector!(
+        "base64_toml.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "toml", "4", "--base64"]
+    ),
+    vector!(
+        "rust_tab_size_2.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "rust", "2"]
+    ),
+    vector!("hex.txt", GOLDEN_INPUT, &["test_txt", "hex"]),
+    vector!("bin.txt", GOLDEN_INPUT, &["test_txt", "bin"]),
+    vector!("octal.txt", GOLDEN_INPUT, &["test_txt", "octal"]),
+    vector!("csv.txt", GOLDEN_INPUT, &["test_txt", "csv"]),
+    vector!(
+        "html.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "html", "4", "--color"]
+    ),
+    vector!("xxd.txt", GOLDEN_INPUT, &["test_txt", "hex", "4", "--xxd"]),
+    vector!(
+        "base64.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "base64", "4", "--wrap", "16"]
+    ),
+    vector!(
+        "base32.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "base32", "4", "--wrap", "16"]
+    ),
+    vector!(
+        "ascii85.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "ascii85", "4", "--wrap", "16", "--frame"]
+    ),
+    vector!("hexstring.txt", GOLDEN_INPUT, &["test_txt", "hexstring"]),
+    vector!("uuencode.txt", GOLDEN_INPUT, &["test.txt", "uuencode"]),
+    vector!(
+        "srec.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "srec", "4", "--base-address", "0xfff8"]
+    ),
+    vector!(
+        "tests_c.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "c", "4", "--emit-tests"]
+    ),
+    vector!(
+        "tests_rust.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "rust", "4", "--emit-tests"]
+    ),
+    vector!(
+        "tests_python.txt",
+        GOLDEN_INPUT,
+        &["test_txt", "python", "4", "--emit-tests"]
+    ),
+    vector!("empty_c.txt", &[], &["test_txt", "c"]),
+    vector!("empty_cdef.txt", &[], &["test_txt", "cdef"]),
+    vector!("empty_rust.txt", &[], &["test_txt", "rust"]),
+    vector!("empty_csharp.txt", &[], &["test_txt", "csharp"]),
+    vector!("empty_python.txt", &[], &["test_txt", "python"]),
+    vector!("empty_javascript.txt", &[], &["test_txt", "javascript"]),
+    vector!("empty_go.txt", &[], &["test_txt", "go"]),
+    vector!("empty_java.txt", &[], &["test_txt", "java"]),
+    vector!("empty_ca65.txt", &[], &["test_txt", "ca65"]),
+    vector!("empty_acme.txt", &[], &["test_txt", "acme"]),
+    vector!("empty_z80asm.txt", &[], &["test_txt", "z80asm"]),
+    vector!("empty_zig.txt", &[], &["test_txt", "zig"]),
+    vector!("empty_swift.txt", &[], &["test_txt", "swift"]),
+    vector!("empty_swift_data.txt", &[], &["test_txt", "swift_data"]),
+    vector!("empty_kotlin.txt", &[], &["test_txt", "kotlin"]),
+    vector!("empty_nim.txt", &[], &["test_txt", "nim"]),
+    vector!("empty_d.txt", &[], &["test_txt", "d"]),
+    vector!("empty_pascal.txt", &[], &["test_txt", "pascal"]),
+    vector!("empty_ada.txt", &[], &["test_txt", "ada"]),
+    vector!("empty_nasm.txt", &[], &["test_txt", "nasm"]),
+    vector!("empty_gas.txt", &[], &["test_txt", "gas"]),
+    vector!("empty_lua.txt", &[], &["test_txt", "lua"]),
+    vector!("empty_lua_table.txt", &[], &["test_txt", "lua_table"]),
+    vector!("empty_php.txt", &[], &["test_txt", "php"]),
+    vector!("empty_php_array.txt", &[], &["test_txt", "php_array"]),
+    vector!("empty_ruby.txt", &[], &["test_txt", "ruby"]),
+    vector!("empty_ruby_string.txt", &[], &["test_txt", "ruby_string"]),
+    vector!("empty_perl.txt", &[], &["test_txt", "perl"]),
+    vector!("empty_haskell.txt", &[], &["test_txt", "haskell"]),
+    vector!("empty_ocaml.txt", &[], &["test_txt", "ocaml"]),
+    vector!("empty_elixir.txt", &[], &["test_txt", "elixir"]),
+    vector!("empty_erlang.txt", &[], &["test_txt", "erlang"]),
+    vector!("empty_scala.txt", &[], &["test_txt", "scala"]),
+    vector!("empty_dart.txt", &[], &["test_txt", "dart"]),
+    vector!("empty_julia.txt", &[], &["test_txt", "julia"]),
+    vector!("empty_r.txt", &[], &["test_txt", "r"]),
+    vector!("empty_matlab.txt", &[], &["test_txt", "matlab"]),
+    vector!("empty_objc.txt", &[], &["test_txt", "objc"]),
+    vector!("empty_verilog.txt", &[], &["test_txt", "verilog"]),
+    vector!("empty_vhdl.txt", &[], &["test_txt", "vhdl"]),
+    vector!("empty_arduino.txt", &[], &["test_txt", "arduino"]),
+    vector!("empty_powershell.txt", &[], &["test_txt", "powershell"]),
+    vector!("empty_bash.txt", &[], &["test_txt", "bash"]),
+    vector!("empty_sh.txt", &[], &["test_txt", "sh"]),
+    vector!("empty_sql.txt", &[], &["test_txt", "sql"]),
+    vector!("empty_postgres.txt", &[], &["test_txt", "postgres"]),
+    vector!("empty_vbnet.txt", &[], &["test_txt", "vbnet"]),
+    vector!("empty_gdscript.txt", &[], &["test_txt", "gdscript"]),
+    vector!("empty_gdscript3.txt", &[], &["test_txt", "gdscript3"]),
+    vector!("empty_fsharp.txt", &[], &["test_txt", "fsharp"]),
+    vector!("empty_fortran.txt", &[], &["test_txt", "fortran"]),
+    vector!("empty_crystal.txt", &[], &["test_txt", "crystal"]),
+    vector!("empty_tcl.txt", &[], &["test_txt", "tcl"]),
+    vector!("empty_lisp.txt", &[], &["test_txt", "lisp"]),
+    vector!("empty_scheme.txt", &[], &["test_txt", "scheme"]),
+    vector!("empty_wat.txt", &[], &["test_txt", "wat"]),
+    vector!("empty_typescript.txt", &[], &["test_txt", "typescript"]),
+    vector!("empty_groovy.txt", &[], &["test_txt", "groovy"]),
+    vector!("empty_solidity.txt", &[], &["test_txt", "solidity"]),
+    vector!("empty_c_string.txt", &[], &["test_txt", "c_string"]),
+    vector!("empty_rust_bytes.txt", &[], &["test_txt", "rust_bytes"]),
+    vector!("empty_yaml.txt", &[], &["test_txt", "yaml"]),
+    vector!("empty_toml.txt", &[], &["test_txt", "toml"]),
+];
+
+/// The programs checking the syntax of an output given on their stdin, by conversion type.
+/// They are only run when installed.
+const VALIDATORS: &[(&str, &str, &[&str])] = &[
+    ("rust", "rustfmt", &["--check", "--edition", "2021"]),
+    ("rust_bytes", "rustfmt", &["--check", "--edition", "2021"]),
+    (
+        "python",
+        "python3",
+        &["-c", "import ast, sys; ast.parse(sys.stdin.read())"],
+    ),
+    ("javascript", "node", &["--check"]),
+];
+
+/// Runs every emitter against the reference vectors and prints a line per vector, then returns
+/// whether they all passed. The outputs are also given to the validators that are installed.
+pub fn selftest() -> bool {
+    let input_file =
+        std::env::temp_dir().join(format!("bin2const-selftest-{}.bin", std::process::id()));
+    let mut failed = 0;
+    for vector in VECTORS {
+        match run_vector(vector, &input_file) {
+            Ok(validated) => println!("ok   {}{}", vector.golden, validated),
+            Err(e) => {
+                println!("FAIL {}: {}", vector.golden, e);
+                failed += 1;
+            }
+        }
+    }
+    let _ = std::fs::remove_file(&input_file);
+    println!("{} passed, {} failed", VECTORS.len() - failed, failed);
+    failed == 0
+}
+
+/// Converts the input of a vector and compares the output to the expected one, then gives it to
+/// its validator. Returns a note on how it was validated.
+fn run_vector(vector: &Vector, input_file: &std::path::Path) -> Result<String, String> {
+    std::fs::write(input_file, vector.input)
+        .map_err(|e| format!("Error while writing {}: {}", input_file.display(), e))?;
+    let (args, flags) = Flags::parse(vector.args.iter().map(Into::into).collect())?;
+    let name = args[0].to_string_lossy();
+    let conversion_type = args[1].to_string_lossy();
+    let tab_size = match args.get(2) {
+        Some(tab_size) => tab_size.to_string_lossy().parse::<usize>().unwrap_or(4),
+        None => 4,
+    };
+    let conversion = convert(input_file, None, &name, &conversion_type, tab_size, &flags)
+        .map_err(|failure| failure.message)?;
+    // The output is printed with a line break after it, but for the xxd dump.
+    let out = match flags.has("--xxd") {
+        true => conversion.out,
+        false => format!("{}\n", conversion.out),
+    };
+    if let Some(summary) = diff_summary(&out, vector.expected) {
+        return Err(format!("the output differs\n{}", summary));
+    }
+
+    let validator = VALIDATORS.iter().find(|(t, _, _)| *t == conversion_type);
+    let (program, validator_args) = match validator {
+        Some((_, program, args)) => (program, args),
+        None => return Ok(String::new()),
+    };
+    let child = Command::new(program)
+        .args(*validator_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(_) => return Ok(format!(" ({} not found, not validated)", program)),
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(out.as_bytes())
+            .map_err(|e| format!("Error while running {}: {}", program, e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Error while running {}: {}", program, e))?;
+    match output.status.success() {
+        true => Ok(format!(" (validated by {})", program)),
+        false => Err(format!(
+            "{} rejected the output\n{}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        )),
+    }
+}
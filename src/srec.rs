@@ -0,0 +1,152 @@
+use crate::decode::decode_hex_digits;
+use crate::library::place_records;
+
+/// The number of data bytes in each S-record, like objcopy.
+const SREC_BYTES_PER_RECORD: usize = 16;
+
+/// The size of the addresses of S-records, which picks the types of their records.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SrecWidth {
+    /// S1 data records ended by an S9 record, for addresses up to 0xffff.
+    Bits16,
+    /// S2 data records ended by an S8 record, for addresses up to 0xffffff.
+    Bits24,
+    /// S3 data records ended by an S7 record, for addresses up to 0xffffffff.
+    Bits32,
+}
+
+impl SrecWidth {
+    /// Parses the width given to --address-width, in bits.
+    pub fn from_name(name: &str) -> Option<SrecWidth> {
+        match name.trim() {
+            "16" | "s19" | "s1" => Some(SrecWidth::Bits16),
+            "24" | "s28" | "s2" => Some(SrecWidth::Bits24),
+            "32" | "s37" | "s3" => Some(SrecWidth::Bits32),
+            _ => None,
+        }
+    }
+
+    /// The smallest width holding every address from `start` to `end`, excluded.
+    pub fn fitting(start: usize, end: usize) -> SrecWidth {
+        match end.saturating_sub(1).max(start) {
+            0..=0xffff => SrecWidth::Bits16,
+            0x10000..=0xffffff => SrecWidth::Bits24,
+            _ => SrecWidth::Bits32,
+        }
+    }
+
+    /// The highest address the records can hold.
+    pub fn max_address(self) -> usize {
+        match self {
+            SrecWidth::Bits16 => 0xffff,
+            SrecWidth::Bits24 => 0xffffff,
+            SrecWidth::Bits32 => 0xffffffff,
+        }
+    }
+
+    /// The size of the addresses, in bytes.
+    fn address_size(self) -> usize {
+        match self {
+            SrecWidth::Bits16 => 2,
+            SrecWidth::Bits24 => 3,
+            SrecWidth::Bits32 => 4,
+        }
+    }
+
+    /// The types of the data records and of the record ending the file.
+    fn record_types(self) -> (u8, u8) {
+        match self {
+            SrecWidth::Bits16 => (1, 9),
+            SrecWidth::Bits24 => (2, 8),
+            SrecWidth::Bits32 => (3, 7),
+        }
+    }
+}
+
+/// Formats a record: its type, its byte count, its address on `address_size` bytes, its data
+/// and its checksum, the ones' complement of the sum of the bytes after the type.
+fn srec_record(kind: u8, address: usize, address_size: usize, data: &[u8]) -> String {
+    let mut bytes = vec![(address_size + data.len() + 1) as u8];
+    bytes.extend_from_slice(&address.to_be_bytes()[std::mem::size_of::<usize>() - address_size..]);
+    bytes.extend_from_slice(data);
+    let sum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    bytes.push(!sum);
+    let hex = bytes.iter().map(|byte| format!("{:02X}", byte));
+    format!("S{}{}\n", kind, hex.collect::<String>())
+}
+
+/// Converts an array of bytes to Motorola S-records loaded at `base_address`: an S0 header
+/// holding `header`, the data records, then the record ending the file with `base_address` as
+/// its start address. The addresses must fit in `width`.
+/// For exemple, with binary = &[0x00, 0x01, 0x02, 0x03], header = "TEST_TXT",
+/// base_address = 0x100 and width = SrecWidth::Bits16, the function returns:
+/// S00B0000544553545F54585455
+/// S107010000010203F1
+/// S9030100FB
+pub fn binary_to_srec(
+    binary: &[u8],
+    header: &str,
+    base_address: usize,
+    width: SrecWidth,
+) -> String {
+    let address_size = width.address_size();
+    let (data_type, end_type) = width.record_types();
+    // The byte count of a record can't count more than 252 bytes of data with its address.
+    let header = &header.as_bytes()[..header.len().min(252)];
+    let mut out = srec_record(0, 0, 2, header);
+    for (i, chunk) in binary.chunks(SREC_BYTES_PER_RECORD).enumerate() {
+        let address = base_address + i * SREC_BYTES_PER_RECORD;
+        out.push_str(&srec_record(data_type, address, address_size, chunk));
+    }
+    out.push_str(&srec_record(end_type, base_address, address_size, &[]));
+    out
+}
+
+/// Reads Motorola S-records back into the bytes of their S1, S2 and S3 data records, laid out by
+/// their addresses from the lowest one, the gaps filled with `fill`. The header, count and start
+/// address records are ignored, and the checksum of every record is checked. The records can't
+/// span more than `max_size` bytes.
+/// For exemple, with text = "S1050100000AEF\nS104010303F4\nS9030100FB\n", fill = 0xff and
+/// max_size = 16, the function returns vec![0x00, 0x0a, 0xff, 0x03].
+pub fn srec_to_binary(text: &str, fill: u8, max_size: usize) -> Result<Vec<u8>, String> {
+    let mut records = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: String| format!("line {}: {}", number + 1, message);
+        let (kind, digits) = match line
+            .strip_prefix('S')
+            .and_then(|rest| rest.split_at_checked(1))
+        {
+            Some((kind, digits)) => (kind, digits),
+            None => return Err(error("a record starts with S and its type".to_string())),
+        };
+        let bytes = decode_hex_digits(digits).map_err(error)?;
+        if bytes.is_empty() || bytes.len() != bytes[0] as usize + 1 {
+            return Err(error(format!(
+                "{} doesn't have the length of its byte count",
+                line
+            )));
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0xff {
+            return Err(error(format!("{} has a wrong checksum", line)));
+        }
+        let address_size = match kind {
+            "1" => 2,
+            "2" => 3,
+            "3" => 4,
+            "0" | "5" | "6" | "7" | "8" | "9" => continue,
+            _ => return Err(error(format!("S{} isn't a valid record type", kind))),
+        };
+        if bytes.len() < address_size + 2 {
+            return Err(error(format!("{} is too short for its address", line)));
+        }
+        let address = bytes[1..=address_size]
+            .iter()
+            .fold(0, |address, byte| address << 8 | *byte as usize);
+        records.push((address, bytes[address_size + 1..bytes.len() - 1].to_vec()));
+    }
+    place_records(records, fill, max_size)
+}
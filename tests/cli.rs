@@ -0,0 +1,1600 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Writes `binary` to a file named `file_name` in the test directory and returns its path.
+fn input_file(file_name: &str, binary: &[u8]) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(file_name);
+    std::fs::write(&path, binary).unwrap();
+    path
+}
+
+/// Runs bin2const with the given arguments and returns what it printed.
+fn run(args: &[&str]) -> String {
+    run_with_status(args).1
+}
+
+/// Runs bin2const with the given arguments and returns its exit code and what it printed.
+fn run_with_status(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_bin2const"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+/// Runs bin2const with the given arguments and `stdin` as its input, and returns what it printed.
+fn run_with_stdin(args: &[&str], stdin: &[u8]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bin2const"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+}
+
+/// Runs bin2const with the given arguments and returns what it printed to stderr.
+fn run_stderr(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_bin2const"))
+        .args(args)
+        .output()
+        .unwrap();
+    String::from_utf8(output.stderr).unwrap()
+}
+
+/// Runs bin2const with arguments making it fail and returns its exit code and its error.
+fn run_error(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_bin2const"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.stdout.is_empty());
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn names_are_uppercased_by_default() {
+    let input = input_file("case_default.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    assert!(run(&[input, "test_txt", "rust"]).starts_with("const TEST_TXT: [u8; 3]"));
+    assert!(run(&[input, "test.txt", "c"]).starts_with("const unsigned char TEST_TXT[]"));
+    assert!(run(&[input, "testTxt", "cdef"]).starts_with("#define TEST_TXT_SIZE 3\n"));
+}
+
+#[test]
+fn case_policy_can_be_chosen() {
+    let input = input_file("case_policy.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let first_line = |case: &str| {
+        run(&[input, "myAsset_png", "python", "--case", case])
+            .lines()
+            .next()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(first_line("upper"), "MY_ASSET_PNG = bytes([");
+    assert_eq!(first_line("lower"), "my_asset_png = bytes([");
+    assert_eq!(first_line("camel"), "myAssetPng = bytes([");
+    assert_eq!(first_line("pascal"), "MyAssetPng = bytes([");
+    assert_eq!(first_line("preserve"), "myAsset_png = bytes([");
+}
+
+#[test]
+fn names_can_be_prefixed_and_suffixed() {
+    let input = input_file("name_affixes.bin", &[1]);
+    let input = input.to_str().unwrap();
+    let args = ["--name-prefix", "asset_", "--name-suffix", "_bin"];
+    let out = run(&[&[input, "logo", "rust"][..], &args].concat());
+    assert!(out.starts_with("const ASSET_LOGO_BIN: [u8; 1] = ["));
+    let out = run(&[&[input, "logo", "js", "--case", "camel"][..], &args].concat());
+    assert!(out.starts_with("const assetLogoBin = new Uint8Array(["));
+}
+
+#[test]
+fn reserved_words_are_rejected() {
+    let input = input_file("reserved_words.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let (status, out) = run_error(&[input, "match", "rust", "--case", "lower"]);
+    assert_eq!(status, 1);
+    assert!(out.starts_with("Invalid constant name: \"match\" is a reserved word in Rust"));
+    let (_, out) = run_error(&[input, "class", "csharp", "--case", "preserve"]);
+    assert!(out.starts_with("Invalid constant name: \"class\" is a reserved word in C#"));
+}
+
+#[test]
+fn invalid_names_are_rejected_or_fixed() {
+    let input = input_file("invalid_names.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let (_, out) = run_error(&[input, "2d-sprite", "c", "--case", "preserve"]);
+    assert!(out.starts_with("Invalid constant name: \"2d-sprite\" starts with a digit"));
+    let out = run(&[input, "2d-sprite", "c", "--case", "preserve", "--fix-names"]);
+    assert!(out.starts_with("const unsigned char _2d_sprite[] = {"));
+    let out = run(&[input, "match", "rust", "--case", "lower", "--fix-names"]);
+    assert!(out.starts_with("const match_: [u8; 3] = ["));
+    let out = run(&[input, "2d-sprite", "r", "--case", "preserve", "--fix-names"]);
+    assert!(out.starts_with("X2d_sprite <- as.raw(c("));
+}
+
+#[test]
+fn check_fails_when_the_output_is_out_of_date() {
+    let input = input_file("check.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("check.rs");
+    let output = output.to_str().unwrap();
+    run(&[input, "check", "rust", "4", output]);
+
+    let (status, out) = run_with_status(&[input, "check", "rust", "4", output, "--check"]);
+    assert_eq!(status, 0);
+    assert_eq!(out, "");
+    assert!(
+        run_stderr(&[input, "check", "rust", "4", output, "--check"]).ends_with("is up to date\n")
+    );
+
+    let input = input_file("check.bin", &[1, 2, 4]);
+    let input = input.to_str().unwrap();
+    let (status, out) = run_error(&[input, "check", "rust", "4", output, "--check"]);
+    assert_eq!(status, 1);
+    assert!(out.contains("is out of date:\n1 line(s) differ"));
+    assert!(out.contains("-     0x01, 0x02, 0x03\n+     0x01, 0x02, 0x04\n"));
+}
+
+#[test]
+fn verify_decodes_every_language_back() {
+    let binary = (0..=255).collect::<Vec<u8>>();
+    let input = input_file("verify.bin", &binary);
+    let input = input.to_str().unwrap();
+    for language in [
+        "c",
+        "cdef",
+        "rust",
+        "csharp",
+        "python",
+        "js",
+        "go",
+        "java",
+        "ca65",
+        "acme",
+        "z80asm",
+        "zig",
+        "swift",
+        "swift_data",
+        "kotlin",
+        "nim",
+        "d",
+        "pascal",
+        "ada",
+        "nasm",
+        "gas",
+        "lua",
+        "lua_table",
+        "php",
+        "php_array",
+        "ruby",
+        "ruby_string",
+        "perl",
+        "haskell",
+        "ocaml",
+        "elixir",
+        "erlang",
+        "scala",
+        "dart",
+        "julia",
+        "r",
+        "matlab",
+        "objc",
+        "verilog",
+        "vhdl",
+        "arduino",
+        "powershell",
+        "bash",
+        "sh",
+        "sql",
+        "postgres",
+        "vbnet",
+        "gdscript",
+        "gdscript3",
+        "fsharp",
+        "fortran",
+        "crystal",
+        "tcl",
+        "lisp",
+        "scheme",
+        "wat",
+        "typescript",
+        "groovy",
+        "solidity",
+        "c_string",
+        "rust_bytes",
+        "yaml",
+        "toml",
+    ] {
+        let (status, _) = run_with_status(&[input, "verify", language, "4", "--verify"]);
+        assert_eq!(status, 0, "{}", language);
+    }
+    let (status, out) = run_error(&[input, "verify", "hex", "4", "--verify"]);
+    assert_eq!(status, 2);
+    assert!(out.ends_with("--verify only works with the constant conversion types\n"));
+}
+
+#[test]
+fn several_inputs_are_emitted_together() {
+    let key = input_file("several_key.bin", &[1, 2]);
+    let cert = input_file("several_cert.bin", &[3]);
+    let (key, cert) = (key.to_str().unwrap(), cert.to_str().unwrap());
+    let out = run(&[
+        "--input", key, "--name", "key", "--input", cert, "--name", "cert", "rust",
+    ]);
+    assert_eq!(
+        out,
+        "const KEY: [u8; 2] = [\n    0x01, 0x02\n];\n\nconst CERT: [u8; 1] = [\n    0x03\n];\n\n"
+    );
+
+    assert_eq!(
+        run_error(&["--input", key, "--name", "key", "--input", cert, "rust"]),
+        (
+            2,
+            "Each --input needs a --name, or none to be named after its file\n".to_string()
+        )
+    );
+    assert_eq!(
+        run_error(&["--input", key, "--name", "key", "--input", cert, "--name", "key", "rust"]),
+        (2, "The constant name key is given twice\n".to_string())
+    );
+    assert_eq!(
+        run_error(&["--input", key, "--name", "key", "--input", cert, "--name", "KEY", "rust"]),
+        (
+            2,
+            "The constant names key and KEY are both emitted as KEY\n".to_string()
+        )
+    );
+    assert_eq!(
+        run_error(&["--input", key, "--name", "my-key", "--input", cert, "--name", "my_key", "c"]),
+        (
+            2,
+            "The constant names my-key and my_key are both emitted as MY_KEY\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn inputs_are_named_after_their_files() {
+    let key = input_file("named_key.der", &[1, 2]);
+    let cert = input_file("named_cert.der", &[3]);
+    let (key, cert) = (key.to_str().unwrap(), cert.to_str().unwrap());
+    assert_eq!(
+        run(&["--input", key, "--input", cert, "rust"]),
+        "const NAMED_CERT_DER: [u8; 1] = [\n    0x03\n];\n\n\
+         const NAMED_KEY_DER: [u8; 2] = [\n    0x01, 0x02\n];\n\n"
+    );
+    assert_eq!(
+        run_error(&["--input", key, "--input", "-", "rust"]),
+        (2, "stdin has no file name, - needs a --name\n".to_string())
+    );
+}
+
+#[test]
+fn stats_are_printed_to_stderr() {
+    let input = input_file("stats.bin", &[1, 2, 3, 4]);
+    let input = input.to_str().unwrap();
+    let stats = run_stderr(&[input, "stats", "rust", "--stats"]);
+    assert!(stats.contains("stats.bin: 4 bytes -> 55 bytes (13.75x), 3 lines, "));
+    let stats = run_stderr(&[input, "stats", "rust", "--stats=json"]);
+    assert!(
+        stats.contains("\"input_size\": 4, \"output_size\": 55, \"ratio\": 13.75, \"lines\": 3,")
+    );
+}
+
+#[test]
+fn regions_are_updated_in_place() {
+    let input = input_file("region.bin", &[1, 2]);
+    let input = input.to_str().unwrap();
+    let output = input_file(
+        "region.rs",
+        b"fn main() {}\r\n\r\n/* bin2const:begin data */\r\nold\r\n/* bin2const:end */\r\n// end\r\n",
+    );
+    let output = output.to_str().unwrap();
+    run(&[input, "data", "rust", "4", output, "--region"]);
+    assert_eq!(
+        std::fs::read_to_string(output).unwrap(),
+        "fn main() {}\r\n\r\n/* bin2const:begin data */\r\nconst DATA: [u8; 2] = [\r\n    0x01, 0x02\r\n];\r\n/* bin2const:end */\r\n// end\r\n"
+    );
+
+    let (status, out) = run_error(&[input, "other", "rust", "4", output, "--region"]);
+    assert_eq!(status, 1);
+    assert!(out.ends_with("No \"bin2const:begin other\" marker\n"));
+}
+
+#[test]
+fn constants_are_replaced_in_place() {
+    let input = input_file("replace.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let output = input_file(
+        "replace.rs",
+        b"pub static DATA: [u8; 1] = [0x00];\n\nfn main() {\n    let _ = DATA;\n}\n",
+    );
+    let output = output.to_str().unwrap();
+    run(&[input, "data", "rust", "4", "--replace-in", output]);
+    assert_eq!(
+        std::fs::read_to_string(output).unwrap(),
+        "pub static DATA: [u8; 3] = [\n    0x01, 0x02, 0x03\n];\n\nfn main() {\n    let _ = DATA;\n}\n"
+    );
+
+    let output = input_file("replace.h", b"#define DATA_SIZE 1\n#define DATA { 0x00 }\n");
+    let output = output.to_str().unwrap();
+    run(&[input, "data", "cdef", "4", "--replace-in", output]);
+    assert_eq!(
+        std::fs::read_to_string(output).unwrap(),
+        "#define DATA_SIZE 3\n#define DATA { \\\n    0x01, 0x02, 0x03 \\\n}\n"
+    );
+
+    let (status, out) = run_error(&[input, "other", "rust", "4", "--replace-in", output]);
+    assert_eq!(status, 1);
+    assert!(out.ends_with("No constant named OTHER\n"));
+}
+
+#[test]
+fn directories_are_filtered() {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("directory");
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(directory.join("icons/deep")).unwrap();
+    std::fs::create_dir_all(directory.join("build")).unwrap();
+    for file in [
+        "logo.png",
+        "icons/home.png",
+        "icons/deep/x.png",
+        "build/out.o",
+        "notes.txt",
+        ".hidden",
+    ] {
+        std::fs::write(directory.join(file), [0]).unwrap();
+    }
+    std::fs::write(directory.join(".gitignore"), "build/\n*.txt\n").unwrap();
+    let directory = directory.to_str().unwrap();
+    let constants = |args: &[&str]| {
+        run(&[&[directory, "assets", "python"], args].concat())
+            .lines()
+            .filter_map(|line| line.strip_suffix(" = bytes(["))
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(
+        constants(&[]),
+        [
+            "ASSETS_BUILD_OUT_O",
+            "ASSETS_ICONS_DEEP_X_PNG",
+            "ASSETS_ICONS_HOME_PNG",
+            "ASSETS_LOGO_PNG",
+            "ASSETS_NOTES_TXT"
+        ]
+    );
+    assert_eq!(
+        constants(&["--respect-gitignore", "--max-depth", "2"]),
+        ["ASSETS_ICONS_HOME_PNG", "ASSETS_LOGO_PNG"]
+    );
+    assert_eq!(
+        constants(&["--include", "*.png", "--exclude", "icons/deep"]),
+        ["ASSETS_ICONS_HOME_PNG", "ASSETS_LOGO_PNG"]
+    );
+    assert_eq!(
+        constants(&["--hidden", "--include", ".*"]),
+        ["ASSETS_GITIGNORE", "ASSETS_HIDDEN"]
+    );
+}
+
+#[test]
+fn glob_patterns_are_expanded() {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("glob");
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(directory.join("icons")).unwrap();
+    for file in ["logo.png", "logo.jpg", "icons/home.png", ".hidden.png"] {
+        std::fs::write(directory.join(file), [0]).unwrap();
+    }
+    let pattern = |glob: &str| format!("{}/{}", directory.display(), glob);
+    let constants = |args: &[&str]| {
+        run(&[args, &["python"]].concat())
+            .lines()
+            .filter_map(|line| line.strip_suffix(" = bytes(["))
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(
+        constants(&[&pattern("*.png"), "assets"]),
+        ["ASSETS_LOGO_PNG"]
+    );
+    assert_eq!(
+        constants(&[
+            "--input",
+            &pattern("**/*.png"),
+            "--input",
+            &pattern("*.jpg")
+        ]),
+        ["ICONS_HOME_PNG", "LOGO_JPG", "LOGO_PNG"]
+    );
+    assert_eq!(
+        constants(&[&pattern("*/*.png"), "assets"]),
+        ["ASSETS_ICONS_HOME_PNG"]
+    );
+
+    let (status, out) = run_error(&[&pattern("*.gif"), "assets", "python"]);
+    assert_eq!(status, 3);
+    assert!(out.ends_with("*.gif matches no file\n"));
+}
+
+#[test]
+fn inputs_can_be_sorted() {
+    let small = input_file("sort_small.bin", &[1]);
+    let big = input_file("sort_big.bin", &[1, 2, 3]);
+    let (small, big) = (small.to_str().unwrap(), big.to_str().unwrap());
+    let constants = |sort: &str| {
+        run(&[
+            "--input", big, "--name", "b", "--input", small, "--name", "a", "--input", small,
+            "--name", "C", "python", "--sort", sort,
+        ])
+        .lines()
+        .filter_map(|line| line.strip_suffix(" = bytes(["))
+        .map(|name| name.to_string())
+        .collect::<Vec<String>>()
+    };
+    assert_eq!(constants("name"), ["A", "B", "C"]);
+    assert_eq!(constants("size"), ["A", "C", "B"]);
+    assert_eq!(constants("manifest-order"), ["B", "A", "C"]);
+}
+
+#[test]
+fn sections_come_with_a_linker_script() {
+    let input = input_file("section.bin", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let script = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("section.ld");
+    let script = script.to_str().unwrap();
+    let out = run(&[
+        input,
+        "data",
+        "c",
+        "4",
+        "--section",
+        ".assets",
+        "--address",
+        "0x08040000",
+        "--linker-script",
+        script,
+    ]);
+    assert!(out.starts_with(
+        "const unsigned char DATA[] __attribute__((section(\".assets\"), used)) = {\n"
+    ));
+    let script = std::fs::read_to_string(script).unwrap();
+    assert!(script.contains("ASSETS (r) : ORIGIN = 0x08040000, LENGTH = 3\n"));
+    assert!(script.contains("KEEP(*(.assets))\n    } > ASSETS\n"));
+
+    let out = run(&[input, "data", "gas", "--section", ".assets"]);
+    assert!(out.starts_with("    .section .assets, \"a\"\n    .global DATA\n"));
+    assert_eq!(
+        run_error(&[input, "data", "rust", "--section", ".assets"]),
+        (1, "--section isn't supported for Rust\n".to_string())
+    );
+}
+
+#[test]
+fn espidf_embeds_with_cmake_or_an_array() {
+    let input = input_file("esp_logo.png", &[1, 2, 3]);
+    let input = input.to_str().unwrap();
+    let out = run(&[input, "logo", "c", "--espidf"]);
+    assert!(out.contains("target_add_binary_data(${COMPONENT_LIB} \""));
+    assert!(out.contains(
+        "extern const uint8_t LOGO_START[] asm(\"_binary_esp_logo_png_start\");\n\
+         extern const uint8_t LOGO_END[] asm(\"_binary_esp_logo_png_end\");\n\
+         #define LOGO_SIZE ((size_t)(LOGO_END - LOGO_START))\n"
+    ));
+    let out = run(&[input, "logo", "c", "--espidf=array"]);
+    assert!(out.starts_with("#define LOGO_SIZE 3\n\nconst unsigned char LOGO[] = {\n"));
+    assert_eq!(
+        run_error(&[input, "logo", "rust", "--espidf"]),
+        (1, "--espidf only works with c\n".to_string())
+    );
+}
+
+#[test]
+fn constants_can_be_looked_up_by_path() {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("lookup");
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(directory.join("icons")).unwrap();
+    std::fs::write(directory.join("logo.png"), [1]).unwrap();
+    std::fs::write(directory.join("icons/home.png"), [2, 3]).unwrap();
+    let directory = directory.to_str().unwrap();
+
+    let out = run(&[directory, "assets", "rust", "--lookup", "find_asset"]);
+    assert!(out.ends_with(
+        "fn find_asset(path: &str) -> Option<&'static [u8]> {\n    match path {\n        \
+         \"icons/home.png\" => Some(&ASSETS_ICONS_HOME_PNG[..]),\n        \
+         \"logo.png\" => Some(&ASSETS_LOGO_PNG[..]),\n        _ => None,\n    }\n}\n\n"
+    ));
+    let out = run(&[directory, "assets", "c", "--lookup", "find_asset"]);
+    assert!(out.contains(
+        "    { \"icons/home.png\", ASSETS_ICONS_HOME_PNG, 2 },\n    \
+         { \"logo.png\", ASSETS_LOGO_PNG, 1 },\n"
+    ));
+    assert_eq!(
+        run_error(&[directory, "assets", "go", "--lookup", "find_asset"]),
+        (2, "--lookup only works with c and rust\n".to_string())
+    );
+}
+
+#[test]
+fn data_can_be_padded_to_a_total_size() {
+    let input = input_file("total_size.bin", &[1, 2]);
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[input, "data", "python", "--total-size", "4"]),
+        "DATA = bytes([\n    0x01, 0x02, 0xff, 0xff\n])\n\n"
+    );
+    assert_eq!(
+        run(&[
+            input,
+            "data",
+            "python",
+            "--total-size",
+            "3",
+            "--fill",
+            "0x00"
+        ]),
+        "DATA = bytes([\n    0x01, 0x02, 0x00\n])\n\n"
+    );
+    assert_eq!(
+        run_error(&[input, "data", "python", "--total-size", "1"]),
+        (
+            1,
+            "The data is 2 bytes, more than the --total-size of 1 bytes\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn rust_include_is_relative_to_the_output() {
+    let input = input_file("rust_include.bin", b"abc");
+    let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("rust_include/out.rs");
+    std::fs::create_dir_all(output.parent().unwrap()).unwrap();
+    let (input, output) = (input.to_str().unwrap(), output.to_str().unwrap());
+    run(&[input, "data", "rust-include", "4", output]);
+    assert_eq!(
+        std::fs::read_to_string(output).unwrap(),
+        "pub static DATA: &[u8] = include_bytes!(\"../rust_include.bin\");\n"
+    );
+    assert_eq!(
+        run_error(&[input, "data", "rust-include", "--total-size", "8"]),
+        (
+            1,
+            "rust-include embeds the file as is, it can't be used with options changing the data\n"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn accessors_are_emitted() {
+    let input = input_file("accessor.bin", b"abc");
+    let input = input.to_str().unwrap();
+    assert!(run(&[input, "logo", "c", "--emit-accessor"]).ends_with(
+        "static inline const uint8_t *logo_data(size_t *len)\n{\n    *len = 3;\n    return LOGO;\n}\n\n"
+    ));
+    assert!(run(&[input, "logo", "rust", "--emit-accessor"])
+        .ends_with("pub fn logo() -> &'static [u8] {\n    &LOGO[..]\n}\n\n"));
+    assert!(run(&[input, "logo", "csharp", "--emit-accessor"])
+        .ends_with("public static System.ReadOnlySpan<byte> Logo => LOGO;\n\n"));
+    assert_eq!(
+        run_error(&[input, "logo", "rust", "--emit-accessor", "--case", "lower"]),
+        (
+            1,
+            "The accessor of logo would have the same name, choose another --case\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn selftest_passes() {
+    let (status, out) = run_with_status(&["selftest"]);
+    assert!(out.ends_with(" passed, 0 failed\n"), "{}", out);
+    assert_eq!(status, 0);
+}
+
+#[test]
+fn go_package_is_declared() {
+    let input = input_file("go_package.bin", b"abc");
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[input, "data", "go", "--package", "assets"]),
+        "package assets\n\nvar DATA = []byte{\n    0x61, 0x62, 0x63,\n}\n\n"
+    );
+    assert_eq!(
+        run_error(&[input, "data", "go", "--package", "func"]),
+        (
+            2,
+            "Invalid package name: \"func\" is a reserved word in Go\n".to_string()
+        )
+    );
+    assert_eq!(
+        run_error(&[input, "data", "rust", "--package", "assets"]),
+        (2, "--package isn't supported for Rust\n".to_string())
+    );
+}
+
+#[test]
+fn java_class_is_declared() {
+    let input = input_file("java_class.bin", b"abc");
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[
+            input,
+            "data",
+            "java",
+            "--class",
+            "Assets",
+            "--package",
+            "com.example"
+        ]),
+        "package com.example;\n\npublic final class Assets {\n    \
+         public static final byte[] DATA = new byte[] {\n        0x61, 0x62, 0x63\n    };\n}\n\n"
+    );
+    assert_eq!(
+        run_error(&[input, "data", "java", "--package", "com.example"]),
+        (2, "--package needs --class for Java\n".to_string())
+    );
+}
+
+#[test]
+fn haskell_and_ocaml_names_start_lowercase() {
+    let input = input_file("haskell_names.bin", b"abc");
+    let input = input.to_str().unwrap();
+    assert!(
+        run_error(&[input, "Font", "haskell", "--case", "preserve"]).1.starts_with(
+            "Invalid constant name: \"Font\" starts with an uppercase letter, which Haskell doesn't allow \
+         for constants"
+        )
+    );
+    assert!(run(&[
+        input,
+        "Font",
+        "haskell",
+        "--case",
+        "preserve",
+        "--fix-names"
+    ])
+    .contains("\nfont :: BS.ByteString\n"));
+    // The import comes before the parameters of the preset.
+    assert!(run(&[input, "font", "haskell", "--webasset"])
+        .starts_with("import qualified Data.ByteString as BS\n\nfontMime :: String\n"));
+    assert!(run_error(&[input, "Font", "ocaml", "--case", "preserve"])
+        .1
+        .starts_with("Invalid constant name: \"Font\" starts with an uppercase letter"));
+}
+
+#[test]
+fn wat_data_is_placed_at_the_address() {
+    let input = input_file("wat_address.bin", b"abc");
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[input, "data", "wat", "--address", "0x400"]),
+        "(data $DATA (i32.const 1024)\n    \"\\61\\62\\63\"\n)\n\n"
+    );
+}
+
+#[test]
+fn typescript_can_be_readonly() {
+    let input = input_file("typescript_readonly.bin", b"abc");
+    let input = input.to_str().unwrap();
+    assert!(run(&[input, "data", "ts", "--readonly"])
+        .starts_with("export const DATA: Readonly<Uint8Array> = new Uint8Array([\n"));
+    assert_eq!(
+        run_error(&[input, "data", "js", "--readonly"]),
+        (1, "--readonly isn't supported for Javascript\n".to_string())
+    );
+}
+
+#[test]
+fn srec_addresses_are_widened_to_fit() {
+    let input = input_file("srec_width.bin", b"abc");
+    let input = input.to_str().unwrap();
+    let record_types = |args: &[&str]| {
+        let out = run(&[&[input, "hdr", "srec"], args].concat());
+        out.lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line[..2].to_string())
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(record_types(&[]), ["S0", "S1", "S9"]);
+    assert_eq!(
+        record_types(&["--base-address", "0xfffe"]),
+        ["S0", "S2", "S8"]
+    );
+    assert_eq!(record_types(&["--address-width", "32"]), ["S0", "S3", "S7"]);
+    assert_eq!(
+        run_error(&[
+            input,
+            "hdr",
+            "srec",
+            "--base-address",
+            "0xfffe",
+            "--address-width",
+            "16"
+        ]),
+        (
+            1,
+            "The data ends at 0x10000, past the highest address of the records, 0xffff\n"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn data_can_be_embedded_as_base64() {
+    let input = input_file("base64.bin", &[0xfb, 0xff, 0xbf]);
+    let input = input.to_str().unwrap();
+    assert_eq!(run(&[input, "x", "base64"]), "+/+/\n\n");
+    assert_eq!(run(&[input, "x", "base64", "--url-safe"]), "-_-_\n\n");
+    assert_eq!(
+        run(&[input, "data", "rust", "--base64"]),
+        "const DATA: &str = \"+/+/\";\n\n"
+    );
+    assert_eq!(
+        run_error(&[input, "data", "c", "--base64", "--emit-tests"]),
+        (1, "--emit-tests can't be used with --base64\n".to_string())
+    );
+}
+
+#[test]
+fn output_can_be_wrapped_in_markdown() {
+    let input = input_file("markdown.bin", b"``` x");
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[input, "x", "rust", "--wrap-markdown"]),
+        "```rust\nconst X: [u8; 5] = [\n    0x60, 0x60, 0x60, 0x20, 0x78\n];\n```\n\n"
+    );
+    assert_eq!(
+        run(&[input, "x", "hexstring", "--wrap-markdown"]),
+        "```text\n6060602078\n```\n\n"
+    );
+    assert_eq!(
+        run(&[input, "x", "c", "--text", "--wrap-markdown"]),
+        "````c\nconst char *X = \"``` x\";\n````\n\n"
+    );
+}
+
+#[test]
+fn encodings_can_be_lowercased() {
+    let input = input_file("encodings_case.bin", b"\xab\xcd");
+    let input = input.to_str().unwrap();
+    assert_eq!(run(&[input, "x", "hexstring"]), "abcd\n\n");
+    assert_eq!(
+        run(&[input, "x", "hexstring", "--case", "upper"]),
+        "ABCD\n\n"
+    );
+    assert_eq!(run(&[input, "x", "base32"]), "VPGQ====\n\n");
+    assert_eq!(
+        run(&[input, "x", "base32", "--case", "lower"]),
+        "vpgq====\n\n"
+    );
+}
+
+#[test]
+fn z85_needs_whole_groups() {
+    let input = input_file("z85.bin", &[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+    let input = input.to_str().unwrap();
+    assert_eq!(run(&[input, "x", "z85"]), "HelloWorld\n\n");
+    assert_eq!(
+        run_error(&[input, "x", "z85", "--length", "6"]),
+        (
+            1,
+            "Z85 only encodes a multiple of 4 bytes, not 6 bytes\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn html_dump_escapes_its_text() {
+    let input = input_file("html_escape.bin", b"<a&b>");
+    let input = input.to_str().unwrap();
+    let out = run(&[input, "x", "html"]);
+    assert!(out.contains(
+        "<tr><td class=\"offset\">00000000</td><td>3c 61 26 62 3e</td><td>&lt;a&amp;b&gt;</td></tr>\n"
+    ));
+}
+
+#[test]
+fn text_columns_decode_their_charset() {
+    // The characters split between two rows are shown on the row they start on, and their
+    // other bytes as a dot.
+    let utf8 = input_file("charset_utf8.bin", "fifteen bytes: été €".as_bytes());
+    assert_eq!(
+        run(&[utf8.to_str().unwrap(), "x", "hex", "--charset", "utf8"]),
+        "00000000  66 69 66 74  65 65 6e 20  62 79 74 65  73 3a 20 c3   |fifteen bytes: é|\n\
+         00000010  a9 74 c3 a9  20 e2 82 ac                             |•té• €••        |\n\n"
+    );
+    let invalid = input_file("charset_invalid.bin", b"\xff\xfe");
+    assert_eq!(
+        run(&[invalid.to_str().unwrap(), "x", "hex", "--charset", "utf8"]),
+        "00000000  ff fe                                                |..              |\n\n"
+    );
+
+    let mut utf16le = "seven c😀!"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<u8>>();
+    utf16le.push(b'A');
+    let utf16le = input_file("charset_utf16le.bin", &utf16le);
+    assert_eq!(
+        run(&[
+            utf16le.to_str().unwrap(),
+            "x",
+            "hex",
+            "--charset",
+            "utf16le"
+        ]),
+        "00000000  73 00 65 00  76 00 65 00  6e 00 20 00  63 00 3d d8   |s•e•v•e•n• •c•😀•|\n\
+         00000010  00 de 21 00  41                                      |••!•.           |\n\n"
+    );
+    let utf16be = "seven c😀!"
+        .encode_utf16()
+        .flat_map(u16::to_be_bytes)
+        .collect::<Vec<u8>>();
+    let utf16be = input_file("charset_utf16be.bin", &utf16be);
+    assert_eq!(
+        run(&[
+            utf16be.to_str().unwrap(),
+            "x",
+            "hex",
+            "--charset",
+            "utf16be"
+        ]),
+        "00000000  00 73 00 65  00 76 00 65  00 6e 00 20  00 63 d8 3d   |s•e•v•e•n• •c•😀•|\n\
+         00000010  de 00 00 21                                          |••!•            |\n\n"
+    );
+}
+
+#[test]
+fn text_columns_decode_ebcdic() {
+    // "Hello, World! 123" in EBCDIC code page 037, then bytes with no printable character.
+    let mut ebcdic = vec![
+        0xc8, 0x85, 0x93, 0x93, 0x96, 0x6b, 0x40, 0xe6, 0x96, 0x99, 0x93, 0x84, 0x5a, 0x40,
+    ];
+    ebcdic.extend([0xf1, 0xf2, 0xf3, 0x00, 0xff]);
+    let ebcdic = input_file("charset_ebcdic.bin", &ebcdic);
+    assert_eq!(
+        run(&[ebcdic.to_str().unwrap(), "x", "hex", "--charset", "ebcdic"]),
+        "00000000  c8 85 93 93  96 6b 40 e6  96 99 93 84  5a 40 f1 f2   |Hello, World! 12|\n\
+         00000010  f3 00 ff                                             |3..             |\n\n"
+    );
+}
+
+#[test]
+fn dumps_are_annotated_with_a_layout() {
+    let input = input_file(
+        "layout.bin",
+        b"\x7fELF\x02\x01\xfe\xff\x00\x00\x80\x3fabc\x00\x34\x12",
+    );
+    let input = input.to_str().unwrap();
+    let layout = input_file(
+        "layout.txt",
+        b"# A made-up header\n\
+          magic    0x00 4 bytes\n\
+          class    4 1 u8\n\
+          delta    6 2 i16\n\
+          scale    0x08 4 f32\n\
+          name     12 4 str   # NUL padded\n\
+          \n\
+          tail     17 4 u32\n\
+          version  16 2 u16be\n\
+          huge     17 0xffffffffffffffff bytes\n",
+    );
+    let dump = run(&[input, "x", "hex", "--layout", layout.to_str().unwrap()]);
+    assert_eq!(
+        dump.lines().collect::<Vec<&str>>(),
+        [
+            "00000000  7f 45 4c 46  02 01 fe ff  00 00 80 3f  61 62 63 00   |.ELF.......?abc.|",
+            "          ^ magic: bytes[4] = 7f 45 4c 46",
+            "                       ^ class: u8[1] = 2 (0x2)",
+            "                             ^ delta: i16[2] = -2",
+            "                                    ^ scale: f32[4] = 1",
+            "                                                 ^ name: str[4] = \"abc\"",
+            "00000010  34 12                                                |4.              |",
+            "          ^ version: u16be[2] = 13330 (0x3412)",
+            "             ^ tail: u32[4] = <out of range>",
+            "             ^ huge: bytes[18446744073709551615] = <out of range>",
+            "",
+        ]
+    );
+
+    for (line, error) in [
+        ("x 0 2 u8", "line 1: u8 fields are 1 byte(s) long"),
+        ("\nx 0 4 u64be", "line 2: u64be fields are 8 byte(s) long"),
+        ("x 0 16 u128", "line 1: unknown type u128"),
+        ("x 0 2", "line 1: expected `name offset size type`"),
+        ("x 0x 2 u16", "line 1: invalid offset"),
+        ("x 0 two u16", "line 1: invalid size"),
+    ] {
+        let layout = input_file("layout_error.txt", line.as_bytes());
+        assert_eq!(
+            run_error(&[input, "x", "hex", "--layout", layout.to_str().unwrap()]),
+            (1, format!("Invalid layout file: {}\n", error))
+        );
+    }
+}
+
+#[test]
+fn dumps_can_be_restricted_to_a_range() {
+    let input = input_file("range.bin", b"0123456789abcdefghij");
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[input, "x", "hex", "-s", "2", "-l", "3"]),
+        "00000002  32 33 34                                             |234             |\n\n"
+    );
+    assert_eq!(
+        run(&[input, "x", "hex", "--offset", "0x10", "--length", "100"]),
+        "00000010  67 68 69 6a                                          |ghij            |\n\n"
+    );
+    assert_eq!(run(&[input, "x", "base64", "-l", "3"]), "MDEy\n\n");
+    assert_eq!(run(&[input, "x", "hex", "-s", "20"]), "\n");
+    for args in [["hex", "-s", "21"], ["base64", "-s", "100"]] {
+        let mut command = vec![input, "x"];
+        command.extend(args);
+        assert_eq!(
+            run_error(&command),
+            (
+                2,
+                format!(
+                    "--offset {} is past the end of the input, which is 20 bytes long\n",
+                    args[2]
+                )
+            )
+        );
+    }
+    assert_eq!(
+        run_error(&[input, "x", "hex", "-l", "ten"]),
+        (2, "Invalid value for --length: ten\n".to_string())
+    );
+}
+
+#[test]
+fn hashes_match_their_reference_vectors() {
+    let vectors: [(&str, &[u8], [&str; 4]); 5] = [
+        (
+            "hash_empty.bin",
+            b"",
+            [
+                "d41d8cd98f00b204e9800998ecf8427e",
+                "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                "00000000",
+            ],
+        ),
+        (
+            "hash_abc.bin",
+            b"abc",
+            [
+                "900150983cd24fb0d6963f7d28e17f72",
+                "a9993e364706816aba3e25717850c26c9cd0d89d",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                "352441c2",
+            ],
+        ),
+        (
+            "hash_check.bin",
+            b"123456789",
+            [
+                "25f9e794323b453885f5181f1b624d0b",
+                "f7c3bc1d808e04732adf679965ccc34ca7ae3441",
+                "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225",
+                "cbf43926",
+            ],
+        ),
+        // 56 bytes, whose padding spills over a second block.
+        (
+            "hash_two_blocks.bin",
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            [
+                "8215ef0796a20bcaaae116d3876c664a",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+                "171a3f5f",
+            ],
+        ),
+        (
+            "hash_long.bin",
+            &[b'a'; 1000],
+            [
+                "cabe45dcc9ae5b66ba86600cca6b8ba8",
+                "291e9a6c66994949b57ba5e650361e98fc36b1ba",
+                "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3",
+                "9a38da03",
+            ],
+        ),
+    ];
+    for (file_name, binary, [md5, sha1, sha256, crc32]) in vectors {
+        let input = input_file(file_name, binary);
+        assert_eq!(
+            run(&[input.to_str().unwrap(), "x", "hash"]),
+            format!(
+                "size    {}\nmd5     {}\nsha1    {}\nsha256  {}\ncrc32   {}\n\n",
+                binary.len(),
+                md5,
+                sha1,
+                sha256,
+                crc32
+            )
+        );
+    }
+}
+
+#[test]
+fn patterns_are_found_with_their_context() {
+    let mut binary = vec![0xde, 0xad, 0xbe, 0xef, 0xaa, 0xaa, 0xaa];
+    binary.extend(0..=12);
+    binary.extend([0xde, 0xad, 0xbf, 0xef]);
+    let input = input_file("find.bin", &binary);
+    let input = input.to_str().unwrap();
+
+    // The context is clipped at both edges of the file.
+    assert_eq!(
+        run(&[input, "de ad b? ef", "find", "--context", "2"]),
+        "match at 0x00000000 (0)\n\
+         00000000  de ad be ef  aa aa                                   |......          |\n\n\
+         match at 0x00000014 (20)\n\
+         00000010  09 0a 0b 0c  de ad bf ef                             |........        |\n\n\
+         2 match(es)\n\n"
+    );
+    // Matches can overlap.
+    assert_eq!(
+        run(&[input, "0xaa,0xaa", "find", "--context", "0"]),
+        "match at 0x00000004 (4)\n\
+         00000000  de ad be ef  aa aa                                   |......          |\n\n\
+         match at 0x00000005 (5)\n\
+         00000000  de ad be ef  aa aa aa                                |.......         |\n\n\
+         2 match(es)\n\n"
+    );
+    // The search starts at -s, but its context doesn't.
+    assert_eq!(
+        run(&[input, "dead", "find", "-s", "1", "--context", "24"]),
+        "match at 0x00000014 (20)\n\
+         00000000  de ad be ef  aa aa aa 00  01 02 03 04  05 06 07 08   |................|\n\
+         00000010  09 0a 0b 0c  de ad bf ef                             |........        |\n\n\
+         1 match(es)\n\n"
+    );
+    assert_eq!(run(&[input, "ff", "find"]), "0 match(es)\n\n");
+    assert_eq!(
+        run_error(&[input, "de a", "find"]),
+        (
+            1,
+            "Invalid pattern: \"a\" is not made of whole bytes\n".to_string()
+        )
+    );
+    assert_eq!(
+        run_error(&[input, "dg", "find"]),
+        (1, "Invalid pattern: invalid hex digit 'g'\n".to_string())
+    );
+
+    let text = input_file("find.txt", b"say hello, hallo");
+    assert_eq!(
+        run(&[
+            text.to_str().unwrap(),
+            "h?llo",
+            "find",
+            "--string",
+            "--context",
+            "1"
+        ]),
+        "match at 0x00000004 (4)\n\
+         00000000  73 61 79 20  68 65 6c 6c  6f 2c                      |say hello,      |\n\n\
+         match at 0x0000000b (11)\n\
+         00000000  73 61 79 20  68 65 6c 6c  6f 2c 20 68  61 6c 6c 6f   |say hello, hallo|\n\n\
+         2 match(es)\n\n"
+    );
+}
+
+#[test]
+fn hex_dumps_decode_back_to_their_bytes() {
+    let binary = (0..=255).chain([0; 40]).collect::<Vec<u8>>();
+    let input = input_file("decode_hex.bin", &binary);
+    let dump = input_file(
+        "decode_hex.txt",
+        run(&[input.to_str().unwrap(), "x", "hex"]).as_bytes(),
+    );
+    let output = input.with_file_name("decode_hex.out");
+    run(&[
+        dump.to_str().unwrap(),
+        "x",
+        "decode-hex",
+        "4",
+        output.to_str().unwrap(),
+    ]);
+    assert_eq!(std::fs::read(&output).unwrap(), binary);
+
+    let xxd = input_file(
+        "decode_xxd.txt",
+        b"00000000: 6162 6364  abcd\n*\n00000010: 31    1\n",
+    );
+    assert_eq!(
+        run(&[xxd.to_str().unwrap(), "x", "unhex"]),
+        "abcdabcdabcdabcd1"
+    );
+    let gap = input_file("decode_gap.txt", b"00000000: 6162\n00000010: 63\n");
+    assert_eq!(
+        run_error(&[gap.to_str().unwrap(), "x", "unhex"]),
+        (
+            1,
+            format!(
+                "{} can't be decoded: line 2: expected the offset 0x2, found 0x10\n",
+                gap.display()
+            )
+        )
+    );
+    // A squeezed line can't grow the output past --max-size.
+    let squeezed = input_file("decode_squeezed.txt", b"00000000: 6162\n*\nffffffff: 63\n");
+    assert_eq!(
+        run_error(&[squeezed.to_str().unwrap(), "x", "unhex", "--max-size", "16"]),
+        (
+            1,
+            format!(
+                "{} can't be decoded: line 3: the dump spans 4294967295 bytes, more than the \
+                 --max-size of 16 bytes\n",
+                squeezed.display()
+            )
+        )
+    );
+    let overflow = input_file(
+        "decode_overflow.txt",
+        b"ffffffffffffffff: 6162\nffffffffffffffff: 63\n",
+    );
+    assert_eq!(
+        run_error(&[overflow.to_str().unwrap(), "x", "unhex"]),
+        (
+            1,
+            format!(
+                "{} can't be decoded: line 2: the dump overflows past the offset \
+                 0xffffffffffffffff\n",
+                overflow.display()
+            )
+        )
+    );
+}
+
+#[test]
+fn arrays_decode_back_to_their_bytes() {
+    let source = input_file(
+        "decode_array.h",
+        b"#define LOGO_LEN 3\nconst uint8_t LOGO[] = {0x61, 0x62, 0x63};\n",
+    );
+    let source = source.to_str().unwrap();
+    assert_eq!(run(&[source, "LOGO", "decode-array"]), "abc");
+    assert_eq!(
+        run_error(&[source, "LOGO_LEN", "decode-array"]),
+        (
+            1,
+            format!(
+                "{} can't be decoded: LOGO_LEN isn't initialized with an array\n",
+                source
+            )
+        )
+    );
+    assert_eq!(
+        run_error(&[source, "ICON", "decode-array"]),
+        (
+            1,
+            format!("{} can't be decoded: No constant named ICON\n", source)
+        )
+    );
+}
+
+#[test]
+fn record_files_are_read_by_address() {
+    let ihex = input_file(
+        "records.hex",
+        b":020000040010EA\n:0100000001FE\n:0100030002FA\n:00000001FF\n",
+    );
+    let ihex = ihex.to_str().unwrap();
+    assert_eq!(
+        run(&[ihex, "x", "rust", "--input-format", "ihex"]),
+        "const X: [u8; 4] = [\n    0x01, 0xff, 0xff, 0x02\n];\n\n"
+    );
+    assert_eq!(
+        run(&[ihex, "x", "rust", "--input-format", "ihex", "--fill", "0"]),
+        "const X: [u8; 4] = [\n    0x01, 0x00, 0x00, 0x02\n];\n\n"
+    );
+    let srec = input_file(
+        "records.srec",
+        b"S1050100000AEF\nS104010303F4\nS9030100FB\n",
+    );
+    let srec = srec.to_str().unwrap();
+    assert_eq!(
+        run(&[srec, "x", "hexstring", "--input-format", "srec"]),
+        "000aff03\n\n"
+    );
+    let corrupted = input_file("corrupted.hex", b":0100000001FF\n");
+    assert_eq!(
+        run_error(&[
+            corrupted.to_str().unwrap(),
+            "x",
+            "c",
+            "--input-format",
+            "ihex"
+        ]),
+        (
+            1,
+            "Invalid Intel HEX input: line 1: :0100000001FF has a wrong checksum\n".to_string()
+        )
+    );
+    // A byte at 0 and one at 0xffff0000 would fill 4 GiB.
+    let sparse = input_file(
+        "sparse.hex",
+        b":0100000001FE\n:02000004FFFFFC\n:0100000002FD\n:00000001FF\n",
+    );
+    let sparse = sparse.to_str().unwrap();
+    assert_eq!(
+        run_error(&[sparse, "x", "c", "--input-format", "ihex"]),
+        (
+            1,
+            "Invalid Intel HEX input: the records span 0x0 to 0xffff0001, 4294901761 bytes, more \
+             than the --max-size of 16777216 bytes, split the file by address or raise --max-size\n"
+                .to_string()
+        )
+    );
+    let gap = input_file("gap.hex", b":0100000001FE\n:0100030002FA\n:00000001FF\n");
+    let gap = gap.to_str().unwrap();
+    let args = [
+        gap,
+        "x",
+        "hexstring",
+        "--input-format",
+        "ihex",
+        "--max-size",
+    ];
+    assert_eq!(run(&[&args[..], &["4"]].concat()), "01ffff02\n\n");
+    assert_eq!(run_error(&[&args[..], &["3"]].concat()).0, 1);
+}
+
+#[test]
+fn text_inputs_are_decoded() {
+    let base64 = input_file("input.b64", b"+/+/\n-_8\n");
+    let base64 = base64.to_str().unwrap();
+    assert_eq!(
+        run(&[base64, "x", "hexstring", "--input-format", "base64"]),
+        "fbffbffbff\n\n"
+    );
+    let hex = input_file("input.hex.txt", b"00 ff\n0a");
+    let hex = hex.to_str().unwrap();
+    assert_eq!(
+        run(&[hex, "x", "rust", "--input-format", "hexstring"]),
+        "const X: [u8; 3] = [\n    0x00, 0xff, 0x0a\n];\n\n"
+    );
+    assert_eq!(
+        run_error(&[hex, "x", "rust", "--input-format", "base32"]),
+        (
+            5,
+            "Unknown input format: base32, expected raw, ihex, srec, base64 or hexstring\n"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn generated_files_can_be_verified() {
+    let input = input_file("verify_command.bin", b"abc");
+    let input = input.to_str().unwrap();
+    let source = input_file(
+        "verify_command.rs",
+        b"const X: [u8; 3] = [0x61, 0x62, 0x63];\n",
+    );
+    let source = source.to_str().unwrap();
+    assert_eq!(
+        run_with_status(&["verify", input, source, "rust"]),
+        (0, String::new())
+    );
+    assert_eq!(
+        run_stderr(&["verify", input, source]),
+        "Verified 3 bytes, sha256 \
+         ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n"
+    );
+
+    let stale = input_file(
+        "verify_stale.rs",
+        b"const X: [u8; 3] = [0x61, 0x62, 0x64];\n",
+    );
+    let (status, out) = run_error(&["verify", input, stale.to_str().unwrap()]);
+    assert_eq!(status, 1);
+    assert!(out.starts_with(
+        "Verification failed, the generated array differs from the input from offset 2\n"
+    ));
+
+    // Without a conversion type, the language is guessed from the extension.
+    for (conversion_type, extension) in [("ocaml", "ml"), ("verilog", "v"), ("sql", "sql")] {
+        let source = input_file(&format!("verify_guessed.{}", extension), b"");
+        let source = source.to_str().unwrap();
+        assert_eq!(run(&[input, "x", conversion_type, source]), "");
+        assert_eq!(
+            run_with_status(&["verify", input, source]),
+            (0, String::new())
+        );
+    }
+    // .m files are taken for Objective-C, MATLAB needs its conversion type.
+    let source = input_file("verify_objc.m", b"");
+    let source = source.to_str().unwrap();
+    assert_eq!(run(&[input, "x", "objc", source]), "");
+    assert_eq!(
+        run_with_status(&["verify", input, source]),
+        (0, String::new())
+    );
+    assert_eq!(run(&[input, "x", "matlab", source]), "");
+    assert_eq!(
+        run_error(&["verify", input, source]),
+        (
+            1,
+            "Verification failed, no byte array found, pass the conversion type\n".to_string()
+        )
+    );
+    assert_eq!(
+        run_with_status(&["verify", input, source, "matlab"]),
+        (0, String::new())
+    );
+    let text = input_file("verify_text.txt", b"abc\n");
+    assert_eq!(
+        run_error(&["verify", input, text.to_str().unwrap()]),
+        (
+            1,
+            "Verification failed, no byte array found, pass the conversion type\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn line_endings_reach_the_last_line() {
+    let input = input_file("line_endings.bin", b"ab");
+    let input = input.to_str().unwrap();
+    assert_eq!(
+        run(&[input, "x", "rust", "2", "--line-ending", "crlf"]),
+        "const X: [u8; 2] = [\r\n  0x61, 0x62\r\n];\r\n\r\n"
+    );
+}
+
+#[test]
+fn arguments_can_be_named() {
+    let input = input_file("named_flags.bin", b"ab");
+    let input = input.to_str().unwrap();
+    let expected = "const X: [u8; 2] = [\n  0x61, 0x62\n];\n\n";
+    assert_eq!(run(&[input, "x", "rust", "2"]), expected);
+    assert_eq!(
+        run(&[input, "--format", "rust", "--tab-size", "2", "--name", "x"]),
+        expected
+    );
+
+    let output = input_file("named_flags.rs", b"");
+    let output = output.to_str().unwrap();
+    assert_eq!(run(&[input, "x", "rust", output]), "");
+    assert_eq!(
+        std::fs::read_to_string(output).unwrap(),
+        "const X: [u8; 2] = [\n    0x61, 0x62\n];\n"
+    );
+    assert_eq!(
+        run(&[input, "--name", "x", "--format", "c", "-o", output]),
+        ""
+    );
+    assert!(std::fs::read_to_string(output)
+        .unwrap()
+        .starts_with("const unsigned char X[]"));
+
+    assert_eq!(
+        run_error(&[input, "x", "rust", "2", output, "extra"]),
+        (2, "Unexpected argument: extra\n".to_string())
+    );
+    assert_eq!(
+        run(&["--version"]),
+        format!("bin2const {}\n", env!("CARGO_PKG_VERSION"))
+    );
+    assert!(run(&["-h"]).starts_with("Usage: bin2const"));
+}
+
+#[test]
+fn unknown_options_are_rejected() {
+    let input = input_file("unknown_options.bin", b"ab");
+    let input = input.to_str().unwrap();
+    let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("keep");
+    let _ = std::fs::remove_file(&output);
+    assert_eq!(
+        run_error(&[input, "x", "rust", "--name-case", output.to_str().unwrap()]),
+        (2, "Unknown option: --name-case, see --help\n".to_string())
+    );
+    assert!(!output.exists());
+    assert_eq!(
+        run_error(&[input, "x", "rust", "-x"]),
+        (2, "Unknown option: -x, see --help\n".to_string())
+    );
+    assert_eq!(
+        run_error(&[input, "x", "rust", "--colour=always"]),
+        (2, "Unknown option: --colour, see --help\n".to_string())
+    );
+}
+
+#[test]
+fn input_can_be_read_from_stdin() {
+    let expected = "const X: [u8; 2] = [\n    0x61, 0x62\n];\n\n";
+    assert_eq!(run_with_stdin(&["-", "x", "rust"], b"ab"), expected);
+    assert_eq!(
+        run_with_stdin(&["--name", "x", "--format", "rust"], b"ab"),
+        expected
+    );
+    assert_eq!(
+        run_error(&["-", "x", "rust-include"]),
+        (
+            1,
+            "rust-include includes a file, it can't read stdin\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn octal_dump_squeezes_repeated_lines() {
+    let input = input_file("octal_squeeze.bin", &[0; 40]);
+    let input = input.to_str().unwrap();
+    let zeros = " 000".repeat(16);
+    assert_eq!(
+        run(&[input, "x", "od"]),
+        format!("0000000{}\n*\n0000040{}\n0000050\n\n", zeros, &zeros[..32])
+    );
+}
+
+#[test]
+fn closed_pipes_end_the_output_quietly() {
+    // Far more than a pipe holds, so bin2const is still writing when the pipe gets closed.
+    let binary = (0..1 << 20).map(|i| i as u8).collect::<Vec<u8>>();
+    let input = input_file("broken_pipe.bin", &binary);
+    let dump = input_file(
+        "broken_pipe.txt",
+        run(&[input.to_str().unwrap(), "x", "hex"]).as_bytes(),
+    );
+    for args in [
+        [input.to_str().unwrap(), "x", "hex"],
+        [dump.to_str().unwrap(), "x", "decode-hex"],
+    ] {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_bin2const"))
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = [0; 16];
+        std::io::Read::read_exact(child.stdout.as_mut().unwrap(), &mut line).unwrap();
+        drop(child.stdout.take());
+        let output = child.wait_with_output().unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8(output.stderr).unwrap(), "");
+    }
+}
+
+#[test]
+fn errors_exit_with_their_kind() {
+    let input = input_file("exit_codes.bin", b"ab");
+    let input = input.to_str().unwrap();
+    let (status, out) = run_error(&[input]);
+    assert_eq!(status, 2);
+    assert!(out.starts_with("Usage: bin2const"));
+    assert_eq!(
+        run_error(&[input, "x", "rust", "--max-depth", "0"]),
+        (2, "--max-depth must be at least 1\n".to_string())
+    );
+
+    let missing = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("exit_codes_missing.bin");
+    let (status, out) = run_error(&[missing.to_str().unwrap(), "x", "rust"]);
+    assert_eq!(status, 3);
+    assert!(out.starts_with("Error while reading"));
+
+    let unwritable = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("exit_codes/missing/out.rs");
+    let (status, out) = run_error(&[input, "x", "rust", "4", unwritable.to_str().unwrap()]);
+    assert_eq!(status, 4);
+    assert!(out.starts_with("Error while writing to"));
+
+    assert_eq!(
+        run_error(&[input, "x", "cobol"]),
+        (5, "Unknown conversion type: cobol\n".to_string())
+    );
+}
+
+#[test]
+fn forged_image_sizes_are_rejected() {
+    // A BMP header declaring 2000000000x2000000000 pixels of 1 bit, with its palette and no data.
+    let mut bmp = b"BM6\0\0\0\0\0\0\x006\0\0\0(\0\0\0\0\x945w\0\x945w\x01\0\x01\0".to_vec();
+    bmp.resize(54, 0);
+    bmp.extend_from_slice(&[0, 0, 0, 0, 0xff, 0xff, 0xff, 0]);
+    let bmp = input_file("forged.bmp", &bmp);
+    assert_eq!(
+        run_error(&[
+            bmp.to_str().unwrap(),
+            "x",
+            "c",
+            "--image",
+            "--pixels",
+            "1bpp"
+        ]),
+        (1, "The BMP image data is truncated\n".to_string())
+    );
+
+    // A GIF of a single pixel on a 65535x65535 screen.
+    let gif = input_file(
+        "forged.gif",
+        b"GIF89a\xff\xff\xff\xff\x80\0\0\0\0\0\xff\xff\xff,\0\0\0\0\x01\0\x01\0\0\x02\x02D\x01\0;",
+    );
+    assert_eq!(
+        run_error(&[
+            gif.to_str().unwrap(),
+            "x",
+            "c",
+            "--image",
+            "--pixels",
+            "1bpp"
+        ]),
+        (
+            1,
+            "The GIF image data is truncated, it holds 1 pixels for a 65535x65535 image\n"
+                .to_string()
+        )
+    );
+
+    // 1x1 PNGs whose header or palette doesn't follow the spec, their chunk CRCs left out.
+    let png = |depth: u8, color: u8, palette: &[u8]| {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x01\0\0\0\x01".to_vec();
+        png.extend_from_slice(&[depth, color, 0, 0, 0, 0, 0, 0, 0]);
+        png.extend_from_slice(&(palette.len() as u32).to_be_bytes());
+        png.extend_from_slice(b"PLTE");
+        png.extend_from_slice(palette);
+        png.extend_from_slice(b"\0\0\0\0\0\0\0\0IEND\0\0\0\0");
+        png
+    };
+    for (depth, color, palette, error) in [
+        (
+            0,
+            0,
+            &[0, 0, 0][..],
+            "Invalid PNG bit depth 0 for the color type 0",
+        ),
+        (
+            12,
+            0,
+            &[0, 0, 0],
+            "Invalid PNG bit depth 12 for the color type 0",
+        ),
+        (
+            5,
+            2,
+            &[0, 0, 0],
+            "Invalid PNG bit depth 5 for the color type 2",
+        ),
+        (
+            16,
+            3,
+            &[0, 0, 0],
+            "Invalid PNG bit depth 16 for the color type 3",
+        ),
+        (
+            8,
+            3,
+            &[0, 0, 0, 0],
+            "The PNG palette isn't made of RGB triplets",
+        ),
+    ] {
+        let forged = input_file("forged.png", &png(depth, color, palette));
+        let forged = forged.to_str().unwrap();
+        assert_eq!(
+            run_error(&[forged, "x", "c", "--pixels", "1bpp"]),
+            (1, format!("{}\n", error))
+        );
+        // --image alone only reads the header.
+        if palette.len() == 3 {
+            assert_eq!(
+                run_error(&[forged, "x", "c", "--image"]),
+                (1, format!("{}\n", error))
+            );
+        }
+    }
+}
@@ -0,0 +1,717 @@
+//! Compares the output of every emitter to the files in tests/golden.
+//! Run with `BIN2CONST_BLESS=1 cargo test` to regenerate the files after an intended change.
+use std::path::PathBuf;
+use std::process::Command;
+
+/// The bytes 0x00 to 0x13 then 0x7f, 0x80 and 0xff, long enough to wrap and covering signedness.
+fn golden_input() -> Vec<u8> {
+    (0x00..=0x13).chain([0x7f, 0x80, 0xff]).collect()
+}
+
+/// Runs bin2const on `input` and compares its output to tests/golden/`golden`.
+fn check(input: &[u8], args: &[&str], golden: &str) {
+    check_file(input, &format!("{}.bin", golden), args, golden);
+}
+
+/// Same as check, with the input written to a file named `file_name`, for the conversions
+/// depending on the extension of their input.
+fn check_file(input: &[u8], file_name: &str, args: &[&str], golden: &str) {
+    let input_path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(file_name);
+    std::fs::write(&input_path, input).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_bin2const"))
+        .arg(&input_path)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "bin2const failed for {}: {}",
+        golden,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    let golden_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(golden);
+    if std::env::var_os("BIN2CONST_BLESS").is_some() {
+        std::fs::write(&golden_path, &output).unwrap();
+    }
+    let expected = std::fs::read_to_string(&golden_path)
+        .unwrap_or_else(|e| panic!("{}: {}", golden_path.display(), e));
+    assert_eq!(
+        output,
+        expected,
+        "output differs from {}",
+        golden_path.display()
+    );
+}
+
+#[test]
+fn c() {
+    check(&golden_input(), &["test_txt", "c"], "c.txt");
+}
+
+#[test]
+fn c_define() {
+    check(&golden_input(), &["test_txt", "cdef"], "cdef.txt");
+}
+
+#[test]
+fn rust() {
+    check(&golden_input(), &["test_txt", "rust"], "rust.txt");
+}
+
+#[test]
+fn csharp() {
+    check(&golden_input(), &["test_txt", "csharp"], "csharp.txt");
+}
+
+#[test]
+fn python() {
+    check(&golden_input(), &["test_txt", "python"], "python.txt");
+}
+
+#[test]
+fn javascript() {
+    check(
+        &golden_input(),
+        &["test_txt", "javascript"],
+        "javascript.txt",
+    );
+}
+
+#[test]
+fn go() {
+    check(&golden_input(), &["test_txt", "go"], "go.txt");
+}
+
+#[test]
+fn java() {
+    check(&golden_input(), &["test_txt", "java"], "java.txt");
+}
+
+#[test]
+fn ca65() {
+    check(&golden_input(), &["test_txt", "ca65"], "ca65.txt");
+}
+
+#[test]
+fn acme() {
+    check(&golden_input(), &["test_txt", "acme"], "acme.txt");
+}
+
+#[test]
+fn z80() {
+    check(&golden_input(), &["test_txt", "z80asm"], "z80asm.txt");
+}
+
+#[test]
+fn zig() {
+    check(&golden_input(), &["test_txt", "zig"], "zig.txt");
+}
+
+#[test]
+fn swift() {
+    check(&golden_input(), &["test_txt", "swift"], "swift.txt");
+}
+
+#[test]
+fn swift_data() {
+    check(
+        &golden_input(),
+        &["test_txt", "swift_data"],
+        "swift_data.txt",
+    );
+}
+
+#[test]
+fn kotlin() {
+    check(&golden_input(), &["test_txt", "kotlin"], "kotlin.txt");
+}
+
+#[test]
+fn nim() {
+    check(&golden_input(), &["test_txt", "nim"], "nim.txt");
+}
+
+#[test]
+fn d() {
+    check(&golden_input(), &["test_txt", "d"], "d.txt");
+}
+
+#[test]
+fn pascal() {
+    check(&golden_input(), &["test_txt", "pascal"], "pascal.txt");
+}
+
+#[test]
+fn ada() {
+    check(&golden_input(), &["test_txt", "ada"], "ada.txt");
+}
+
+#[test]
+fn nasm() {
+    check(&golden_input(), &["test_txt", "nasm"], "nasm.txt");
+}
+
+#[test]
+fn gas() {
+    check(&golden_input(), &["test_txt", "gas"], "gas.txt");
+}
+
+#[test]
+fn lua() {
+    check(&golden_input(), &["test_txt", "lua"], "lua.txt");
+}
+
+#[test]
+fn lua_table() {
+    check(&golden_input(), &["test_txt", "lua_table"], "lua_table.txt");
+}
+
+#[test]
+fn php() {
+    check(&golden_input(), &["test_txt", "php"], "php.txt");
+}
+
+#[test]
+fn php_array() {
+    check(&golden_input(), &["test_txt", "php_array"], "php_array.txt");
+}
+
+#[test]
+fn ruby() {
+    check(&golden_input(), &["test_txt", "ruby"], "ruby.txt");
+}
+
+#[test]
+fn ruby_string() {
+    check(
+        &golden_input(),
+        &["test_txt", "ruby_string"],
+        "ruby_string.txt",
+    );
+}
+
+#[test]
+fn perl() {
+    check(&golden_input(), &["test_txt", "perl"], "perl.txt");
+}
+
+#[test]
+fn haskell() {
+    check(&golden_input(), &["test_txt", "haskell"], "haskell.txt");
+}
+
+#[test]
+fn ocaml() {
+    check(&golden_input(), &["test_txt", "ocaml"], "ocaml.txt");
+}
+
+#[test]
+fn elixir() {
+    check(&golden_input(), &["test_txt", "elixir"], "elixir.txt");
+}
+
+#[test]
+fn erlang() {
+    check(&golden_input(), &["test_txt", "erlang"], "erlang.txt");
+}
+
+#[test]
+fn scala() {
+    check(&golden_input(), &["test_txt", "scala"], "scala.txt");
+}
+
+#[test]
+fn dart() {
+    check(&golden_input(), &["test_txt", "dart"], "dart.txt");
+}
+
+#[test]
+fn julia() {
+    check(&golden_input(), &["test_txt", "julia"], "julia.txt");
+}
+
+#[test]
+fn r() {
+    check(&golden_input(), &["test_txt", "r"], "r.txt");
+}
+
+#[test]
+fn matlab() {
+    check(&golden_input(), &["test_txt", "matlab"], "matlab.txt");
+}
+
+#[test]
+fn objc() {
+    check(&golden_input(), &["test_txt", "objc"], "objc.txt");
+}
+
+#[test]
+fn verilog() {
+    check(&golden_input(), &["test_txt", "verilog"], "verilog.txt");
+}
+
+#[test]
+fn vhdl() {
+    check(&golden_input(), &["test_txt", "vhdl"], "vhdl.txt");
+}
+
+#[test]
+fn arduino() {
+    check(&golden_input(), &["test_txt", "arduino"], "arduino.txt");
+}
+
+#[test]
+fn powershell() {
+    check(
+        &golden_input(),
+        &["test_txt", "powershell"],
+        "powershell.txt",
+    );
+}
+
+#[test]
+fn bash() {
+    check(&golden_input(), &["test_txt", "bash"], "bash.txt");
+}
+
+#[test]
+fn sh() {
+    check(&golden_input(), &["test_txt", "sh"], "sh.txt");
+}
+
+#[test]
+fn sql() {
+    check(&golden_input(), &["test_txt", "sql"], "sql.txt");
+}
+
+#[test]
+fn postgres() {
+    check(&golden_input(), &["test_txt", "postgres"], "postgres.txt");
+}
+
+#[test]
+fn vbnet() {
+    check(&golden_input(), &["test_txt", "vbnet"], "vbnet.txt");
+}
+
+#[test]
+fn gdscript() {
+    check(&golden_input(), &["test_txt", "gdscript"], "gdscript.txt");
+}
+
+#[test]
+fn gdscript3() {
+    check(&golden_input(), &["test_txt", "gdscript3"], "gdscript3.txt");
+}
+
+#[test]
+fn fsharp() {
+    check(&golden_input(), &["test_txt", "fsharp"], "fsharp.txt");
+}
+
+#[test]
+fn fortran() {
+    check(&golden_input(), &["test_txt", "fortran"], "fortran.txt");
+}
+
+#[test]
+fn crystal() {
+    check(&golden_input(), &["test_txt", "crystal"], "crystal.txt");
+}
+
+#[test]
+fn tcl() {
+    check(&golden_input(), &["test_txt", "tcl"], "tcl.txt");
+}
+
+#[test]
+fn lisp() {
+    check(&golden_input(), &["test_txt", "lisp"], "lisp.txt");
+}
+
+#[test]
+fn scheme() {
+    check(&golden_input(), &["test_txt", "scheme"], "scheme.txt");
+}
+
+#[test]
+fn wat() {
+    check(&golden_input(), &["test_txt", "wat"], "wat.txt");
+}
+
+#[test]
+fn typescript() {
+    check(
+        &golden_input(),
+        &["test_txt", "typescript"],
+        "typescript.txt",
+    );
+}
+
+#[test]
+fn groovy() {
+    check(&golden_input(), &["test_txt", "groovy"], "groovy.txt");
+}
+
+#[test]
+fn solidity() {
+    check(&golden_input(), &["test_txt", "solidity"], "solidity.txt");
+}
+
+#[test]
+fn c_string() {
+    check(&golden_input(), &["test_txt", "c_string"], "c_string.txt");
+}
+
+#[test]
+fn rust_bytes() {
+    check(
+        &golden_input(),
+        &["test_txt", "rust_bytes"],
+        "rust_bytes.txt",
+    );
+}
+
+#[test]
+fn yaml() {
+    check(&golden_input(), &["test_txt", "yaml"], "yaml.txt");
+}
+
+#[test]
+fn toml() {
+    check(&golden_input(), &["test_txt", "toml"], "toml.txt");
+}
+
+#[test]
+fn base64_constants() {
+    for language in ["yaml", "toml"] {
+        check(
+            &golden_input(),
+            &["test_txt", language, "4", "--base64"],
+            &format!("base64_{}.txt", language),
+        );
+    }
+}
+
+#[test]
+fn tab_size() {
+    check(
+        &golden_input(),
+        &["test_txt", "rust", "2"],
+        "rust_tab_size_2.txt",
+    );
+}
+
+#[test]
+fn hex() {
+    check(&golden_input(), &["test_txt", "hex"], "hex.txt");
+}
+
+#[test]
+fn base64() {
+    check(
+        &golden_input(),
+        &["test_txt", "base64", "4", "--wrap", "16"],
+        "base64.txt",
+    );
+}
+
+#[test]
+fn base32() {
+    check(
+        &golden_input(),
+        &["test_txt", "base32", "4", "--wrap", "16"],
+        "base32.txt",
+    );
+}
+
+#[test]
+fn ascii85() {
+    check(
+        &golden_input(),
+        &["test_txt", "ascii85", "4", "--wrap", "16", "--frame"],
+        "ascii85.txt",
+    );
+}
+
+#[test]
+fn hexstring() {
+    check(&golden_input(), &["test_txt", "hexstring"], "hexstring.txt");
+}
+
+#[test]
+fn uuencode() {
+    check(&golden_input(), &["test.txt", "uuencode"], "uuencode.txt");
+}
+
+#[test]
+fn srec() {
+    check(
+        &golden_input(),
+        &["test_txt", "srec", "4", "--base-address", "0xfff8"],
+        "srec.txt",
+    );
+}
+
+#[test]
+fn xxd() {
+    check(
+        &golden_input(),
+        &["test_txt", "hex", "4", "--xxd"],
+        "xxd.txt",
+    );
+}
+
+#[test]
+fn octal() {
+    check(&golden_input(), &["test_txt", "octal"], "octal.txt");
+}
+
+#[test]
+fn csv() {
+    check(&golden_input(), &["test_txt", "csv"], "csv.txt");
+    check(b"a,\"\t\n", &["test_txt", "csv"], "csv_quoted.txt");
+    check(b"a,\"\t\n", &["test_txt", "tsv"], "tsv_escaped.txt");
+}
+
+#[test]
+fn html() {
+    check(
+        &golden_input(),
+        &["test_txt", "html", "4", "--color"],
+        "html.txt",
+    );
+}
+
+#[test]
+fn binary() {
+    check(&golden_input(), &["test_txt", "bin"], "bin.txt");
+}
+
+#[test]
+fn empty_input() {
+    for language in [
+        "c",
+        "cdef",
+        "rust",
+        "csharp",
+        "python",
+        "javascript",
+        "go",
+        "java",
+        "ca65",
+        "acme",
+        "z80asm",
+        "zig",
+        "swift",
+        "swift_data",
+        "kotlin",
+        "nim",
+        "d",
+        "pascal",
+        "ada",
+        "nasm",
+        "gas",
+        "lua",
+        "lua_table",
+        "php",
+        "php_array",
+        "ruby",
+        "ruby_string",
+        "perl",
+        "haskell",
+        "ocaml",
+        "elixir",
+        "erlang",
+        "scala",
+        "dart",
+        "julia",
+        "r",
+        "matlab",
+        "objc",
+        "verilog",
+        "vhdl",
+        "arduino",
+        "powershell",
+        "bash",
+        "sh",
+        "sql",
+        "postgres",
+        "vbnet",
+        "gdscript",
+        "gdscript3",
+        "fsharp",
+        "fortran",
+        "crystal",
+        "tcl",
+        "lisp",
+        "scheme",
+        "wat",
+        "typescript",
+        "groovy",
+        "solidity",
+        "c_string",
+        "rust_bytes",
+        "yaml",
+        "toml",
+    ] {
+        check(
+            &[],
+            &["test_txt", language],
+            &format!("empty_{}.txt", language),
+        );
+    }
+}
+
+#[test]
+fn emitted_tests() {
+    for language in ["c", "rust", "python"] {
+        check(
+            &golden_input(),
+            &["test_txt", language, "4", "--emit-tests"],
+            &format!("tests_{}.txt", language),
+        );
+    }
+}
+
+#[test]
+fn text() {
+    let input = "Hello \"world\"\n\ttab \\ ??= é\r\nend\"".as_bytes();
+    for language in ["c", "rust", "python"] {
+        check(
+            input,
+            &["test_txt", language, "4", "--text"],
+            &format!("text_{}.txt", language),
+        );
+        check(
+            input,
+            &["test_txt", language, "4", "--text", "--escape", "ascii"],
+            &format!("text_ascii_{}.txt", language),
+        );
+    }
+}
+
+#[test]
+fn lines() {
+    let input = "Hello \"world\"\r\n\ttab é\n\nend\n".as_bytes();
+    for language in ["c", "rust", "python"] {
+        check(
+            input,
+            &["test_txt", language, "4", "--lines"],
+            &format!("lines_{}.txt", language),
+        );
+    }
+}
+
+#[test]
+fn image() {
+    let input = include_bytes!("golden/image.png");
+    check(input, &["test_png", "c", "4", "--image"], "image_c.txt");
+    check(
+        input,
+        &["test_png", "rust", "4", "--pixels", "rgb565"],
+        "image_rgb565.txt",
+    );
+    check(
+        input,
+        &["test_png", "python", "4", "--pixels", "1bpp"],
+        "image_1bpp.txt",
+    );
+}
+
+#[test]
+fn bmp_image() {
+    // 3x2 pixels of 24 bits, stored bottom-up with padded rows.
+    let input = include_bytes!("golden/image.bmp");
+    check(input, &["test_bmp", "c", "4", "--image"], "bmp_c.txt");
+    check(
+        input,
+        &["test_bmp", "rust", "4", "--pixels", "rgb565"],
+        "bmp_rgb565.txt",
+    );
+    check(
+        input,
+        &["test_bmp", "python", "4", "--pixels", "1bpp"],
+        "bmp_1bpp.txt",
+    );
+}
+
+#[test]
+fn gif_image() {
+    // 3x2 pixels of a 4 colors palette.
+    let input = include_bytes!("golden/image.gif");
+    check(input, &["test_gif", "c", "4", "--image"], "gif_c.txt");
+    check(
+        input,
+        &["test_gif", "rust", "4", "--pixels", "rgb565"],
+        "gif_rgb565.txt",
+    );
+    check(
+        input,
+        &["test_gif", "python", "4", "--pixels", "1bpp"],
+        "gif_1bpp.txt",
+    );
+}
+
+#[test]
+fn wav() {
+    // 8000Hz mono 8 bits, with a LIST chunk of odd size before the samples.
+    let mut input = b"RIFF\x00\x00\x00\x00WAVEfmt \x10\x00\x00\x00\x01\x00\x01\x00".to_vec();
+    input.extend_from_slice(b"\x40\x1f\x00\x00\x40\x1f\x00\x00\x01\x00\x08\x00");
+    input.extend_from_slice(b"LIST\x03\x00\x00\x00abc\x00data\x04\x00\x00\x00\x80\xff\x80\x00");
+    check(&input, &["test_wav", "c", "4", "--wav"], "wav_c.txt");
+    check(&input, &["test_wav", "rust", "4", "--pcm"], "wav_pcm.txt");
+}
+
+#[test]
+fn font() {
+    let input = b"STARTFONT 2.1\nFONT -misc-fixed\nFAMILY_NAME \"Fixed\"\nENDFONT\n";
+    check(input, &["test_font", "c", "4", "--font"], "font_c.txt");
+    check(
+        input,
+        &["test_font", "rust", "4", "--font"],
+        "font_rust.txt",
+    );
+    check(
+        input,
+        &["test_font", "python", "4", "--font"],
+        "font_python.txt",
+    );
+    // The family name is escaped for the string templates of the language.
+    let input = b"STARTFONT 2.1\nFONT -misc-fixed\nFAMILY_NAME \"Price$Tag #{x}\"\nENDFONT\n";
+    check(
+        input,
+        &["test_font", "kotlin", "4", "--font"],
+        "font_kotlin.txt",
+    );
+    check(
+        input,
+        &["test_font", "elixir", "4", "--font"],
+        "font_elixir.txt",
+    );
+}
+
+#[test]
+fn webasset() {
+    let input = b"<!doctype html><p>Hello, hello, hello!</p>\n";
+    for language in ["c", "rust", "python"] {
+        check_file(
+            input,
+            "index.html",
+            &["index", language, "4", "--webasset"],
+            &format!("webasset_{}.txt", language),
+        );
+    }
+    // The languages without structs get separate constants.
+    check_file(
+        input,
+        "index.html",
+        &["index", "kotlin", "4", "--webasset"],
+        "webasset_kotlin.txt",
+    );
+}